@@ -12,18 +12,16 @@ use cohete::{
 };
 
 fn make_device(id: &str, model: JetsonModel, ip: Option<&str>) -> JetsonDevice {
-    JetsonDevice {
-        info: DeviceInfo {
-            id: id.to_string(),
-            model,
-            connection: match ip {
-                Some(addr) => ConnectionMethod::Ethernet(addr.parse().unwrap()),
-                None => ConnectionMethod::Usb,
-            },
-            jetpack_version: Some("5.1.2".to_string()),
-            hostname: Some(format!("{}.local", id)),
+    JetsonDevice::new(DeviceInfo {
+        id: id.to_string(),
+        model,
+        connection: match ip {
+            Some(addr) => ConnectionMethod::Ethernet(addr.parse().unwrap()),
+            None => ConnectionMethod::Usb,
         },
-    }
+        jetpack_version: Some("5.1.2".to_string()),
+        hostname: Some(format!("{}.local", id)),
+    })
 }
 
 #[tokio::main]
@@ -103,6 +101,8 @@ async fn main() -> Result<()> {
         quantization: Some("q4_0".to_string()),
         memory_budget_mb: 4000,
         thermal_policy: ThermalPolicy::conservative(),
+        max_oom_retries: 2,
+        compress: false,
     };
 
     println!("Deployment Config:");
@@ -78,7 +78,7 @@ fn main() -> Result<()> {
     ];
 
     for (name, size_mb) in model_sizes {
-        let level = JetsonQuantizer::select_for_budget(size_mb, &budget);
+        let level = JetsonQuantizer::select_for_budget(size_mb, &budget)?;
         let quantized = (size_mb as f32 * level.memory_factor()) as u64;
         println!("{}: {} -> {} ({} MB)",
             name, level, level.as_str(), quantized);
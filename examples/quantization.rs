@@ -86,7 +86,7 @@ fn main() -> Result<()> {
     for (model_name, f16_size) in models {
         print!("{:<15}", model_name);
         for (_, budget) in &devices {
-            let level = JetsonQuantizer::select_for_budget(f16_size, budget);
+            let level = JetsonQuantizer::select_for_budget(f16_size, budget)?;
             print!(" {:>12}", level.as_str());
         }
         println!();
@@ -120,7 +120,7 @@ fn main() -> Result<()> {
     println!("  Model F16 size: {} MB", llama_7b_f16_mb);
     println!("  Available memory: {} MB", budget.available_mb());
 
-    let optimal = JetsonQuantizer::select_for_budget(llama_7b_f16_mb, &budget);
+    let optimal = JetsonQuantizer::select_for_budget(llama_7b_f16_mb, &budget)?;
     let quantized_size = (llama_7b_f16_mb as f32 * optimal.memory_factor()) as u64;
 
     println!("\nRecommendation:");
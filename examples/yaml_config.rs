@@ -38,9 +38,12 @@ fleet:
       thermal_policy: aggressive
       memory_budget_mb: 4000
 
+model_registry:
+  llama7b: "pacha://models/llama-2-7b-chat"
+
 models:
   - name: llama-7b
-    source: "pacha://models/llama-2-7b-chat"
+    source: "@llama7b"
     quantization: q4_0
     devices: all
   - name: phi-2
@@ -113,7 +116,7 @@ provision:
     println!("\nModels:");
     for model in &config.models {
         println!("  {}:", model.name);
-        println!("    Source: {}", model.source);
+        println!("    Source: {}", config.resolve_source(&model.source)?);
         println!("    Quantization: {:?}", model.quantization);
         println!("    Devices: {}", model.devices);
     }
@@ -0,0 +1,196 @@
+//! Audit trail of commands run on devices.
+//!
+//! Provides a pluggable auditor and a JSONL file sink for regulated environments.
+
+use crate::Result;
+use serde::{Deserialize, Serialize};
+use std::fs::{File, OpenOptions};
+use std::io::Write;
+use std::path::Path;
+use std::sync::{Mutex, PoisonError};
+use std::time::{Duration, SystemTime, UNIX_EPOCH};
+
+/// Result of a single audited command.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct CommandOutcome {
+    /// Exit code, if the command ran
+    pub exit_code: Option<i32>,
+    /// Transport error, if the command could not be run
+    pub error: Option<String>,
+    /// Wall-clock duration
+    pub duration: Duration,
+}
+
+/// Receives a record of every command run through a [`JetsonDevice`].
+///
+/// [`JetsonDevice`]: crate::device::JetsonDevice
+pub trait CommandAuditor: std::fmt::Debug + Send + Sync {
+    /// Record a command and its outcome.
+    ///
+    /// Auditing must not fail the command, so sinks handle their own errors.
+    fn record(&self, device_id: &str, command: &str, outcome: &CommandOutcome);
+}
+
+/// One line of the audit log.
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+pub struct AuditEntry {
+    /// Milliseconds since the Unix epoch
+    pub timestamp_ms: u64,
+    /// Device ID
+    pub device_id: String,
+    /// Command as sent to the device
+    pub command: String,
+    /// Exit code, if the command ran
+    pub exit_code: Option<i32>,
+    /// Transport error, if the command could not be run
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub error: Option<String>,
+    /// Wall-clock duration in milliseconds
+    pub duration_ms: u64,
+}
+
+impl AuditEntry {
+    /// Build an entry timestamped now.
+    #[must_use]
+    pub fn now(device_id: &str, command: &str, outcome: &CommandOutcome) -> Self {
+        let timestamp = SystemTime::now()
+            .duration_since(UNIX_EPOCH)
+            .unwrap_or_default();
+        Self {
+            timestamp_ms: u64::try_from(timestamp.as_millis()).unwrap_or(u64::MAX),
+            device_id: device_id.to_string(),
+            command: command.to_string(),
+            exit_code: outcome.exit_code,
+            error: outcome.error.clone(),
+            duration_ms: u64::try_from(outcome.duration.as_millis()).unwrap_or(u64::MAX),
+        }
+    }
+}
+
+/// Auditor appending JSON lines to a file.
+#[derive(Debug)]
+pub struct FileAuditor {
+    file: Mutex<File>,
+}
+
+impl FileAuditor {
+    /// Open (or create) an audit log for appending.
+    ///
+    /// # Errors
+    ///
+    /// Returns an error if the file cannot be opened.
+    pub fn open(path: impl AsRef<Path>) -> Result<Self> {
+        let file = OpenOptions::new()
+            .create(true)
+            .append(true)
+            .open(path.as_ref())?;
+        Ok(Self {
+            file: Mutex::new(file),
+        })
+    }
+}
+
+impl CommandAuditor for FileAuditor {
+    fn record(&self, device_id: &str, command: &str, outcome: &CommandOutcome) {
+        let entry = AuditEntry::now(device_id, command, outcome);
+        let line = match serde_json::to_string(&entry) {
+            Ok(line) => line,
+            Err(e) => {
+                tracing::warn!(error = %e, "Failed to serialize audit entry");
+                return;
+            }
+        };
+        let mut file = self.file.lock().unwrap_or_else(PoisonError::into_inner);
+        if let Err(e) = writeln!(file, "{line}") {
+            tracing::warn!(error = %e, "Failed to write audit entry");
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::device::mock::{self, MockExecutor};
+    use std::sync::Arc;
+
+    /// Auditor keeping entries in memory.
+    #[derive(Debug, Default)]
+    struct RecordingAuditor {
+        entries: Mutex<Vec<(String, String, Option<i32>)>>,
+    }
+
+    impl CommandAuditor for RecordingAuditor {
+        fn record(&self, device_id: &str, command: &str, outcome: &CommandOutcome) {
+            self.entries.lock().unwrap().push((
+                device_id.to_string(),
+                command.to_string(),
+                outcome.exit_code,
+            ));
+        }
+    }
+
+    #[tokio::test]
+    async fn test_every_exec_is_audited() {
+        let exec = Arc::new(MockExecutor::new().fail("false", 1, "nope"));
+        let auditor = Arc::new(RecordingAuditor::default());
+        let device = mock::device("j1", crate::JetsonModel::OrinNano8GB, &exec)
+            .with_auditor(auditor.clone());
+
+        device.exec("uname -a").await.unwrap();
+        assert!(device.exec("false").await.is_err());
+        device.upload(b"data", "/tmp/f").await.unwrap();
+
+        let entries = auditor.entries.lock().unwrap().clone();
+        assert_eq!(
+            entries,
+            vec![
+                ("j1".to_string(), "uname -a".to_string(), Some(0)),
+                ("j1".to_string(), "false".to_string(), Some(1)),
+                (
+                    "j1".to_string(),
+                    "mkdir -p '/tmp' && { cat > '/tmp/f.tmp' && mv -f '/tmp/f.tmp' '/tmp/f'; } || { s=$?; rm -f '/tmp/f.tmp'; exit $s; }".to_string(),
+                    Some(0)
+                ),
+            ]
+        );
+    }
+
+    #[test]
+    fn test_file_auditor_writes_jsonl() {
+        let path = std::env::temp_dir().join(format!("cohete-audit-{}.jsonl", std::process::id()));
+        let _ = std::fs::remove_file(&path);
+        let auditor = FileAuditor::open(&path).unwrap();
+
+        auditor.record(
+            "j1",
+            "nvpmodel -q",
+            &CommandOutcome {
+                exit_code: Some(0),
+                error: None,
+                duration: Duration::from_millis(12),
+            },
+        );
+        auditor.record(
+            "j2",
+            "tegrastats",
+            &CommandOutcome {
+                exit_code: None,
+                error: Some("connection reset".to_string()),
+                duration: Duration::ZERO,
+            },
+        );
+
+        let content = std::fs::read_to_string(&path).unwrap();
+        std::fs::remove_file(&path).unwrap();
+        let entries: Vec<AuditEntry> = content
+            .lines()
+            .map(|line| serde_json::from_str(line).unwrap())
+            .collect();
+        assert_eq!(entries.len(), 2);
+        assert_eq!(entries[0].device_id, "j1");
+        assert_eq!(entries[0].exit_code, Some(0));
+        assert_eq!(entries[0].duration_ms, 12);
+        assert!(entries[0].timestamp_ms > 0);
+        assert_eq!(entries[1].error.as_deref(), Some("connection reset"));
+    }
+}
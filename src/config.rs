@@ -3,12 +3,19 @@
 //! Provides declarative configuration (Architectural Invariant).
 
 use crate::{
+    fleet::{ApplyReport, Fleet, FleetState},
+    power::{FanSpeed, PowerProfile},
+    quantize::QuantLevel,
     thermal::ThermalPolicy,
     Result, Error,
 };
 use serde::{Deserialize, Serialize};
+use std::collections::{BTreeMap, HashMap};
 use std::path::Path;
 
+/// Prefix marking a model source as a registry alias.
+pub const MODEL_ALIAS_PREFIX: char = '@';
+
 /// Root configuration structure.
 #[derive(Debug, Clone, Serialize, Deserialize)]
 pub struct CoheteConfig {
@@ -24,6 +31,10 @@ pub struct CoheteConfig {
     #[serde(default)]
     pub fleet: FleetConfig,
 
+    /// Model source aliases (alias -> source URL)
+    #[serde(default, skip_serializing_if = "HashMap::is_empty")]
+    pub model_registry: HashMap<String, String>,
+
     /// Model deployment settings
     #[serde(default)]
     pub models: Vec<ModelConfig>,
@@ -39,8 +50,24 @@ pub struct CoheteConfig {
     /// Provisioning settings
     #[serde(default)]
     pub provision: ProvisionYamlConfig,
+
+    /// Quantization by device memory, for devices a model does not pin
+    #[serde(default, skip_serializing_if = "Vec::is_empty")]
+    pub quant_tiers: Vec<QuantTier>,
 }
 
+/// Quantization for devices with at least `min_memory_mb` of memory budget.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize)]
+pub struct QuantTier {
+    /// Smallest `memory_budget_mb` in the tier
+    pub min_memory_mb: u64,
+    /// Quantization used by the tier
+    pub quant: QuantLevel,
+}
+
+/// Quantization for devices below every tier.
+pub const DEFAULT_TIER_QUANT: QuantLevel = QuantLevel::Q4_0;
+
 fn default_version() -> String {
     "1.0".to_string()
 }
@@ -51,10 +78,12 @@ impl Default for CoheteConfig {
             version: default_version(),
             discovery: DiscoveryConfig::default(),
             fleet: FleetConfig::default(),
+            model_registry: HashMap::new(),
             models: Vec::new(),
             thermal: ThermalPoliciesConfig::default(),
             inference: InferenceConfig::default(),
             provision: ProvisionYamlConfig::default(),
+            quant_tiers: Vec::new(),
         }
     }
 }
@@ -74,10 +103,115 @@ impl CoheteConfig {
     ///
     /// # Errors
     ///
-    /// Returns an error if YAML is invalid.
+    /// Returns an error if YAML is invalid or a model uses an unknown alias.
     pub fn from_yaml(yaml: &str) -> Result<Self> {
-        serde_yaml::from_str(yaml)
-            .map_err(|e| Error::InvalidYaml(e.to_string()))
+        let config: Self =
+            serde_yaml::from_str(yaml).map_err(|e| Error::InvalidYaml(e.to_string()))?;
+        config.validate_model_sources()?;
+        Ok(config)
+    }
+
+    /// Resolve a model source, expanding `@alias` from the model registry.
+    ///
+    /// Sources without the `@` prefix are returned unchanged.
+    ///
+    /// # Errors
+    ///
+    /// Returns `Error::Config` if the alias is not in the registry.
+    pub fn resolve_source<'a>(&'a self, source: &'a str) -> Result<&'a str> {
+        let Some(alias) = source.strip_prefix(MODEL_ALIAS_PREFIX) else {
+            return Ok(source);
+        };
+        self.model_registry
+            .get(alias)
+            .map(String::as_str)
+            .ok_or_else(|| Error::Config(format!("unknown model alias '{source}'")))
+    }
+
+    /// Check that every model source alias is in the registry.
+    ///
+    /// # Errors
+    ///
+    /// Returns `Error::Config` naming the first model with an unknown alias.
+    pub fn validate_model_sources(&self) -> Result<()> {
+        for model in &self.models {
+            self.resolve_source(&model.source).map_err(|e| match e {
+                Error::Config(msg) => Error::Config(format!("model {}: {msg}", model.name)),
+                e => e,
+            })?;
+        }
+        Ok(())
+    }
+
+    /// Quantization for a device from its memory tier in [`Self::quant_tiers`].
+    ///
+    /// The tier with the largest `min_memory_mb` not above the device's
+    /// `memory_budget_mb` applies; below every tier, [`DEFAULT_TIER_QUANT`].
+    ///
+    /// # Errors
+    ///
+    /// Returns `Error::Config` if the device is not in the fleet config.
+    pub fn quant_for_device(&self, device_id: &str) -> Result<QuantLevel> {
+        Ok(tier_quant(
+            &self.quant_tiers,
+            self.device_memory_mb(device_id)?,
+        ))
+    }
+
+    /// Quantization for a model on a device.
+    ///
+    /// A model's `quantization` pins the level for every device; otherwise
+    /// its own `quant_tiers`, if any, override the fleet-wide tiers.
+    ///
+    /// # Errors
+    ///
+    /// Returns `Error::Config` if the model or device is not configured, or
+    /// the model's `quantization` is not a known level.
+    pub fn quant_for_model_device(&self, model: &str, device_id: &str) -> Result<QuantLevel> {
+        let config = self
+            .models
+            .iter()
+            .find(|m| m.name == model)
+            .ok_or_else(|| Error::Config(format!("unknown model '{model}'")))?;
+        if let Some(quant) = &config.quantization {
+            return quant.parse().map_err(|_| {
+                Error::Config(format!("model {model}: unknown quantization '{quant}'"))
+            });
+        }
+        let tiers = if config.quant_tiers.is_empty() {
+            &self.quant_tiers
+        } else {
+            &config.quant_tiers
+        };
+        Ok(tier_quant(tiers, self.device_memory_mb(device_id)?))
+    }
+
+    fn device_memory_mb(&self, device_id: &str) -> Result<u64> {
+        self.fleet
+            .devices
+            .iter()
+            .find(|d| d.id == device_id)
+            .map(|d| d.memory_budget_mb)
+            .ok_or_else(|| Error::Config(format!("unknown device '{device_id}'")))
+    }
+
+    /// Apply this config to its fleet, acting only where it differs from
+    /// `current`.
+    ///
+    /// Builds the fleet with [`Fleet::from_config`] and converges it with
+    /// [`Fleet::apply_diff`], so re-applying an unchanged config does
+    /// nothing. Model sources are fetched with [`HostModelFetcher`]; use
+    /// `Fleet::apply_diff` on a fleet with another fetcher for `pacha://`
+    /// sources.
+    ///
+    /// # Errors
+    ///
+    /// Returns `Error::Config` if the config is invalid or a source cannot
+    /// be fetched, or an error if an action fails.
+    ///
+    /// [`HostModelFetcher`]: crate::fleet::HostModelFetcher
+    pub async fn apply_diff(&self, current: &FleetState) -> Result<ApplyReport> {
+        Fleet::from_config(self)?.apply_diff(self, current).await
     }
 
     /// Serialize to YAML string.
@@ -132,6 +266,22 @@ pub struct FleetConfig {
     /// Device configurations
     #[serde(default)]
     pub devices: Vec<DeviceYamlConfig>,
+
+    /// Largest model `Fleet::deploy_model` accepts, in GB
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub max_model_size_gb: Option<f64>,
+
+    /// Per-group overrides; a device joins a group with a `group` tag
+    #[serde(default, skip_serializing_if = "HashMap::is_empty")]
+    pub groups: HashMap<String, FleetGroupConfig>,
+}
+
+/// Settings for devices tagged with a group name.
+#[derive(Debug, Clone, Serialize, Deserialize, Default, PartialEq)]
+pub struct FleetGroupConfig {
+    /// Overrides the fleet's `max_model_size_gb` for the group
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub max_model_size_gb: Option<f64>,
 }
 
 /// Device YAML configuration.
@@ -154,6 +304,92 @@ pub struct DeviceYamlConfig {
     /// Memory budget in MB
     #[serde(default = "default_memory_budget")]
     pub memory_budget_mb: u64,
+
+    /// Rollout priority (lowest deploys first; use for canaries)
+    #[serde(default)]
+    pub deploy_priority: i32,
+
+    /// Free-form labels; `role: standby` keeps the device as a cordoned spare
+    #[serde(default, skip_serializing_if = "HashMap::is_empty")]
+    pub tags: HashMap<String, String>,
+
+    /// Remote power control for hard reboots
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub power: Option<PowerControlConfig>,
+
+    /// Power profile applied with `Fleet::apply_power_profiles`
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub power_profile: Option<PowerProfileYaml>,
+}
+
+/// Power profile YAML: a named preset with an optional fan override.
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+pub struct PowerProfileYaml {
+    /// Preset name: `max_performance`, `balanced` or `power_saver`
+    pub preset: String,
+    /// Fan speed override, as PWM (`128`) or percentage (`50%`)
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub fan_speed: Option<FanSpeed>,
+}
+
+impl PowerProfileYaml {
+    /// Resolve the preset and apply the fan override.
+    ///
+    /// # Errors
+    ///
+    /// Returns `Error::Config` for an unknown preset name.
+    pub fn to_profile(&self) -> Result<PowerProfile> {
+        let mut profile = match self.preset.as_str() {
+            "max_performance" => PowerProfile::max_performance(),
+            "balanced" => PowerProfile::balanced(),
+            "power_saver" => PowerProfile::power_saver(),
+            other => return Err(Error::Config(format!("unknown power preset '{other}'"))),
+        };
+        if let Some(fan_speed) = self.fan_speed {
+            profile.fan_speed = fan_speed;
+        }
+        Ok(profile)
+    }
+
+    /// Describe a profile as a preset; `None` if its mode and clocks match none.
+    #[must_use]
+    pub fn from_profile(profile: &PowerProfile) -> Option<Self> {
+        [
+            ("max_performance", PowerProfile::max_performance()),
+            ("balanced", PowerProfile::balanced()),
+            ("power_saver", PowerProfile::power_saver()),
+        ]
+        .into_iter()
+        .find(|(_, preset)| {
+            preset.mode == profile.mode && preset.enable_clocks == profile.enable_clocks
+        })
+        .map(|(name, preset)| Self {
+            preset: name.to_string(),
+            fan_speed: (preset.fan_speed != profile.fan_speed).then_some(profile.fan_speed),
+        })
+    }
+}
+
+/// How a device's power is switched remotely.
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(tag = "type", rename_all = "lowercase")]
+pub enum PowerControlConfig {
+    /// Outlet on a network PDU, switched with `POST {url}/outlets/{outlet}/{off,on}`
+    Pdu {
+        /// PDU base URL
+        url: String,
+        /// Outlet number
+        outlet: u32,
+    },
+    /// Relay on a GPIO line of another host, switched with `gpioset`
+    Gpio {
+        /// Host driving the relay (SSH)
+        host: String,
+        /// GPIO chip, e.g. `gpiochip0`
+        chip: String,
+        /// Line offset on the chip
+        line: u32,
+    },
 }
 
 fn default_thermal_policy() -> String {
@@ -170,7 +406,7 @@ pub struct ModelConfig {
     /// Model name
     pub name: String,
 
-    /// Model source (pacha:// URL)
+    /// Model source (pacha:// URL, or `@alias` from the model registry)
     pub source: String,
 
     /// Quantization level
@@ -180,6 +416,19 @@ pub struct ModelConfig {
     /// Target devices ("all" or list)
     #[serde(default = "default_devices")]
     pub devices: String,
+
+    /// Per-model memory tiers, replacing the fleet-wide `quant_tiers`
+    #[serde(default, skip_serializing_if = "Vec::is_empty")]
+    pub quant_tiers: Vec<QuantTier>,
+}
+
+/// Level of the highest tier `memory_mb` reaches, in any tier order.
+fn tier_quant(tiers: &[QuantTier], memory_mb: u64) -> QuantLevel {
+    tiers
+        .iter()
+        .filter(|tier| tier.min_memory_mb <= memory_mb)
+        .max_by_key(|tier| tier.min_memory_mb)
+        .map_or(DEFAULT_TIER_QUANT, |tier| tier.quant)
 }
 
 fn default_devices() -> String {
@@ -216,7 +465,7 @@ impl Default for ThermalPoliciesConfig {
 }
 
 /// Thermal policy YAML.
-#[derive(Debug, Clone, Serialize, Deserialize)]
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
 pub struct ThermalPolicyYaml {
     /// Threshold temperature in Celsius
     pub threshold_c: f32,
@@ -242,6 +491,16 @@ impl From<ThermalPolicyYaml> for ThermalPolicy {
     }
 }
 
+impl From<&ThermalPolicy> for ThermalPolicyYaml {
+    fn from(policy: &ThermalPolicy) -> Self {
+        Self {
+            threshold_c: policy.threshold_c,
+            cooldown_c: policy.cooldown_c,
+            check_interval_ms: policy.check_interval_ms,
+        }
+    }
+}
+
 /// Inference server configuration.
 #[derive(Debug, Clone, Serialize, Deserialize)]
 pub struct InferenceConfig {
@@ -303,6 +562,11 @@ pub struct ProvisionYamlConfig {
     /// Packages to install
     #[serde(default)]
     pub packages: Vec<String>,
+
+    /// Kernel parameters by key (e.g. `vm.swappiness: "10"`), checked by
+    /// `Fleet::detect_drift`
+    #[serde(default, skip_serializing_if = "BTreeMap::is_empty")]
+    pub sysctls: BTreeMap<String, String>,
 }
 
 /// NVMe YAML configuration.
@@ -407,4 +671,138 @@ fleet:
         let policy: ThermalPolicy = yaml.into();
         assert_eq!(policy.threshold_c, 70.0);
     }
+
+    const TIERS_YAML: &str = r#"
+fleet:
+  devices:
+    - id: nano-4gb
+      memory_budget_mb: 3000
+    - id: nano-8gb
+      memory_budget_mb: 6000
+    - id: nx-16gb
+      memory_budget_mb: 14000
+    - id: tiny
+      memory_budget_mb: 1500
+quant_tiers:
+  - min_memory_mb: 12000
+    quant: q8_0
+  - min_memory_mb: 2048
+    quant: q4_0
+  - min_memory_mb: 5000
+    quant: q5_0
+models:
+  - name: chat
+    source: "pacha://models/chat.gguf"
+  - name: embed
+    source: "pacha://models/embed.gguf"
+    quant_tiers:
+      - min_memory_mb: 0
+        quant: q8_0
+  - name: pinned
+    source: "pacha://models/pinned.gguf"
+    quantization: q5_1
+"#;
+
+    #[test]
+    fn test_quant_for_device_by_memory_tier() {
+        let config = CoheteConfig::from_yaml(TIERS_YAML).unwrap();
+        let quant = |id| config.quant_for_device(id).unwrap();
+        assert_eq!(quant("nano-4gb"), QuantLevel::Q4_0);
+        assert_eq!(quant("nano-8gb"), QuantLevel::Q5_0);
+        assert_eq!(quant("nx-16gb"), QuantLevel::Q8_0);
+        // Below every tier
+        assert_eq!(quant("tiny"), DEFAULT_TIER_QUANT);
+        assert!(matches!(
+            config.quant_for_device("ghost"),
+            Err(Error::Config(_))
+        ));
+    }
+
+    #[test]
+    fn test_quant_for_model_device_overrides() {
+        let config = CoheteConfig::from_yaml(TIERS_YAML).unwrap();
+        let quant = |model, id| config.quant_for_model_device(model, id).unwrap();
+        // Fleet tiers, then the model's own tiers, then a pinned level
+        assert_eq!(quant("chat", "nano-8gb"), QuantLevel::Q5_0);
+        assert_eq!(quant("embed", "nano-4gb"), QuantLevel::Q8_0);
+        assert_eq!(quant("pinned", "nx-16gb"), QuantLevel::Q5_1);
+        assert!(matches!(
+            config.quant_for_model_device("missing", "nano-4gb"),
+            Err(Error::Config(_))
+        ));
+
+        let mut bad = config.clone();
+        bad.models[2].quantization = Some("q3_k".to_string());
+        assert!(matches!(
+            bad.quant_for_model_device("pinned", "nx-16gb"),
+            Err(Error::Config(m)) if m.contains("pinned") && m.contains("q3_k")
+        ));
+
+        let yaml = config.to_yaml().unwrap();
+        assert!(yaml.contains("min_memory_mb: 12000"));
+        let again = CoheteConfig::from_yaml(&yaml).unwrap();
+        assert_eq!(again.quant_tiers, config.quant_tiers);
+        assert_eq!(again.models[1].quant_tiers, config.models[1].quant_tiers);
+    }
+
+    #[tokio::test]
+    async fn test_apply_diff_from_config() {
+        let config = CoheteConfig::from_yaml(TIERS_YAML).unwrap();
+        let current = FleetState::from_config(&config).unwrap();
+        assert!(config.apply_diff(&current).await.unwrap().is_noop());
+
+        // pacha:// sources need a fetcher; nothing is touched without one
+        let err = config.apply_diff(&FleetState::default()).await.unwrap_err();
+        assert!(matches!(err, Error::Config(m) if m.contains("pacha://models/chat.gguf")));
+    }
+
+    const REGISTRY_YAML: &str = r#"
+model_registry:
+  llama7b: "pacha://models/llama-2-7b-chat.gguf"
+models:
+  - name: chat
+    source: "@llama7b"
+  - name: chat-canary
+    source: "@llama7b"
+    devices: jetson-01
+  - name: embed
+    source: "pacha://models/bge-small.gguf"
+"#;
+
+    #[test]
+    fn test_model_alias_resolution() {
+        let config = CoheteConfig::from_yaml(REGISTRY_YAML).unwrap();
+        let sources: Vec<&str> = config
+            .models
+            .iter()
+            .map(|m| config.resolve_source(&m.source).unwrap())
+            .collect();
+        assert_eq!(
+            sources,
+            vec![
+                "pacha://models/llama-2-7b-chat.gguf",
+                "pacha://models/llama-2-7b-chat.gguf",
+                "pacha://models/bge-small.gguf",
+            ]
+        );
+
+        let yaml = config.to_yaml().unwrap();
+        assert_eq!(
+            CoheteConfig::from_yaml(&yaml).unwrap().model_registry.len(),
+            1
+        );
+    }
+
+    #[test]
+    fn test_unresolved_model_alias() {
+        let yaml = REGISTRY_YAML.replace(
+            "source: \"@llama7b\"\n    devices",
+            "source: \"@llama13b\"\n    devices",
+        );
+        let err = CoheteConfig::from_yaml(&yaml).unwrap_err();
+        assert!(matches!(err, Error::Config(_)));
+        assert!(err.to_string().contains("chat-canary"));
+        assert!(err.to_string().contains("@llama13b"));
+        assert_eq!(err.to_string().matches("Configuration error").count(), 1);
+    }
 }
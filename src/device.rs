@@ -3,8 +3,70 @@
 //! This module provides functionality to discover, connect to, and manage
 //! NVIDIA Jetson devices via USB, Ethernet, or mDNS.
 
-use crate::{Error, JetsonModel, Result};
-use std::net::IpAddr;
+use crate::{
+    audit::{CommandAuditor, CommandOutcome},
+    memory::{parse_carveouts, MemoryZones},
+    power::{FanController, PowerController, PowerMode, FAN_PWM},
+    thermal::{BenchResult, SoakResult, TegraMonitor, TripType},
+    Error, JetsonModel, Result, Subsystem,
+};
+use serde::{Deserialize, Serialize};
+use std::collections::{BTreeMap, HashSet};
+use std::net::{IpAddr, Ipv4Addr};
+use std::path::{Path, PathBuf};
+use std::sync::Arc;
+use std::time::{Duration, Instant, SystemTime, UNIX_EPOCH};
+
+/// Samples taken during a thermal soak or benchmark.
+const SOAK_SAMPLES: u32 = 20;
+
+/// Passive trip point on Orin modules, used when sysfs reports none (°C).
+const ORIN_PASSIVE_TRIP_C: f32 = 99.0;
+
+/// `mktemp` template for a benchmark workload's output, one per run.
+const BENCH_LOG_TEMPLATE: &str = "/tmp/cohete-bench.XXXXXX";
+
+/// Time a benchmark workload gets to exit after SIGTERM before SIGKILL.
+const BENCH_KILL_GRACE: Duration = Duration::from_secs(5);
+
+/// Interval between checks that a benchmark workload has exited.
+const BENCH_POLL_INTERVAL: Duration = Duration::from_millis(100);
+
+/// debugfs mount point; its nodes are only readable by root.
+const DEBUGFS: &str = "/sys/kernel/debug";
+
+/// Separator between values in a batched sysfs read (ASCII record separator).
+const SYSFS_SEPARATOR: char = '\x1e';
+
+/// Separator between a node's path and value in a glob read (ASCII unit
+/// separator).
+const SYSFS_PATH_SEPARATOR: char = '\x1f';
+
+/// stderr marker for a batched-read glob that matched several nodes.
+const SYSFS_MULTI_MATCH: &str = "nodes matched:";
+
+/// First three octets of the L4T USB gadget network (`192.168.55.0/24`).
+const USB_DEFAULT_SUBNET: [u8; 3] = [192, 168, 55];
+
+/// Support bundle files and the commands that produce them.
+const SUPPORT_SOURCES: &[(&str, &str)] = &[
+    ("model.txt", "cat /proc/device-tree/model"),
+    ("jetpack.txt", "cat /etc/nv_tegra_release"),
+    (
+        "cuda.txt",
+        "cat /usr/local/cuda/version.json 2>/dev/null || /usr/local/cuda/bin/nvcc --version",
+    ),
+    ("journal.txt", "journalctl -b --no-pager -n 2000"),
+    ("thermal.txt", "tegrastats --interval 500 | head -n 20"),
+    ("nvpmodel.txt", "sudo nvpmodel -q --verbose"),
+    ("dmesg.txt", "sudo dmesg | tail -n 2000"),
+    ("storage.txt", "df -h; lsblk; sudo smartctl -a /dev/nvme0n1"),
+    ("meminfo.txt", "cat /proc/meminfo"),
+];
+
+/// Prints the display manager state, then `display-server` if X or Wayland runs.
+const HEADLESS_PROBE: &str = "systemctl is-active display-manager.service 2>/dev/null; \
+    pgrep -x 'Xorg|Xwayland|gnome-shell|weston' >/dev/null && echo display-server; true";
 
 /// Connection method to Jetson device.
 #[derive(Debug, Clone, PartialEq, Eq)]
@@ -38,15 +100,325 @@ pub struct DeviceInfo {
     pub hostname: Option<String>,
 }
 
+/// Host-side network interface of a Jetson's USB gadget link.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct UsbInterface {
+    /// Interface name (e.g. `usb0`, `enx...`)
+    pub name: String,
+    /// Host address on the link
+    pub addr: Ipv4Addr,
+    /// Subnet prefix length
+    pub prefix_len: u8,
+}
+
+impl UsbInterface {
+    /// Address of the Jetson on this link: host `.1` of the subnet.
+    #[must_use]
+    pub fn device_addr(&self) -> Ipv4Addr {
+        let mask = u32::MAX
+            .checked_shl(32 - u32::from(self.prefix_len.min(32)))
+            .unwrap_or(0);
+        Ipv4Addr::from((u32::from(self.addr) & mask) | 1)
+    }
+}
+
+/// Parse USB gadget links from `ip -4 -o addr show` output.
+///
+/// An interface counts as a Jetson link if it is on the L4T default
+/// `192.168.55.0/24` subnet or named `usb*`, so boards moved to other
+/// subnets are still found.
+#[must_use]
+pub fn parse_usb_interfaces(output: &str) -> Vec<UsbInterface> {
+    output
+        .lines()
+        .filter_map(|line| {
+            let mut fields = line.split_whitespace().skip(1);
+            let name = fields.next()?.trim_end_matches(':');
+            let (addr, prefix_len) = fields
+                .skip_while(|f| *f != "inet")
+                .nth(1)?
+                .split_once('/')?;
+            let addr: Ipv4Addr = addr.parse().ok()?;
+            let on_default_subnet = addr.octets()[..3] == USB_DEFAULT_SUBNET;
+            (on_default_subnet || name.starts_with("usb")).then(|| UsbInterface {
+                name: name.to_string(),
+                addr,
+                prefix_len: prefix_len.parse().unwrap_or(24),
+            })
+        })
+        .collect()
+}
+
+/// Answer to an mDNS query for Jetson devices.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct MdnsResponder {
+    /// Advertised hostname, e.g. `jetson.local`
+    pub hostname: String,
+    /// Address the answer came from
+    pub addr: IpAddr,
+    /// Module serial number from the TXT record
+    pub serial: Option<String>,
+    /// MAC address from the TXT record
+    pub mac: Option<String>,
+}
+
+impl MdnsResponder {
+    /// What tells two responders apart: serial, then MAC, then address.
+    fn identity(&self) -> String {
+        self.serial
+            .as_ref()
+            .or(self.mac.as_ref())
+            .map_or_else(|| self.addr.to_string(), |id| id.to_ascii_lowercase())
+    }
+}
+
+/// Captured output of a command run on a device.
+#[derive(Debug, Clone, Default, PartialEq, Eq)]
+pub struct CommandOutput {
+    /// Exit status (0 on success)
+    pub exit_code: i32,
+    /// Standard output
+    pub stdout: Vec<u8>,
+    /// Standard error
+    pub stderr: Vec<u8>,
+}
+
+impl CommandOutput {
+    /// Check if the command exited successfully.
+    #[must_use]
+    pub const fn success(&self) -> bool {
+        self.exit_code == 0
+    }
+
+    /// Standard output of a successful command.
+    ///
+    /// # Errors
+    ///
+    /// Returns `Error::CommandFailed` if the command exited non-zero.
+    pub fn into_stdout(self, command: &str) -> Result<Vec<u8>> {
+        if !self.success() {
+            return Err(Error::CommandFailed {
+                command: command.to_string(),
+                exit_code: self.exit_code,
+                stderr: String::from_utf8_lossy(&self.stderr).trim().to_string(),
+            });
+        }
+        Ok(self.stdout)
+    }
+}
+
+/// Transport used to run shell commands on a device.
+///
+/// The default transport is SSH; tests and alternative transports
+/// (serial console, local shell) plug in via [`JetsonDevice::with_executor`].
+pub trait CommandExecutor: std::fmt::Debug + Send + Sync {
+    /// Run a shell command and capture its output.
+    ///
+    /// # Errors
+    ///
+    /// Returns an error if the command could not be run at all. A command
+    /// that runs but exits non-zero is reported through [`CommandOutput`].
+    fn run(&self, command: &str) -> Result<CommandOutput>;
+
+    /// Run a shell command with `input` piped to its standard input.
+    ///
+    /// # Errors
+    ///
+    /// Returns an error if the command could not be run, or the transport
+    /// cannot stream input.
+    fn run_with_input(&self, command: &str, input: &[u8]) -> Result<CommandOutput> {
+        let _ = input;
+        Err(Error::Internal(format!(
+            "Transport cannot stream input to: {command}"
+        )))
+    }
+}
+
+/// SSH command transport.
+#[derive(Debug, Clone)]
+pub struct SshExecutor {
+    host: String,
+}
+
+impl SshExecutor {
+    /// Create a transport for the given host.
+    #[must_use]
+    pub fn new(host: impl Into<String>) -> Self {
+        Self { host: host.into() }
+    }
+
+    /// Target host.
+    #[must_use]
+    pub fn host(&self) -> &str {
+        &self.host
+    }
+}
+
+impl CommandExecutor for SshExecutor {
+    fn run(&self, command: &str) -> Result<CommandOutput> {
+        // Placeholder - would use russh session to self.host
+        Err(Error::Internal(format!(
+            "Command execution not implemented: {command}"
+        )))
+    }
+}
+
+/// Transport running commands on the local host through `sh -c`.
+///
+/// Used for fleet-side tooling such as PDU control.
+#[derive(Debug, Clone, Copy, Default)]
+pub struct LocalExecutor;
+
+impl LocalExecutor {
+    fn spawn(command: &str, input: Option<&[u8]>) -> Result<CommandOutput> {
+        use std::io::Write;
+        use std::process::{Command, Stdio};
+
+        let mut child = Command::new("sh")
+            .arg("-c")
+            .arg(command)
+            .stdin(if input.is_some() {
+                Stdio::piped()
+            } else {
+                Stdio::null()
+            })
+            .stdout(Stdio::piped())
+            .stderr(Stdio::piped())
+            .spawn()?;
+        if let (Some(input), Some(mut stdin)) = (input, child.stdin.take()) {
+            stdin.write_all(input)?;
+        }
+        let output = child.wait_with_output()?;
+        Ok(CommandOutput {
+            // Killed by a signal: report like a shell would
+            exit_code: output.status.code().unwrap_or(-1),
+            stdout: output.stdout,
+            stderr: output.stderr,
+        })
+    }
+}
+
+impl CommandExecutor for LocalExecutor {
+    fn run(&self, command: &str) -> Result<CommandOutput> {
+        Self::spawn(command, None)
+    }
+
+    fn run_with_input(&self, command: &str, input: &[u8]) -> Result<CommandOutput> {
+        Self::spawn(command, Some(input))
+    }
+}
+
+/// Device-side filter decompressing a zstd upload.
+const ZSTD_FILTER: &str = "zstd -d -q -c";
+
+/// Run synchronous transport work on tokio's blocking pool, so an SSH
+/// round trip does not stall an async worker thread.
+pub(crate) async fn blocking<T: Send + 'static>(
+    work: impl FnOnce() -> Result<T> + Send + 'static,
+) -> Result<T> {
+    tokio::task::spawn_blocking(work)
+        .await
+        .map_err(|e| Error::Internal(format!("Transport task failed: {e}")))?
+}
+
+/// Run synchronous transport work that borrows its input, such as an
+/// upload streaming a whole model, without copying it for the blocking pool.
+///
+/// Multi-threaded runtimes hand the worker's other tasks off while `work`
+/// blocks; elsewhere `work` runs in place.
+fn block_in_place<T>(work: impl FnOnce() -> T) -> T {
+    match tokio::runtime::Handle::try_current() {
+        Ok(handle) if handle.runtime_flavor() == tokio::runtime::RuntimeFlavor::MultiThread => {
+            tokio::task::block_in_place(work)
+        }
+        _ => work(),
+    }
+}
+
 /// Handle to a connected Jetson device.
-#[derive(Debug)]
+///
+/// Construct it with [`Self::new`] (or `From<DeviceInfo>`) and the
+/// `with_*` builders. The transport, auditor and resolver are private, so
+/// a struct literal `JetsonDevice { info }` no longer compiles; replace it
+/// with `JetsonDevice::new(info)`, which keeps the SSH transport.
+#[derive(Debug, Clone)]
 pub struct JetsonDevice {
     /// Device information
     pub info: DeviceInfo,
-    // SSH session would be stored here
+    executor: Arc<dyn CommandExecutor>,
+    auditor: Option<Arc<dyn CommandAuditor>>,
+    resolver: Arc<dyn CommandExecutor>,
 }
 
 impl JetsonDevice {
+    /// Create a device handle using the SSH transport.
+    #[must_use]
+    pub fn new(info: DeviceInfo) -> Self {
+        let host = match &info.connection {
+            ConnectionMethod::Usb => "192.168.55.1".to_string(),
+            ConnectionMethod::Ethernet(ip) => ip.to_string(),
+            ConnectionMethod::Mdns(hostname) => hostname.clone(),
+        };
+        Self {
+            info,
+            executor: Arc::new(SshExecutor::new(host)),
+            auditor: None,
+            resolver: Arc::new(LocalExecutor),
+        }
+    }
+
+    /// Replace the command transport.
+    #[must_use]
+    pub fn with_executor(mut self, executor: Arc<dyn CommandExecutor>) -> Self {
+        self.executor = executor;
+        self
+    }
+
+    /// Record every command run on this device.
+    #[must_use]
+    pub fn with_auditor(mut self, auditor: Arc<dyn CommandAuditor>) -> Self {
+        self.auditor = Some(auditor);
+        self
+    }
+
+    /// Replace the host-side transport used to resolve mDNS hostnames.
+    #[must_use]
+    pub fn with_resolver(mut self, resolver: Arc<dyn CommandExecutor>) -> Self {
+        self.resolver = resolver;
+        self
+    }
+
+    /// Address the device is reached at, resolving mDNS hostnames.
+    ///
+    /// # Errors
+    ///
+    /// Returns `Error::ConnectionFailed` if an mDNS hostname does not
+    /// resolve, or an error if the resolver cannot be run.
+    pub async fn resolved_addr(&self) -> Result<IpAddr> {
+        let hostname = match &self.info.connection {
+            ConnectionMethod::Usb => return Ok(IpAddr::V4(Ipv4Addr::new(192, 168, 55, 1))),
+            ConnectionMethod::Ethernet(ip) => return Ok(*ip),
+            ConnectionMethod::Mdns(hostname) => hostname,
+        };
+        let resolver = Arc::clone(&self.resolver);
+        let command = format!("getent hosts {}", shell_quote(hostname));
+        let output = blocking(move || resolver.run(&command)).await?;
+        String::from_utf8_lossy(&output.stdout)
+            .split_whitespace()
+            .next()
+            .and_then(|addr| addr.parse().ok())
+            .filter(|_| output.success())
+            .ok_or_else(|| Error::ConnectionFailed {
+                host: hostname.clone(),
+                reason: "mDNS hostname did not resolve".to_string(),
+            })
+    }
+
+    /// Whether the device's address resolves and it answers a command.
+    pub async fn is_reachable(&self) -> bool {
+        self.resolved_addr().await.is_ok() && self.exec("true").await.is_ok()
+    }
+
     /// Discover all Jetson devices on the network and USB.
     ///
     /// # Errors
@@ -55,9 +427,10 @@ impl JetsonDevice {
     pub async fn discover_all() -> Result<Vec<Self>> {
         let mut devices = Vec::new();
 
-        // Try USB connection first
-        if let Ok(device) = Self::discover_usb().await {
-            devices.push(device);
+        // Try USB connections first; assume the default link if none is seen
+        match Self::discover_usb_all().await {
+            Ok(usb) if !usb.is_empty() => devices.extend(usb),
+            _ => devices.extend(Self::discover_usb().await),
         }
 
         // Try mDNS discovery
@@ -83,7 +456,71 @@ impl JetsonDevice {
             hostname: None,
         };
 
-        Ok(Self { info })
+        Ok(Self::new(info))
+    }
+
+    /// Discover every Jetson attached over USB-C to this host.
+    ///
+    /// Each board appears as its own host network interface; see
+    /// [`Self::from_usb_interfaces`] for ids and addresses.
+    ///
+    /// # Errors
+    ///
+    /// Returns an error if the host's interfaces cannot be listed.
+    pub async fn discover_usb_all() -> Result<Vec<Self>> {
+        blocking(|| Self::discover_usb_with(&LocalExecutor)).await
+    }
+
+    /// First USB-attached Jetson, or the default USB link if none is seen.
+    ///
+    /// # Errors
+    ///
+    /// Returns an error if USB discovery fails.
+    pub async fn discover_usb_one() -> Result<Self> {
+        match Self::discover_usb_all().await?.into_iter().next() {
+            Some(device) => Ok(device),
+            None => Self::discover_usb().await,
+        }
+    }
+
+    fn discover_usb_with(host: &dyn CommandExecutor) -> Result<Vec<Self>> {
+        let command = "ip -4 -o addr show";
+        let output = host.run(command)?.into_stdout(command)?;
+        Ok(Self::from_usb_interfaces(&parse_usb_interfaces(
+            &String::from_utf8_lossy(&output),
+        )))
+    }
+
+    /// Build devices from host-side USB gadget interfaces.
+    ///
+    /// Ids are `jetson-usb-{interface}`, stable across rediscovery while
+    /// the interface keeps its name. A board on the default subnet uses
+    /// [`ConnectionMethod::Usb`]; one on another subnet is reached at that
+    /// subnet's `.1` over [`ConnectionMethod::Ethernet`]; two boards left
+    /// on the default subnet share its address, so move all but one.
+    #[must_use]
+    pub fn from_usb_interfaces(interfaces: &[UsbInterface]) -> Vec<Self> {
+        let mut interfaces: Vec<&UsbInterface> = interfaces.iter().collect();
+        interfaces.sort_by(|a, b| a.name.cmp(&b.name));
+        interfaces.dedup_by(|a, b| a.name == b.name);
+        interfaces
+            .into_iter()
+            .map(|iface| {
+                let addr = iface.device_addr();
+                let connection = if addr.octets()[..3] == USB_DEFAULT_SUBNET {
+                    ConnectionMethod::Usb
+                } else {
+                    ConnectionMethod::Ethernet(IpAddr::V4(addr))
+                };
+                Self::new(DeviceInfo {
+                    id: format!("jetson-usb-{}", iface.name),
+                    model: JetsonModel::Unknown,
+                    connection,
+                    jetpack_version: None,
+                    hostname: None,
+                })
+            })
+            .collect()
     }
 
     /// Discover Jetson devices via mDNS.
@@ -93,7 +530,69 @@ impl JetsonDevice {
     /// Returns an error if mDNS discovery fails.
     pub async fn discover_mdns() -> Result<Vec<Self>> {
         // Placeholder - would use mdns-sd crate
-        Ok(Vec::new())
+        Ok(Self::from_mdns_responders(&[]))
+    }
+
+    /// Build devices from mDNS answers, keeping hostname collisions apart.
+    ///
+    /// Answers with the same serial (or MAC, or address) are one device.
+    /// When distinct devices claim one hostname they are ordered by serial
+    /// or MAC and given suffixed ids (`jetson`, `jetson-2`, ...), and are
+    /// reached by address since the name would resolve to either.
+    #[must_use]
+    pub fn from_mdns_responders(responders: &[MdnsResponder]) -> Vec<Self> {
+        let mut by_name: BTreeMap<String, Vec<&MdnsResponder>> = BTreeMap::new();
+        for responder in responders {
+            let name = responder
+                .hostname
+                .trim_end_matches('.')
+                .to_ascii_lowercase();
+            let claims = by_name.entry(name).or_default();
+            if !claims.iter().any(|c| c.identity() == responder.identity()) {
+                claims.push(responder);
+            }
+        }
+
+        let base_of = |hostname: &str| hostname.trim_end_matches(".local").to_string();
+        let mut taken: HashSet<String> = by_name.keys().map(|h| base_of(h)).collect();
+        let mut devices = Vec::new();
+        for (hostname, mut claims) in by_name {
+            let base = base_of(&hostname);
+            claims.sort_by_key(|c| c.identity());
+            if claims.len() > 1 {
+                tracing::warn!(
+                    hostname = %hostname,
+                    responders = ?claims.iter().map(|c| c.identity()).collect::<Vec<_>>(),
+                    "mDNS hostname collision; assigning suffixed ids"
+                );
+            }
+            let mut suffix = 1;
+            for (index, claim) in claims.iter().enumerate() {
+                let (id, connection) = if claims.len() == 1 {
+                    (base.clone(), ConnectionMethod::Mdns(hostname.clone()))
+                } else if index == 0 {
+                    (base.clone(), ConnectionMethod::Ethernet(claim.addr))
+                } else {
+                    // Skip suffixes another device really advertises
+                    let id = loop {
+                        suffix += 1;
+                        let id = format!("{base}-{suffix}");
+                        if taken.insert(id.clone()) {
+                            break id;
+                        }
+                    };
+                    (id, ConnectionMethod::Ethernet(claim.addr))
+                };
+                devices.push(Self::new(DeviceInfo {
+                    id,
+                    model: JetsonModel::Unknown,
+                    connection,
+                    jetpack_version: None,
+                    hostname: Some(hostname.clone()),
+                }));
+            }
+        }
+        devices
     }
 
     /// Connect to a specific IP address.
@@ -110,7 +609,7 @@ impl JetsonDevice {
             hostname: None,
         };
 
-        Ok(Self { info })
+        Ok(Self::new(info))
     }
 
     /// Get device identifier.
@@ -131,41 +630,810 @@ impl JetsonDevice {
         &self.info
     }
 
-    /// Execute a command on the device.
+    /// Execute a command on the device and return its standard output.
+    ///
+    /// Output is decoded lossily: invalid UTF-8 sequences (common in
+    /// `journalctl` and driver logs) become `U+FFFD` rather than failing.
+    /// Use [`Self::exec_bytes`] when the exact bytes matter.
     ///
     /// # Errors
     ///
-    /// Returns an error if command execution fails.
+    /// Returns an error if command execution fails or the command exits
+    /// non-zero.
     pub async fn exec(&self, command: &str) -> Result<String> {
-        // Placeholder - would use SSH
-        Err(Error::Internal(format!(
-            "Command execution not implemented: {command}"
-        )))
+        self.exec_bytes(command)
+            .await
+            .map(|stdout| String::from_utf8_lossy(&stdout).into_owned())
+    }
+
+    /// Execute a command on the device and return its raw standard output.
+    ///
+    /// # Errors
+    ///
+    /// Returns an error if command execution fails or the command exits
+    /// non-zero.
+    pub async fn exec_bytes(&self, command: &str) -> Result<Vec<u8>> {
+        let device = self.clone();
+        let command = command.to_string();
+        blocking(move || device.exec_bytes_blocking(&command)).await
+    }
+
+    /// Execute a command from synchronous code.
+    pub(crate) fn exec_blocking(&self, command: &str) -> Result<String> {
+        self.exec_bytes_blocking(command)
+            .map(|stdout| String::from_utf8_lossy(&stdout).into_owned())
+    }
+
+    /// Write `data` to a file on the device, creating parent directories.
+    ///
+    /// # Errors
+    ///
+    /// Returns an error if the transport cannot stream data or the write fails.
+    #[allow(clippy::unused_async)]
+    pub async fn upload(&self, data: &[u8], remote_path: impl AsRef<Path>) -> Result<()> {
+        block_in_place(|| self.upload_through(data, remote_path.as_ref(), "cat"))
+    }
+
+    /// Write zstd-compressed `data` to a file on the device, decompressing
+    /// it there with `zstd -d`.
+    ///
+    /// # Errors
+    ///
+    /// Returns an error if the transport cannot stream data, or `zstd` is
+    /// missing on the device or rejects the data.
+    #[allow(clippy::unused_async)]
+    pub async fn upload_zstd(&self, data: &[u8], remote_path: impl AsRef<Path>) -> Result<()> {
+        block_in_place(|| self.upload_through(data, remote_path.as_ref(), ZSTD_FILTER))
+    }
+
+    /// Upload shared `data` from tokio's blocking pool, decompressing it
+    /// on the device first if `zstd` is set.
+    ///
+    /// Unlike [`Self::upload`], which blocks the calling task, concurrent
+    /// calls transfer in parallel without copying the payload per device.
+    pub(crate) async fn upload_shared(
+        &self,
+        data: Arc<[u8]>,
+        remote_path: PathBuf,
+        zstd: bool,
+    ) -> Result<()> {
+        let device = self.clone();
+        let filter = if zstd { ZSTD_FILTER } else { "cat" };
+        blocking(move || device.upload_through(&data, &remote_path, filter)).await
+    }
+
+    /// Pipe `data` through `filter` into `remote_path`.
+    ///
+    /// Output goes to a temporary file that replaces `remote_path` only once
+    /// `filter` succeeds, so a failed transfer leaves the old file intact.
+    fn upload_through(&self, data: &[u8], remote_path: &Path, filter: &str) -> Result<()> {
+        let path = remote_path.to_string_lossy();
+        let dir = remote_path
+            .parent()
+            .map_or_else(|| ".".into(), Path::to_string_lossy);
+        let tmp = shell_quote(&format!("{path}.tmp"));
+        let command = format!(
+            "mkdir -p {} && {{ {filter} > {tmp} && mv -f {tmp} {}; }} || {{ s=$?; rm -f {tmp}; exit $s; }}",
+            shell_quote(&dir),
+            shell_quote(&path)
+        );
+        self.run_checked(&command, Some(data)).map(|_| ())
+    }
+
+    fn exec_bytes_blocking(&self, command: &str) -> Result<Vec<u8>> {
+        self.run_checked(command, None)
+    }
+
+    fn run_checked(&self, command: &str, input: Option<&[u8]>) -> Result<Vec<u8>> {
+        tracing::debug!(device = %self.info.id, command, "exec");
+        let started = Instant::now();
+        let result = input.map_or_else(
+            || self.executor.run(command),
+            |input| self.executor.run_with_input(command, input),
+        );
+        if let Some(auditor) = &self.auditor {
+            let outcome = CommandOutcome {
+                exit_code: result.as_ref().ok().map(|o| o.exit_code),
+                error: result.as_ref().err().map(ToString::to_string),
+                duration: started.elapsed(),
+            };
+            auditor.record(&self.info.id, command, &outcome);
+        }
+        result?.into_stdout(command)
+    }
+
+    /// Get available memory in MB.
+    ///
+    /// # Errors
+    ///
+    /// Returns an error if memory query fails.
+    pub async fn available_memory_mb(&self) -> Result<u64> {
+        // Would parse /proc/meminfo via SSH
+        Ok(self.info.model.memory_mb() / 2) // Conservative estimate
+    }
+
+    /// Query the inference server for the trueno backend actually in use.
+    ///
+    /// Reads `backend` (or `compute.backend`) from the server's telemetry
+    /// endpoint. The compute hint is advisory, so this may differ from it.
+    ///
+    /// # Errors
+    ///
+    /// Returns an error if the server does not respond or reports no backend.
+    pub async fn active_backend(&self, port: u16) -> Result<String> {
+        let body = self
+            .exec(&format!("curl -sf http://localhost:{port}/telemetry"))
+            .await?;
+        parse_telemetry_backend(&body)
+    }
+
+    /// Run the device hot to confirm its cooling works.
+    ///
+    /// Switches to MAXN, runs `stress-ng` for `duration`, and samples
+    /// temperatures and fan duty throughout. The device is left in MAXN.
+    ///
+    /// # Errors
+    ///
+    /// Returns an error if the power mode cannot be set, the load cannot be
+    /// started, or tegrastats cannot be sampled.
+    pub async fn thermal_soak(&self, duration: Duration) -> Result<SoakResult> {
+        let fan = FanController::new();
+        PowerController::new()
+            .set_mode(self, PowerMode::Maxn)
+            .await?;
+        let fan_start_pwm = fan.pwm(self).await.ok();
+        self.exec(&format!(
+            "nohup stress-ng --cpu 0 --timeout {}s >/dev/null 2>&1 &",
+            duration.as_secs().max(1)
+        ))
+        .await?;
+
+        let mut monitor = TegraMonitor::connect(self)?;
+        let throttle_c = passive_trip_c(&mut monitor).await?;
+
+        let interval = duration / SOAK_SAMPLES;
+        let mut peak_temp_c = f32::MIN;
+        let mut fan_peak_pwm = fan_start_pwm;
+        for i in 0..SOAK_SAMPLES {
+            if i > 0 {
+                tokio::time::sleep(interval).await;
+            }
+            let stats = monitor.sample_async().await?;
+            peak_temp_c = peak_temp_c.max(stats.gpu_temp).max(stats.cpu_temp);
+            if let Ok(pwm) = fan.pwm(self).await {
+                fan_peak_pwm = Some(fan_peak_pwm.map_or(pwm, |peak| peak.max(pwm)));
+            }
+        }
+
+        let result = SoakResult {
+            duration,
+            peak_temp_c,
+            throttle_c,
+            throttled: peak_temp_c >= throttle_c,
+            fan_start_pwm,
+            fan_peak_pwm,
+        };
+        tracing::info!(
+            device = %self.id(),
+            peak_temp_c,
+            throttled = result.throttled,
+            passed = result.passed(),
+            "Thermal soak finished"
+        );
+        Ok(result)
+    }
+
+    /// Benchmark a workload while profiling its thermal signature.
+    ///
+    /// Runs `command` in the background for `duration`, sampling
+    /// temperatures throughout, then waits for it to exit. If the
+    /// workload's last line of output is a number, it is taken as its
+    /// throughput in tokens/s.
+    ///
+    /// # Errors
+    ///
+    /// Returns an error if the workload cannot be started, tegrastats
+    /// cannot be sampled, or `Error::Timeout` if the workload outlives
+    /// `duration` by more than its kill grace period.
+    pub async fn benchmark(&self, command: &str, duration: Duration) -> Result<BenchResult> {
+        let started_at = Instant::now();
+        let started = self
+            .exec(&format!(
+                "log=$(mktemp {BENCH_LOG_TEMPLATE}) || exit 1; \
+                 nohup timeout -k {}s {:.3}s sh -c {} >\"$log\" 2>&1 & echo \"$! $log\"",
+                BENCH_KILL_GRACE.as_secs(),
+                duration.as_secs_f64().max(0.001),
+                shell_quote(command)
+            ))
+            .await?;
+        let (pid, log) = started
+            .trim()
+            .split_once(' ')
+            .and_then(|(pid, log)| Some((pid.parse::<u32>().ok()?, shell_quote(log))))
+            .ok_or_else(|| Error::Parse {
+                context: "benchmark start".to_string(),
+                message: format!("expected PID and log path, got: {}", started.trim()),
+            })?;
+
+        let mut monitor = TegraMonitor::connect(self)?;
+        let throttle_c = passive_trip_c(&mut monitor).await?;
+
+        let interval = duration / SOAK_SAMPLES;
+        let mut samples = Vec::with_capacity(SOAK_SAMPLES as usize);
+        for i in 0..SOAK_SAMPLES {
+            if i > 0 {
+                tokio::time::sleep(interval).await;
+            }
+            let stats = monitor.sample_async().await?;
+            samples.push((interval * i, stats.gpu_temp.max(stats.cpu_temp)));
+        }
+
+        // Throughput is only final once the workload has exited; a zombie
+        // awaiting its reaper has already released its resources, so zombies
+        // count as exited
+        let limit = duration + BENCH_KILL_GRACE + BENCH_POLL_INTERVAL;
+        while !self
+            .exec(&format!("ps -o stat= -p {pid} | grep -v Z || true"))
+            .await?
+            .trim()
+            .is_empty()
+        {
+            if started_at.elapsed() > limit {
+                return Err(Error::Timeout {
+                    operation: format!("benchmark workload {pid} on {}", self.id()),
+                    timeout_ms: u64::try_from(limit.as_millis()).unwrap_or(u64::MAX),
+                });
+            }
+            tokio::time::sleep(BENCH_POLL_INTERVAL).await;
+        }
+        let tokens_per_sec = self
+            .exec(&format!("tail -n 1 {log}; rm -f {log}"))
+            .await
+            .ok()
+            .and_then(|line| line.trim().parse().ok());
+        let result = BenchResult::from_samples(duration, tokens_per_sec, &samples, throttle_c);
+        tracing::info!(
+            device = %self.id(),
+            peak_temp = result.peak_temp,
+            throttled = result.throttled,
+            "Benchmark finished"
+        );
+        Ok(result)
+    }
+
+    /// Carrier board identifier from the device-tree compatible list.
+    ///
+    /// NVIDIA boards report the carrier part number (e.g. `p3768`);
+    /// third-party carriers report their vendor compatible string.
+    ///
+    /// # Errors
+    ///
+    /// Returns an error if the device tree cannot be read or names no carrier.
+    pub async fn carrier_board(&self) -> Result<String> {
+        let compatible = self
+            .exec("tr '\\0' '\\n' < /proc/device-tree/compatible")
+            .await?;
+        parse_carrier_id(&compatible).ok_or_else(|| Error::Parse {
+            context: "/proc/device-tree/compatible".to_string(),
+            message: format!("no carrier board in: {}", compatible.trim()),
+        })
+    }
+
+    /// Carrier board, mapped to its known characteristics.
+    ///
+    /// # Errors
+    ///
+    /// See [`Self::carrier_board`].
+    pub async fn carrier(&self) -> Result<CarrierBoard> {
+        Ok(CarrierBoard::from_id(&self.carrier_board().await?))
+    }
+
+    /// Detect the CPU's SIMD extensions from `/proc/cpuinfo`.
+    ///
+    /// # Errors
+    ///
+    /// Returns an error if cpuinfo cannot be read or has no Features line.
+    pub async fn simd_features(&self) -> Result<SimdFeatures> {
+        let cpuinfo = self.exec("cat /proc/cpuinfo").await?;
+        SimdFeatures::from_cpuinfo(&cpuinfo).ok_or_else(|| Error::Parse {
+            context: "/proc/cpuinfo".to_string(),
+            message: "no Features line".to_string(),
+        })
+    }
+
+    /// Memory split between system and iGPU carveouts.
+    ///
+    /// Carveouts are read from nvmap debugfs (root); most significant on
+    /// AGX Orin, they are usually absent on Orin Nano.
+    ///
+    /// # Errors
+    ///
+    /// Returns an error if `/proc/meminfo` has no `MemTotal`.
+    pub async fn memory_zones(&self) -> Result<MemoryZones> {
+        let meminfo = self.exec("grep MemTotal /proc/meminfo").await?;
+        let system_kb: u64 = meminfo
+            .split_whitespace()
+            .nth(1)
+            .and_then(|kb| kb.parse().ok())
+            .ok_or_else(|| Error::Parse {
+                context: "/proc/meminfo".to_string(),
+                message: format!("no MemTotal in: {}", meminfo.trim()),
+            })?;
+        let carveouts = self
+            .exec(&format!(
+                "sudo grep -H . {DEBUGFS}/nvmap/*/size 2>/dev/null || true"
+            ))
+            .await?;
+        Ok(MemoryZones {
+            system_mb: system_kb / 1024,
+            gpu_carveout_mb: parse_carveouts(&carveouts),
+            unified: self.info.model.is_unified_memory(),
+        })
+    }
+
+    /// Whether no graphical session is running.
+    ///
+    /// The device is headless when the display manager is not active and
+    /// no X or Wayland server process exists.
+    ///
+    /// # Errors
+    ///
+    /// Returns an error if the state cannot be queried.
+    pub async fn is_headless(&self) -> Result<bool> {
+        let state = self.exec(HEADLESS_PROBE).await?;
+        Ok(!state
+            .lines()
+            .any(|line| matches!(line.trim(), "active" | "activating" | "display-server")))
+    }
+
+    /// Boot to the console (`multi-user.target`) or back to the desktop.
+    ///
+    /// Only the default target changes; the running session is left alone.
+    /// Returns `true` if a reboot is required for the change to take effect,
+    /// which is when the running state differs from the one requested.
+    ///
+    /// # Errors
+    ///
+    /// Returns an error if the default target cannot be set.
+    pub async fn set_headless(&self, headless: bool) -> Result<bool> {
+        let target = if headless {
+            "multi-user.target"
+        } else {
+            "graphical.target"
+        };
+        self.exec(&format!("sudo systemctl set-default {target}"))
+            .await?;
+        Ok(self.is_headless().await? != headless)
+    }
+
+    /// Collect diagnostics into `{out_dir}/{id}-support.tar` for issue reports.
+    ///
+    /// The bundle holds the model, `JetPack` and CUDA versions, the boot's
+    /// journal, tegrastats samples, nvpmodel state, dmesg (undervoltage
+    /// warnings), storage and SMART status, and `/proc/meminfo`. A source
+    /// that fails is recorded in its file instead of aborting the bundle.
+    ///
+    /// # Errors
+    ///
+    /// Returns an error if the bundle cannot be written to `out_dir`.
+    pub async fn support_bundle(&self, out_dir: &Path) -> Result<PathBuf> {
+        let name = format!("{}-support", self.info.id);
+        let summary = format!(
+            "id: {}\nmodel: {}\nconnection: {:?}\njetpack: {}\n",
+            self.info.id,
+            self.info.model,
+            self.info.connection,
+            self.info.jetpack_version.as_deref().unwrap_or("unknown"),
+        );
+        let mut entries = vec![(format!("{name}/device.txt"), summary.into_bytes())];
+        for (file, command) in SUPPORT_SOURCES {
+            let contents = match self.exec_bytes(command).await {
+                Ok(stdout) => stdout,
+                Err(e) => format!("# {command}\n# failed: {e}\n").into_bytes(),
+            };
+            entries.push((format!("{name}/{file}"), contents));
+        }
+
+        tokio::fs::create_dir_all(out_dir).await?;
+        let path = out_dir.join(format!("{name}.tar"));
+        let mtime = SystemTime::now()
+            .duration_since(UNIX_EPOCH)
+            .map_or(0, |d| d.as_secs());
+        tokio::fs::write(&path, tar_archive(&entries, mtime)).await?;
+        Ok(path)
+    }
+
+    /// Read a sysfs (or debugfs) node, trimmed.
+    ///
+    /// Paths are expanded by the device shell, so globs such as `hwmon*`
+    /// work. debugfs nodes are read with sudo.
+    ///
+    /// # Errors
+    ///
+    /// Returns `Error::SubsystemUnavailable` if the node is missing or
+    /// not readable.
+    pub async fn read_sysfs(&self, path: &str) -> Result<String> {
+        let sudo = if path.starts_with(DEBUGFS) {
+            "sudo "
+        } else {
+            ""
+        };
+        self.exec(&format!("{sudo}cat {path}"))
+            .await
+            .map(|output| output.trim().to_string())
+            .map_err(|e| sysfs_error(path, e))
+    }
+
+    /// Read several sysfs nodes in one round trip, trimmed, in order.
+    ///
+    /// # Errors
+    ///
+    /// Returns `Error::SubsystemUnavailable` if any node is missing or not
+    /// readable, or `Error::Parse` if a glob matched more than one node.
+    pub async fn read_sysfs_many(&self, paths: &[&str]) -> Result<Vec<String>> {
+        if paths.is_empty() {
+            return Ok(Vec::new());
+        }
+        let sudo = if paths.iter().any(|p| p.starts_with(DEBUGFS)) {
+            "sudo "
+        } else {
+            ""
+        };
+        // Expand each glob first so a multi-match fails instead of being
+        // read as one concatenated value
+        let command = paths
+            .iter()
+            .map(|path| {
+                format!(
+                    "{{ set -- {path}; [ $# -eq 1 ] || {{ echo \"{path} {SYSFS_MULTI_MATCH} $#\" >&2; false; }} && {sudo}cat \"$1\"; }} && printf '\\036'"
+                )
+            })
+            .collect::<Vec<_>>()
+            .join(" && ");
+        let output = self.exec(&command).await.map_err(|e| {
+            let Error::CommandFailed { stderr, .. } = &e else {
+                return sysfs_error(paths[0], e);
+            };
+            if let Some(line) = stderr.lines().find(|l| l.contains(SYSFS_MULTI_MATCH)) {
+                return Error::Parse {
+                    context: "sysfs batch".to_string(),
+                    message: line.to_string(),
+                };
+            }
+            let failed = paths.iter().find(|p| stderr.contains(**p)).copied();
+            sysfs_error(failed.unwrap_or(paths[0]), e)
+        })?;
+        let values: Vec<String> = output
+            .split_terminator(SYSFS_SEPARATOR)
+            .map(|value| value.trim().to_string())
+            .collect();
+        if values.len() != paths.len() {
+            return Err(Error::Parse {
+                context: "sysfs batch".to_string(),
+                message: format!("expected {} values, got {}", paths.len(), values.len()),
+            });
+        }
+        Ok(values)
+    }
+
+    /// Read every node matching the glob `patterns`, as trimmed
+    /// `(path, value)` pairs.
+    ///
+    /// Patterns that match nothing are skipped.
+    ///
+    /// # Errors
+    ///
+    /// Returns `Error::SubsystemUnavailable` if a matched node is not
+    /// readable.
+    pub(crate) async fn read_sysfs_glob(&self, patterns: &[&str]) -> Result<Vec<(String, String)>> {
+        if patterns.is_empty() {
+            return Ok(Vec::new());
+        }
+        let sudo = if patterns.iter().any(|p| p.starts_with(DEBUGFS)) {
+            "sudo "
+        } else {
+            ""
+        };
+        let command = format!(
+            "for f in {}; do [ -e \"$f\" ] || continue; printf '%s\\037' \"$f\" && {sudo}cat \"$f\" && printf '\\036' || exit 1; done",
+            patterns.join(" ")
+        );
+        let output = self.exec(&command).await.map_err(|e| {
+            let failed = match &e {
+                Error::CommandFailed { stderr, .. } => {
+                    patterns.iter().find(|p| stderr.contains(**p)).copied()
+                }
+                _ => None,
+            };
+            sysfs_error(failed.unwrap_or(patterns[0]), e)
+        })?;
+        Ok(output
+            .split_terminator(SYSFS_SEPARATOR)
+            .filter_map(|record| record.trim_start().split_once(SYSFS_PATH_SEPARATOR))
+            .map(|(path, value)| (path.to_string(), value.trim().to_string()))
+            .collect())
+    }
+
+    /// Write a value to a sysfs (or debugfs) node as root.
+    ///
+    /// # Errors
+    ///
+    /// Returns `Error::SubsystemUnavailable` if the node is missing or
+    /// sudo is not permitted.
+    pub async fn write_sysfs(&self, path: &str, value: &str) -> Result<()> {
+        let value = if value
+            .chars()
+            .all(|c| c.is_ascii_alphanumeric() || "-_.".contains(c))
+        {
+            value.to_string()
+        } else {
+            shell_quote(value)
+        };
+        self.exec(&format!("echo {value} | sudo tee {path} > /dev/null"))
+            .await
+            .map(|_| ())
+            .map_err(|e| sysfs_error(path, e))
+    }
+
+    /// Get compute hint for trueno backend selection.
+    ///
+    /// SIMD features assume the Orin CPU baseline; use
+    /// [`Self::detect_compute_hint`] to probe the device.
+    #[must_use]
+    pub fn compute_hint(&self) -> ComputeHint {
+        let prefer_neon = true;
+        let cuda_available = true;
+        ComputeHint {
+            prefer_neon,
+            memory_budget_mb: self.info.model.memory_mb() / 2,
+            cuda_available,
+            backend: ComputeBackend::select(prefer_neon, cuda_available),
+            simd: SimdFeatures::orin(),
+        }
+    }
+
+    /// Compute hint carrying SIMD features detected on the device.
+    ///
+    /// # Errors
+    ///
+    /// Returns an error if SIMD features cannot be detected.
+    pub async fn detect_compute_hint(&self) -> Result<ComputeHint> {
+        Ok(ComputeHint {
+            simd: self.simd_features().await?,
+            ..self.compute_hint()
+        })
+    }
+}
+
+impl From<DeviceInfo> for JetsonDevice {
+    fn from(info: DeviceInfo) -> Self {
+        Self::new(info)
+    }
+}
+
+/// Carrier board a Jetson module is mounted on.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+pub enum CarrierBoard {
+    /// Orin Nano / Orin NX developer kit (P3768)
+    OrinDevKit,
+    /// AGX Orin developer kit (P3737)
+    AgxOrinDevKit,
+    /// Xavier NX developer kit (P3509), also used with Orin NX
+    XavierNxDevKit,
+    /// Seeed reComputer J30/J40 series
+    SeeedReComputer,
+    /// Auvidea carrier (JNX, X2xx series)
+    Auvidea,
+    /// Unrecognized carrier
+    Unknown,
+}
+
+impl CarrierBoard {
+    /// Map a carrier identifier from [`JetsonDevice::carrier_board`].
+    #[must_use]
+    pub fn from_id(id: &str) -> Self {
+        match id {
+            "p3768" => Self::OrinDevKit,
+            "p3737" => Self::AgxOrinDevKit,
+            "p3509" => Self::XavierNxDevKit,
+            id if id.starts_with("seeed,") => Self::SeeedReComputer,
+            id if id.starts_with("auvidea,") => Self::Auvidea,
+            _ => Self::Unknown,
+        }
+    }
+
+    /// PWM node of the fan header, if the carrier has a controllable fan.
+    ///
+    /// Unknown carriers are assumed to follow the devkit layout.
+    #[must_use]
+    pub const fn fan_pwm_path(&self) -> Option<&'static str> {
+        match self {
+            Self::Auvidea => None,
+            _ => Some(FAN_PWM),
+        }
+    }
+
+    /// Number of M.2 Key M slots for SSDs.
+    #[must_use]
+    pub const fn nvme_slots(&self) -> u8 {
+        match self {
+            Self::OrinDevKit => 2,
+            Self::AgxOrinDevKit
+            | Self::XavierNxDevKit
+            | Self::SeeedReComputer
+            | Self::Auvidea
+            | Self::Unknown => 1,
+        }
+    }
+}
+
+/// Lowest passive trip point, where clocks throttle (°C).
+async fn passive_trip_c(monitor: &mut TegraMonitor) -> Result<f32> {
+    Ok(monitor
+        .hw_trip_points()
+        .await?
+        .iter()
+        .filter(|t| t.kind == TripType::Passive)
+        .map(|t| t.temp_c)
+        .reduce(f32::min)
+        .unwrap_or(ORIN_PASSIVE_TRIP_C))
+}
+
+/// NVIDIA carrier board part numbers.
+const NVIDIA_CARRIERS: [&str; 3] = ["p3768", "p3737", "p3509"];
+
+/// Extract the carrier identifier from a device-tree compatible list.
+///
+/// Entries are NUL- or newline-separated. The board entry joins carrier and
+/// module part numbers with `+` (e.g. `nvidia,p3768-0000+p3767-0005`).
+fn parse_carrier_id(compatible: &str) -> Option<String> {
+    let entries: Vec<&str> = compatible
+        .split(['\0', '\n'])
+        .map(str::trim)
+        .filter(|e| !e.is_empty())
+        .collect();
+    if let Some(vendor) = entries.iter().find(|e| !e.starts_with("nvidia,")) {
+        return Some((*vendor).to_string());
+    }
+    let board = entries.iter().find(|e| e.contains('+'))?;
+    let parts: Vec<&str> = board
+        .trim_start_matches("nvidia,")
+        .split('+')
+        .filter_map(|part| part.split('-').next())
+        .collect();
+    parts
+        .iter()
+        .find(|part| NVIDIA_CARRIERS.contains(part))
+        .or_else(|| parts.first())
+        .map(|part| (*part).to_string())
+}
+
+/// ARM SIMD extensions reported in `/proc/cpuinfo`.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash, Default, Serialize, Deserialize)]
+#[allow(clippy::struct_excessive_bools)]
+pub struct SimdFeatures {
+    /// Advanced SIMD (NEON)
+    pub asimd: bool,
+    /// NEON dot product (`asimddp`)
+    pub dotprod: bool,
+    /// NEON half-precision arithmetic (`asimdhp`)
+    pub fp16: bool,
+    /// Int8 matrix multiply (`i8mm`)
+    pub i8mm: bool,
+    /// Brain floating point (`bf16`)
+    pub bf16: bool,
+    /// Scalable Vector Extension
+    pub sve: bool,
+    /// SVE2
+    pub sve2: bool,
+}
+
+impl SimdFeatures {
+    /// Features of the Orin CPU cores (`Cortex-A78AE`).
+    #[must_use]
+    pub const fn orin() -> Self {
+        Self {
+            asimd: true,
+            dotprod: true,
+            fp16: true,
+            i8mm: false,
+            bf16: false,
+            sve: false,
+            sve2: false,
+        }
+    }
+
+    /// Parse the first `Features` line of `/proc/cpuinfo`.
+    #[must_use]
+    pub fn from_cpuinfo(cpuinfo: &str) -> Option<Self> {
+        let (_, flags) = cpuinfo
+            .lines()
+            .filter_map(|line| line.split_once(':'))
+            .find(|(key, _)| key.trim() == "Features")?;
+        let mut features = Self::default();
+        for flag in flags.split_whitespace() {
+            match flag {
+                "asimd" => features.asimd = true,
+                "asimddp" => features.dotprod = true,
+                "asimdhp" => features.fp16 = true,
+                "i8mm" => features.i8mm = true,
+                "bf16" => features.bf16 = true,
+                "sve" => features.sve = true,
+                "sve2" => features.sve2 = true,
+                _ => {}
+            }
+        }
+        Some(features)
+    }
+
+    /// Guaranteed vector register width in bits.
+    ///
+    /// SVE width is implementation-defined, so this reports the 128-bit minimum.
+    #[must_use]
+    pub const fn vector_width_bits(&self) -> u32 {
+        if self.asimd || self.sve {
+            128
+        } else {
+            0
+        }
+    }
+
+    /// Features present on both sides.
+    #[must_use]
+    pub const fn intersect(&self, other: &Self) -> Self {
+        Self {
+            asimd: self.asimd && other.asimd,
+            dotprod: self.dotprod && other.dotprod,
+            fp16: self.fp16 && other.fp16,
+            i8mm: self.i8mm && other.i8mm,
+            bf16: self.bf16 && other.bf16,
+            sve: self.sve && other.sve,
+            sve2: self.sve2 && other.sve2,
+        }
     }
+}
 
-    /// Get available memory in MB.
-    ///
-    /// # Errors
-    ///
-    /// Returns an error if memory query fails.
-    pub async fn available_memory_mb(&self) -> Result<u64> {
-        // Would parse /proc/meminfo via SSH
-        Ok(self.info.model.memory_mb() / 2) // Conservative estimate
+/// trueno compute backend.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash, Serialize, Deserialize)]
+#[serde(rename_all = "lowercase")]
+pub enum ComputeBackend {
+    /// ARM NEON SIMD
+    Neon,
+    /// CUDA (limited on Jetson)
+    Cuda,
+}
+
+impl ComputeBackend {
+    /// Select a backend from hint preferences.
+    #[must_use]
+    pub const fn select(prefer_neon: bool, cuda_available: bool) -> Self {
+        if cuda_available && !prefer_neon {
+            Self::Cuda
+        } else {
+            Self::Neon
+        }
     }
 
-    /// Get compute hint for trueno backend selection.
+    /// Lowercase name as reported by trueno.
     #[must_use]
-    pub fn compute_hint(&self) -> ComputeHint {
-        ComputeHint {
-            prefer_neon: true,
-            memory_budget_mb: self.info.model.memory_mb() / 2,
-            cuda_available: true,
+    pub const fn as_str(&self) -> &'static str {
+        match self {
+            Self::Neon => "neon",
+            Self::Cuda => "cuda",
         }
     }
 }
 
+impl std::fmt::Display for ComputeBackend {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        write!(f, "{}", self.as_str())
+    }
+}
+
 /// Hint for trueno backend selection.
-#[derive(Debug, Clone)]
+///
+/// Serializable so placement decisions can be handed to repartir.
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
 pub struct ComputeHint {
     /// Prefer ARM NEON backend
     pub prefer_neon: bool,
@@ -173,6 +1441,301 @@ pub struct ComputeHint {
     pub memory_budget_mb: u64,
     /// CUDA available (limited on Jetson)
     pub cuda_available: bool,
+    /// Selected backend
+    pub backend: ComputeBackend,
+    /// CPU SIMD extensions for kernel selection
+    #[serde(default)]
+    pub simd: SimdFeatures,
+}
+
+impl ComputeHint {
+    /// Combine hints from multiple sources.
+    ///
+    /// Takes the smaller memory budget, requires CUDA on both sides, and
+    /// keeps a NEON preference from either side.
+    #[must_use]
+    pub fn merge(&self, other: &Self) -> Self {
+        let prefer_neon = self.prefer_neon || other.prefer_neon;
+        let cuda_available = self.cuda_available && other.cuda_available;
+        Self {
+            prefer_neon,
+            memory_budget_mb: self.memory_budget_mb.min(other.memory_budget_mb),
+            cuda_available,
+            backend: ComputeBackend::select(prefer_neon, cuda_available),
+            simd: self.simd.intersect(&other.simd),
+        }
+    }
+}
+
+/// Extract the backend name from an inference server telemetry document.
+fn parse_telemetry_backend(body: &str) -> Result<String> {
+    let parse_error = |message: String| Error::Parse {
+        context: "telemetry".to_string(),
+        message,
+    };
+    let json: serde_json::Value =
+        serde_json::from_str(body).map_err(|e| parse_error(e.to_string()))?;
+    json.get("backend")
+        .or_else(|| json.pointer("/compute/backend"))
+        .and_then(serde_json::Value::as_str)
+        .map(str::to_ascii_lowercase)
+        .ok_or_else(|| parse_error("no backend field".to_string()))
+}
+
+/// Map a failed sysfs access to the subsystem that owns the node.
+///
+/// Only a missing node or denied access becomes
+/// `Error::SubsystemUnavailable`. Other failures pass through unchanged,
+/// such as ssh's exit 255 for a dropped connection, so callers can still
+/// retry them.
+pub(crate) fn sysfs_error(path: &str, err: Error) -> Error {
+    const DENIED: [&str; 3] = ["Permission denied", "not permitted", "password is required"];
+    let Error::CommandFailed {
+        exit_code, stderr, ..
+    } = &err
+    else {
+        return err;
+    };
+    let stderr = stderr.trim();
+    let denied = DENIED.iter().any(|denied| stderr.contains(denied));
+    if *exit_code == 255 || !(denied || stderr.contains("No such file")) {
+        return err;
+    }
+    let reason = if path.starts_with(DEBUGFS) {
+        format!("{path} needs root and debugfs mounted at {DEBUGFS}: {stderr}")
+    } else if denied {
+        format!("{path} needs root: {stderr}")
+    } else {
+        format!("{path} is not accessible: {stderr}")
+    };
+    Error::SubsystemUnavailable {
+        subsystem: sysfs_subsystem(path),
+        reason,
+    }
+}
+
+/// Subsystem a sysfs node belongs to, from its path.
+fn sysfs_subsystem(path: &str) -> Subsystem {
+    if path.contains("thermal") {
+        Subsystem::Thermal
+    } else if path.contains("nvme") {
+        Subsystem::Nvme
+    } else {
+        Subsystem::Power
+    }
+}
+
+/// Build an uncompressed ustar archive of regular files.
+///
+/// Names longer than the 100-byte header field are preceded by a GNU
+/// long-name entry, which GNU tar, bsdtar and Python's `tarfile` all read.
+fn tar_archive(entries: &[(String, Vec<u8>)], mtime: u64) -> Vec<u8> {
+    fn octal(field: &mut [u8], value: u64) {
+        let digits = format!("{value:0width$o}", width = field.len() - 1);
+        field[..digits.len()].copy_from_slice(digits.as_bytes());
+    }
+
+    fn push(archive: &mut Vec<u8>, name: &[u8], typeflag: u8, data: &[u8], mtime: u64) {
+        let mut header = [0u8; 512];
+        let len = name.len().min(100);
+        header[..len].copy_from_slice(&name[..len]);
+        octal(&mut header[100..108], 0o644);
+        octal(&mut header[108..116], 0);
+        octal(&mut header[116..124], 0);
+        octal(&mut header[124..136], data.len() as u64);
+        octal(&mut header[136..148], mtime);
+        header[156] = typeflag;
+        header[257..263].copy_from_slice(b"ustar\0");
+        header[263..265].copy_from_slice(b"00");
+        header[148..156].fill(b' ');
+        let checksum: u64 = header.iter().map(|b| u64::from(*b)).sum();
+        octal(&mut header[148..155], checksum);
+
+        archive.extend_from_slice(&header);
+        archive.extend_from_slice(data);
+        archive.resize(archive.len().next_multiple_of(512), 0);
+    }
+
+    let mut archive = Vec::new();
+    for (name, data) in entries {
+        let name = name.as_bytes();
+        if name.len() > 100 {
+            let mut long_name = name.to_vec();
+            long_name.push(0);
+            push(&mut archive, b"././@LongLink", b'L', &long_name, mtime);
+        }
+        push(&mut archive, name, b'0', data, mtime);
+    }
+    archive.resize(archive.len() + 1024, 0);
+    archive
+}
+
+/// Quote an argument for a POSIX shell command line.
+pub(crate) fn shell_quote(arg: &str) -> String {
+    format!("'{}'", arg.replace('\'', r"'\''"))
+}
+
+/// Scripted command transport for unit tests.
+#[cfg(test)]
+pub(crate) mod mock {
+    use super::{CommandExecutor, CommandOutput, ConnectionMethod, DeviceInfo, JetsonDevice};
+    use crate::{JetsonModel, Result};
+    use std::collections::VecDeque;
+    use std::sync::{Arc, Mutex, MutexGuard, PoisonError};
+    use std::time::{Duration, Instant};
+
+    type Rule = (String, VecDeque<CommandOutput>);
+
+    /// Executor that answers commands by substring match.
+    ///
+    /// Each rule holds a queue of outputs; the last output repeats once
+    /// the queue drains. Unmatched commands succeed with empty output.
+    #[derive(Debug, Default)]
+    pub struct MockExecutor {
+        rules: Mutex<Vec<Rule>>,
+        calls: Mutex<Vec<String>>,
+        inputs: Mutex<Vec<(String, Vec<u8>)>>,
+        spans: Mutex<Vec<(String, Instant, Instant)>>,
+        latency: Duration,
+    }
+
+    impl MockExecutor {
+        pub fn new() -> Self {
+            Self::default()
+        }
+
+        /// Block each command for `latency`, like a slow link.
+        pub fn with_latency(mut self, latency: Duration) -> Self {
+            self.latency = latency;
+            self
+        }
+
+        /// Answer commands containing `pattern` with `stdout`.
+        pub fn on(self, pattern: &str, stdout: &str) -> Self {
+            self.on_seq(pattern, &[stdout])
+        }
+
+        /// Answer commands containing `pattern` with raw stdout bytes.
+        pub fn on_bytes(self, pattern: &str, stdout: &[u8]) -> Self {
+            let output = CommandOutput {
+                exit_code: 0,
+                stdout: stdout.to_vec(),
+                stderr: Vec::new(),
+            };
+            self.rules()
+                .push((pattern.to_string(), VecDeque::from([output])));
+            self
+        }
+
+        /// Answer successive commands containing `pattern` in order.
+        pub fn on_seq(self, pattern: &str, stdouts: &[&str]) -> Self {
+            let outputs = stdouts
+                .iter()
+                .map(|out| CommandOutput {
+                    exit_code: 0,
+                    stdout: out.as_bytes().to_vec(),
+                    stderr: Vec::new(),
+                })
+                .collect();
+            self.rules().push((pattern.to_string(), outputs));
+            self
+        }
+
+        /// Fail commands containing `pattern` with an exit code and stderr.
+        pub fn fail(self, pattern: &str, exit_code: i32, stderr: &str) -> Self {
+            let output = CommandOutput {
+                exit_code,
+                stdout: Vec::new(),
+                stderr: stderr.as_bytes().to_vec(),
+            };
+            self.rules()
+                .push((pattern.to_string(), VecDeque::from([output])));
+            self
+        }
+
+        /// Commands run so far.
+        pub fn calls(&self) -> Vec<String> {
+            self.calls
+                .lock()
+                .unwrap_or_else(PoisonError::into_inner)
+                .clone()
+        }
+
+        /// Commands run with piped input, with the input bytes.
+        pub fn inputs(&self) -> Vec<(String, Vec<u8>)> {
+            self.inputs
+                .lock()
+                .unwrap_or_else(PoisonError::into_inner)
+                .clone()
+        }
+
+        /// When each command containing `pattern` started and finished.
+        pub fn spans(&self, pattern: &str) -> Vec<(Instant, Instant)> {
+            self.spans
+                .lock()
+                .unwrap_or_else(PoisonError::into_inner)
+                .iter()
+                .filter(|(command, ..)| command.contains(pattern))
+                .map(|(_, start, end)| (*start, *end))
+                .collect()
+        }
+
+        /// Count of commands run that contain `pattern`.
+        pub fn count(&self, pattern: &str) -> usize {
+            self.calls().iter().filter(|c| c.contains(pattern)).count()
+        }
+
+        fn rules(&self) -> MutexGuard<'_, Vec<Rule>> {
+            self.rules.lock().unwrap_or_else(PoisonError::into_inner)
+        }
+    }
+
+    impl CommandExecutor for MockExecutor {
+        fn run(&self, command: &str) -> Result<CommandOutput> {
+            let started = Instant::now();
+            std::thread::sleep(self.latency);
+            self.spans
+                .lock()
+                .unwrap_or_else(PoisonError::into_inner)
+                .push((command.to_string(), started, Instant::now()));
+            self.calls
+                .lock()
+                .unwrap_or_else(PoisonError::into_inner)
+                .push(command.to_string());
+            let output = self
+                .rules()
+                .iter_mut()
+                .find(|(pattern, _)| command.contains(pattern.as_str()))
+                .and_then(|(_, outputs)| {
+                    if outputs.len() > 1 {
+                        outputs.pop_front()
+                    } else {
+                        outputs.front().cloned()
+                    }
+                });
+            Ok(output.unwrap_or_default())
+        }
+
+        fn run_with_input(&self, command: &str, input: &[u8]) -> Result<CommandOutput> {
+            self.inputs
+                .lock()
+                .unwrap_or_else(PoisonError::into_inner)
+                .push((command.to_string(), input.to_vec()));
+            self.run(command)
+        }
+    }
+
+    /// Build a device backed by `executor`.
+    pub fn device(id: &str, model: JetsonModel, executor: &Arc<MockExecutor>) -> JetsonDevice {
+        JetsonDevice::new(DeviceInfo {
+            id: id.to_string(),
+            model,
+            connection: ConnectionMethod::Usb,
+            jetpack_version: None,
+            hostname: None,
+        })
+        .with_executor(executor.clone())
+    }
 }
 
 #[cfg(test)]
@@ -226,11 +1789,53 @@ mod tests {
             jetpack_version: None,
             hostname: None,
         };
-        let device = JetsonDevice { info };
+        let device = JetsonDevice::new(info);
         let hint = device.compute_hint();
         assert!(hint.prefer_neon);
         assert_eq!(hint.memory_budget_mb, 4096);
         assert!(hint.cuda_available);
+        assert_eq!(hint.backend, ComputeBackend::Neon);
+    }
+
+    #[test]
+    fn test_compute_hint_serde_roundtrip() {
+        let hint = ComputeHint {
+            prefer_neon: false,
+            memory_budget_mb: 12000,
+            cuda_available: true,
+            backend: ComputeBackend::Cuda,
+            simd: SimdFeatures::orin(),
+        };
+        let json = serde_json::to_string(&hint).unwrap();
+        assert!(json.contains("\"backend\":\"cuda\""));
+        let parsed: ComputeHint = serde_json::from_str(&json).unwrap();
+        assert_eq!(parsed, hint);
+    }
+
+    #[test]
+    fn test_compute_hint_merge() {
+        let scheduler = ComputeHint {
+            prefer_neon: false,
+            memory_budget_mb: 6000,
+            cuda_available: true,
+            backend: ComputeBackend::Cuda,
+            simd: SimdFeatures::orin(),
+        };
+        let device = ComputeHint {
+            prefer_neon: false,
+            memory_budget_mb: 4096,
+            cuda_available: false,
+            backend: ComputeBackend::Neon,
+            simd: SimdFeatures::default(),
+        };
+        let merged = scheduler.merge(&device);
+        assert_eq!(merged.memory_budget_mb, 4096);
+        assert!(!merged.cuda_available);
+        assert!(!merged.prefer_neon);
+        assert_eq!(merged.backend, ComputeBackend::Neon);
+
+        let both_cuda = scheduler.merge(&scheduler);
+        assert_eq!(both_cuda.backend, ComputeBackend::Cuda);
     }
 
     #[test]
@@ -242,8 +1847,22 @@ mod tests {
             jetpack_version: None,
             hostname: None,
         };
-        let device = JetsonDevice { info };
+        let device = JetsonDevice::new(info);
+        assert_eq!(device.id(), "my-jetson");
+    }
+
+    #[test]
+    fn test_device_from_info() {
+        let info = DeviceInfo {
+            id: "my-jetson".to_string(),
+            model: JetsonModel::OrinNX16GB,
+            connection: ConnectionMethod::Usb,
+            jetpack_version: None,
+            hostname: None,
+        };
+        let device = JetsonDevice::from(info);
         assert_eq!(device.id(), "my-jetson");
+        assert_eq!(device.model(), JetsonModel::OrinNX16GB);
     }
 
     #[test]
@@ -255,7 +1874,7 @@ mod tests {
             jetpack_version: None,
             hostname: None,
         };
-        let device = JetsonDevice { info };
+        let device = JetsonDevice::new(info);
         assert_eq!(device.model(), JetsonModel::AgxOrin64GB);
     }
 
@@ -268,7 +1887,7 @@ mod tests {
             jetpack_version: Some("6.0".to_string()),
             hostname: None,
         };
-        let device = JetsonDevice { info };
+        let device = JetsonDevice::new(info);
         let retrieved = device.info();
         assert_eq!(retrieved.id, "test-device");
         assert_eq!(retrieved.model, JetsonModel::OrinNano4GB);
@@ -281,6 +1900,85 @@ mod tests {
         assert_eq!(device.info().connection, ConnectionMethod::Usb);
     }
 
+    const IP_ADDR_TWO_BOARDS: &str = "\
+1: lo    inet 127.0.0.1/8 scope host lo\\       valid_lft forever preferred_lft forever
+2: eth0    inet 10.0.0.5/24 brd 10.0.0.255 scope global eth0\\       valid_lft forever preferred_lft forever
+7: usb0    inet 192.168.55.100/24 brd 192.168.55.255 scope global dynamic usb0\\       valid_lft 3500sec preferred_lft 3500sec
+9: usb1    inet 192.168.56.100/24 brd 192.168.56.255 scope global dynamic usb1\\       valid_lft 3500sec preferred_lft 3500sec
+";
+
+    #[test]
+    fn test_parse_usb_interfaces() {
+        let interfaces = parse_usb_interfaces(IP_ADDR_TWO_BOARDS);
+        let names: Vec<&str> = interfaces.iter().map(|i| i.name.as_str()).collect();
+        assert_eq!(names, ["usb0", "usb1"]);
+        assert_eq!(interfaces[1].addr, Ipv4Addr::new(192, 168, 56, 100));
+        assert_eq!(interfaces[1].prefix_len, 24);
+        assert_eq!(interfaces[1].device_addr(), Ipv4Addr::new(192, 168, 56, 1));
+
+        // Renamed NICs are kept when on the default gadget subnet
+        let enx = "4: enx0e1f2a3b4c5d    inet 192.168.55.100/24 scope global enx0e1f2a3b4c5d\n";
+        assert_eq!(parse_usb_interfaces(enx)[0].name, "enx0e1f2a3b4c5d");
+    }
+
+    #[test]
+    fn test_discover_usb_multiple_boards() {
+        use mock::MockExecutor;
+        let host = MockExecutor::new().on("ip -4 -o addr", IP_ADDR_TWO_BOARDS);
+        let devices = JetsonDevice::discover_usb_with(&host).unwrap();
+
+        let ids: Vec<&str> = devices.iter().map(JetsonDevice::id).collect();
+        assert_eq!(ids, ["jetson-usb-usb0", "jetson-usb-usb1"]);
+        assert_eq!(devices[0].info().connection, ConnectionMethod::Usb);
+        assert_eq!(
+            devices[1].info().connection,
+            ConnectionMethod::Ethernet(IpAddr::V4(Ipv4Addr::new(192, 168, 56, 1)))
+        );
+
+        // Stable across rediscovery, regardless of listing order
+        let mut lines: Vec<&str> = IP_ADDR_TWO_BOARDS.lines().collect();
+        lines.reverse();
+        let reversed = lines.join("\n");
+        let host = MockExecutor::new().on("ip -4 -o addr", &reversed);
+        let again = JetsonDevice::discover_usb_with(&host).unwrap();
+        let again: Vec<&str> = again.iter().map(JetsonDevice::id).collect();
+        assert_eq!(again, ids);
+    }
+
+    #[tokio::test]
+    async fn test_resolved_addr() {
+        use mock::MockExecutor;
+        let usb = mock::device(
+            "j1",
+            JetsonModel::OrinNano8GB,
+            &Arc::new(MockExecutor::new()),
+        );
+        assert_eq!(
+            usb.resolved_addr().await.unwrap(),
+            IpAddr::V4(Ipv4Addr::new(192, 168, 55, 1))
+        );
+
+        let resolver = Arc::new(MockExecutor::new().on("getent", "10.0.0.9  jetson.local\n"));
+        let mdns = JetsonDevice::new(DeviceInfo {
+            id: "j2".to_string(),
+            model: JetsonModel::OrinNano8GB,
+            connection: ConnectionMethod::Mdns("jetson.local".to_string()),
+            jetpack_version: None,
+            hostname: None,
+        })
+        .with_resolver(resolver);
+        assert_eq!(
+            mdns.resolved_addr().await.unwrap(),
+            IpAddr::V4(Ipv4Addr::new(10, 0, 0, 9))
+        );
+
+        let missing = mdns.with_resolver(Arc::new(MockExecutor::new().fail("getent", 2, "")));
+        assert!(matches!(
+            missing.resolved_addr().await,
+            Err(Error::ConnectionFailed { host, .. }) if host == "jetson.local"
+        ));
+    }
+
     #[tokio::test]
     async fn test_discover_mdns() {
         let devices = JetsonDevice::discover_mdns().await.unwrap();
@@ -288,6 +1986,65 @@ mod tests {
         assert!(devices.is_empty());
     }
 
+    #[test]
+    fn test_mdns_hostname_collision() {
+        let responder = |addr: [u8; 4], serial: &str| MdnsResponder {
+            hostname: "jetson.local".to_string(),
+            addr: IpAddr::V4(Ipv4Addr::from(addr)),
+            serial: Some(serial.to_string()),
+            mac: None,
+        };
+        let responders = [
+            responder([192, 168, 1, 21], "1424321000456"),
+            responder([192, 168, 1, 20], "1424321000123"),
+            // Same device answering again
+            responder([192, 168, 1, 20], "1424321000123"),
+            responder([192, 168, 1, 22], "1424321000789"),
+            MdnsResponder {
+                hostname: "jetson-2.local".to_string(),
+                ..responder([192, 168, 1, 40], "1424321000999")
+            },
+            MdnsResponder {
+                hostname: "lab-agx.local.".to_string(),
+                addr: IpAddr::V4(Ipv4Addr::new(192, 168, 1, 30)),
+                serial: None,
+                mac: Some("48:B0:2D:00:00:01".to_string()),
+            },
+        ];
+
+        let devices = JetsonDevice::from_mdns_responders(&responders);
+        let found: Vec<(&str, &ConnectionMethod)> = devices
+            .iter()
+            .map(|d| (d.id(), &d.info().connection))
+            .collect();
+        assert_eq!(
+            found,
+            [
+                (
+                    "jetson-2",
+                    &ConnectionMethod::Mdns("jetson-2.local".to_string())
+                ),
+                (
+                    "jetson",
+                    &ConnectionMethod::Ethernet(IpAddr::V4(Ipv4Addr::new(192, 168, 1, 20)))
+                ),
+                (
+                    "jetson-3",
+                    &ConnectionMethod::Ethernet(IpAddr::V4(Ipv4Addr::new(192, 168, 1, 21)))
+                ),
+                (
+                    "jetson-4",
+                    &ConnectionMethod::Ethernet(IpAddr::V4(Ipv4Addr::new(192, 168, 1, 22)))
+                ),
+                (
+                    "lab-agx",
+                    &ConnectionMethod::Mdns("lab-agx.local".to_string())
+                ),
+            ]
+        );
+        assert_eq!(devices[2].info().hostname.as_deref(), Some("jetson.local"));
+    }
+
     #[tokio::test]
     async fn test_discover_all() {
         let devices = JetsonDevice::discover_all().await.unwrap();
@@ -316,7 +2073,7 @@ mod tests {
             jetpack_version: None,
             hostname: None,
         };
-        let device = JetsonDevice { info };
+        let device = JetsonDevice::new(info);
         let mem = device.available_memory_mb().await.unwrap();
         assert_eq!(mem, 4096); // Half of 8192
     }
@@ -330,22 +2087,17 @@ mod tests {
             jetpack_version: None,
             hostname: None,
         };
-        let device = JetsonDevice { info };
+        let device = JetsonDevice::new(info);
         let result = device.exec("ls -la").await;
         assert!(result.is_err());
     }
 
     #[test]
     fn test_compute_hint_all_models() {
-        for model in [
-            JetsonModel::OrinNano4GB,
-            JetsonModel::OrinNano8GB,
-            JetsonModel::OrinNX8GB,
-            JetsonModel::OrinNX16GB,
-            JetsonModel::AgxOrin32GB,
-            JetsonModel::AgxOrin64GB,
-            JetsonModel::Unknown,
-        ] {
+        for &model in JetsonModel::variants()
+            .iter()
+            .chain(&[JetsonModel::Unknown])
+        {
             let info = DeviceInfo {
                 id: "test".to_string(),
                 model,
@@ -353,10 +2105,497 @@ mod tests {
                 jetpack_version: None,
                 hostname: None,
             };
-            let device = JetsonDevice { info };
+            let device = JetsonDevice::new(info);
             let hint = device.compute_hint();
             assert!(hint.prefer_neon);
             assert_eq!(hint.memory_budget_mb, model.memory_mb() / 2);
         }
     }
+
+    #[tokio::test]
+    async fn test_exec_invalid_utf8_is_lossy() {
+        use mock::MockExecutor;
+
+        let raw = b"kernel: usb 1-2: \xff\xfe bad descriptor\n";
+        let exec = Arc::new(MockExecutor::new().on_bytes("journalctl", raw));
+        let device = mock::device("j1", JetsonModel::OrinNano8GB, &exec);
+
+        let text = device.exec("journalctl -k -n 1").await.unwrap();
+        assert_eq!(text, "kernel: usb 1-2: \u{FFFD}\u{FFFD} bad descriptor\n");
+
+        let bytes = device.exec_bytes("journalctl -k -n 1").await.unwrap();
+        assert_eq!(bytes, raw.to_vec());
+    }
+
+    #[test]
+    fn test_shell_quote() {
+        assert_eq!(shell_quote("/mnt/nvme/models"), "'/mnt/nvme/models'");
+        assert_eq!(shell_quote("it's here"), r"'it'\''s here'");
+    }
+
+    #[test]
+    fn test_compute_backend_display() {
+        assert_eq!(ComputeBackend::Neon.to_string(), "neon");
+        assert_eq!(ComputeBackend::Cuda.as_str(), "cuda");
+    }
+
+    #[test]
+    fn test_parse_telemetry_backend() {
+        assert_eq!(
+            parse_telemetry_backend(r#"{"backend":"CUDA","tokens":12}"#).unwrap(),
+            "cuda"
+        );
+        assert_eq!(
+            parse_telemetry_backend(r#"{"compute":{"backend":"neon"}}"#).unwrap(),
+            "neon"
+        );
+        assert!(parse_telemetry_backend(r#"{"tokens":12}"#).is_err());
+        assert!(parse_telemetry_backend("<html>").is_err());
+    }
+
+    #[tokio::test]
+    async fn test_active_backend() {
+        use mock::MockExecutor;
+
+        let exec =
+            Arc::new(MockExecutor::new().on("localhost:9090/telemetry", r#"{"backend":"neon"}"#));
+        let device = mock::device("j1", JetsonModel::OrinNano8GB, &exec);
+        assert_eq!(device.active_backend(9090).await.unwrap(), "neon");
+        assert_eq!(
+            exec.calls(),
+            vec!["curl -sf http://localhost:9090/telemetry"]
+        );
+    }
+
+    #[tokio::test]
+    async fn test_upload() {
+        use mock::MockExecutor;
+
+        let exec = Arc::new(MockExecutor::new());
+        let device = mock::device("j1", JetsonModel::OrinNano8GB, &exec);
+        device
+            .upload(b"GGUF", "/mnt/nvme/models/model.gguf")
+            .await
+            .unwrap();
+        assert_eq!(
+            exec.inputs(),
+            vec![(
+                "mkdir -p '/mnt/nvme/models' && { cat > '/mnt/nvme/models/model.gguf.tmp' && mv -f '/mnt/nvme/models/model.gguf.tmp' '/mnt/nvme/models/model.gguf'; } || { s=$?; rm -f '/mnt/nvme/models/model.gguf.tmp'; exit $s; }".to_string(),
+                b"GGUF".to_vec()
+            )]
+        );
+    }
+
+    #[tokio::test]
+    async fn test_upload_zstd_failure_keeps_existing_file() {
+        let dir = tempfile::tempdir().unwrap();
+        let path = dir.path().join("model.gguf");
+        std::fs::write(&path, b"old model").unwrap();
+        let device = local_device();
+
+        // Not zstd data: decompression fails after the shell opened its output
+        assert!(device.upload_zstd(b"not zstd", &path).await.is_err());
+        assert_eq!(std::fs::read(&path).unwrap(), b"old model");
+        assert!(!dir.path().join("model.gguf.tmp").exists());
+
+        device.upload(b"new model", &path).await.unwrap();
+        assert_eq!(std::fs::read(&path).unwrap(), b"new model");
+    }
+
+    #[tokio::test]
+    async fn test_upload_unsupported_transport() {
+        let device = JetsonDevice::new(DeviceInfo {
+            id: "j1".to_string(),
+            model: JetsonModel::OrinNano8GB,
+            connection: ConnectionMethod::Usb,
+            jetpack_version: None,
+            hostname: None,
+        });
+        assert!(device.upload(b"x", "/tmp/x").await.is_err());
+    }
+
+    #[test]
+    fn test_local_executor() {
+        let output = LocalExecutor.run("echo hi; echo oops >&2; exit 3").unwrap();
+        assert_eq!(output.exit_code, 3);
+        assert_eq!(output.stdout, b"hi\n");
+        assert!(output.into_stdout("probe").is_err());
+
+        let output = LocalExecutor.run_with_input("cat", b"piped").unwrap();
+        assert_eq!(output.into_stdout("cat").unwrap(), b"piped");
+    }
+
+    #[test]
+    fn test_parse_carrier_id() {
+        let cases = [
+            (
+                "nvidia,p3768-0000+p3767-0005\0nvidia,p3767-0005\0nvidia,tegra234\0",
+                "p3768",
+            ),
+            ("nvidia,p3737-0000+p3701-0005\0nvidia,tegra234\0", "p3737"),
+            // Module listed first, carrier as the `+` suffix
+            ("nvidia,p3767-0000+p3509-0000\0nvidia,tegra234\0", "p3509"),
+            (
+                "seeed,recomputer-j401\0nvidia,p3768-0000+p3767-0000\0nvidia,tegra234\0",
+                "seeed,recomputer-j401",
+            ),
+        ];
+        for (compatible, expected) in cases {
+            assert_eq!(parse_carrier_id(compatible).as_deref(), Some(expected));
+        }
+        assert_eq!(parse_carrier_id("nvidia,tegra234\0"), None);
+    }
+
+    #[test]
+    fn test_carrier_board_mapping() {
+        assert_eq!(CarrierBoard::from_id("p3768"), CarrierBoard::OrinDevKit);
+        assert_eq!(CarrierBoard::from_id("p3509"), CarrierBoard::XavierNxDevKit);
+        assert_eq!(
+            CarrierBoard::from_id("seeed,recomputer-j401"),
+            CarrierBoard::SeeedReComputer
+        );
+        assert_eq!(
+            CarrierBoard::from_id("auvidea,jnx42"),
+            CarrierBoard::Auvidea
+        );
+        assert_eq!(CarrierBoard::from_id("p9999"), CarrierBoard::Unknown);
+
+        assert_eq!(CarrierBoard::OrinDevKit.nvme_slots(), 2);
+        assert!(CarrierBoard::Auvidea.fan_pwm_path().is_none());
+        assert!(crate::power::FanController::for_carrier(CarrierBoard::Auvidea).is_none());
+        assert!(crate::power::FanController::for_carrier(CarrierBoard::OrinDevKit).is_some());
+    }
+
+    #[tokio::test]
+    async fn test_carrier_from_device_tree() {
+        use mock::MockExecutor;
+
+        let exec = Arc::new(MockExecutor::new().on(
+            "/proc/device-tree/compatible",
+            "nvidia,p3768-0000+p3767-0005\nnvidia,p3767-0005\nnvidia,tegra234\n",
+        ));
+        let device = mock::device("j1", JetsonModel::OrinNano8GB, &exec);
+        assert_eq!(device.carrier_board().await.unwrap(), "p3768");
+        assert_eq!(device.carrier().await.unwrap(), CarrierBoard::OrinDevKit);
+        assert_eq!(
+            exec.calls()[0],
+            "tr '\\0' '\\n' < /proc/device-tree/compatible"
+        );
+    }
+
+    const ORIN_CPUINFO: &str = "processor\t: 0
+BogoMIPS\t: 62.50
+Features\t: fp asimd evtstrm aes pmull sha1 sha2 crc32 atomics fphp asimdhp cpuid asimdrdm lrcpc dcpop asimddp uscat ilrcpc flagm paca pacg
+CPU implementer\t: 0x41
+CPU architecture: 8
+CPU part\t: 0xd42
+";
+
+    #[test]
+    fn test_simd_features_orin_cpuinfo() {
+        let features = SimdFeatures::from_cpuinfo(ORIN_CPUINFO).unwrap();
+        assert_eq!(features, SimdFeatures::orin());
+        assert!(features.asimd && features.dotprod && features.fp16);
+        assert!(!features.sve);
+        assert_eq!(features.vector_width_bits(), 128);
+    }
+
+    #[test]
+    fn test_simd_features_sve_and_missing() {
+        let features =
+            SimdFeatures::from_cpuinfo("Features\t: fp asimd sve sve2 i8mm bf16\n").unwrap();
+        assert!(features.sve && features.sve2 && features.i8mm && features.bf16);
+        assert!(!features.dotprod);
+        assert!(SimdFeatures::from_cpuinfo("processor\t: 0\n").is_none());
+    }
+
+    #[tokio::test]
+    async fn test_detect_compute_hint() {
+        use mock::MockExecutor;
+
+        let exec = Arc::new(MockExecutor::new().on("/proc/cpuinfo", "Features\t: fp asimd sve\n"));
+        let device = mock::device("j1", JetsonModel::OrinNano8GB, &exec);
+        let hint = device.detect_compute_hint().await.unwrap();
+        assert!(hint.simd.sve);
+        assert!(!hint.simd.dotprod);
+        assert_eq!(
+            hint.memory_budget_mb,
+            device.compute_hint().memory_budget_mb
+        );
+        assert_eq!(device.compute_hint().simd, SimdFeatures::orin());
+    }
+
+    #[tokio::test]
+    async fn test_read_sysfs_many_batches() {
+        use mock::MockExecutor;
+        let exec =
+            Arc::new(MockExecutor::new().on("thermal_zone0", "48500\n\x1ecpu-thermal\n\x1e"));
+        let device = mock::device("j1", JetsonModel::OrinNano8GB, &exec);
+        let values = device
+            .read_sysfs_many(&[
+                "/sys/class/thermal/thermal_zone0/temp",
+                "/sys/class/thermal/thermal_zone0/type",
+            ])
+            .await
+            .unwrap();
+        assert_eq!(values, vec!["48500", "cpu-thermal"]);
+        assert_eq!(exec.calls().len(), 1);
+        assert!(device.read_sysfs_many(&[]).await.unwrap().is_empty());
+    }
+
+    /// Device whose commands run in a local shell, for sysfs-like trees.
+    fn local_device() -> JetsonDevice {
+        JetsonDevice::new(DeviceInfo {
+            id: "local".to_string(),
+            model: JetsonModel::OrinNano8GB,
+            connection: ConnectionMethod::Usb,
+            jetpack_version: None,
+            hostname: None,
+        })
+        .with_executor(Arc::new(LocalExecutor))
+    }
+
+    #[tokio::test]
+    async fn test_read_sysfs_many_rejects_multi_match_glob() {
+        let dir = tempfile::tempdir().unwrap();
+        for (zone, temp) in [("thermal_zone0", "48500"), ("thermal_zone1", "51000")] {
+            std::fs::create_dir(dir.path().join(zone)).unwrap();
+            std::fs::write(dir.path().join(zone).join("temp"), format!("{temp}\n")).unwrap();
+        }
+        let root = dir.path().display();
+        let device = local_device();
+
+        let one = format!("{root}/thermal_zone1/temp");
+        let glob_one = format!("{root}/thermal_zone0/te*");
+        let values = device.read_sysfs_many(&[&one, &glob_one]).await.unwrap();
+        assert_eq!(values, vec!["51000", "48500"]);
+
+        let glob = format!("{root}/thermal_zone*/temp");
+        let err = device.read_sysfs_many(&[&one, &glob]).await.unwrap_err();
+        assert!(matches!(&err, Error::Parse { message, .. } if message.contains(&glob)));
+
+        let missing = format!("{root}/thermal_zone9/temp");
+        let err = device.read_sysfs_many(&[&one, &missing]).await.unwrap_err();
+        assert!(matches!(
+            err,
+            Error::SubsystemUnavailable {
+                subsystem: Subsystem::Thermal,
+                ..
+            }
+        ));
+    }
+
+    #[tokio::test]
+    async fn test_read_sysfs_glob_labels_each_node() {
+        let dir = tempfile::tempdir().unwrap();
+        for zone in ["thermal_zone0", "thermal_zone1"] {
+            std::fs::create_dir(dir.path().join(zone)).unwrap();
+            std::fs::write(dir.path().join(zone).join("type"), format!("{zone}-type\n")).unwrap();
+        }
+        let root = dir.path().display();
+        let types = format!("{root}/thermal_zone*/type");
+        let trips = format!("{root}/thermal_zone*/trip_point_*_temp");
+
+        let nodes = local_device()
+            .read_sysfs_glob(&[&types, &trips])
+            .await
+            .unwrap();
+        assert_eq!(
+            nodes,
+            vec![
+                (
+                    format!("{root}/thermal_zone0/type"),
+                    "thermal_zone0-type".to_string()
+                ),
+                (
+                    format!("{root}/thermal_zone1/type"),
+                    "thermal_zone1-type".to_string()
+                ),
+            ]
+        );
+        assert!(local_device()
+            .read_sysfs_glob(&[])
+            .await
+            .unwrap()
+            .is_empty());
+    }
+
+    #[tokio::test]
+    async fn test_read_sysfs_debugfs_uses_sudo() {
+        use mock::MockExecutor;
+        let exec = Arc::new(MockExecutor::new().on("emc/rate", "3199000000\n"));
+        let device = mock::device("j1", JetsonModel::OrinNano8GB, &exec);
+        let rate = device
+            .read_sysfs("/sys/kernel/debug/bpmp/debug/clk/emc/rate")
+            .await
+            .unwrap();
+        assert_eq!(rate, "3199000000");
+        assert!(exec.calls()[0].starts_with("sudo cat "));
+    }
+
+    #[tokio::test]
+    async fn test_write_sysfs_permission_denied() {
+        use mock::MockExecutor;
+        let exec = Arc::new(MockExecutor::new().fail("tee", 1, "sudo: a password is required\n"));
+        let device = mock::device("j1", JetsonModel::OrinNano8GB, &exec);
+        let path = "/sys/class/thermal/cooling_device0/cur_state";
+        let err = device.write_sysfs(path, "3").await.unwrap_err();
+        assert_eq!(
+            exec.calls(),
+            vec![format!("echo 3 | sudo tee {path} > /dev/null")]
+        );
+        match err {
+            Error::SubsystemUnavailable { subsystem, reason } => {
+                assert_eq!(subsystem, Subsystem::Thermal);
+                assert!(reason.contains("needs root"));
+            }
+            other => panic!("unexpected error: {other}"),
+        }
+
+        device.write_sysfs(path, "a b").await.unwrap_err();
+        assert!(exec.calls()[1].starts_with("echo 'a b' | "));
+    }
+
+    #[tokio::test]
+    async fn test_is_headless_from_display_manager_state() {
+        use mock::MockExecutor;
+        let desktop = Arc::new(MockExecutor::new().on("is-active", "active\ndisplay-server\n"));
+        let starting = Arc::new(MockExecutor::new().on("is-active", "activating\n"));
+        let console = Arc::new(MockExecutor::new().on("is-active", "inactive\n"));
+        let no_dm = Arc::new(MockExecutor::new().on("is-active", ""));
+        let headless = |exec| async move {
+            let device = mock::device("j1", JetsonModel::OrinNano8GB, &exec);
+            device.is_headless().await.unwrap()
+        };
+        assert!(!headless(desktop).await);
+        assert!(!headless(starting).await);
+        assert!(headless(console).await);
+        assert!(headless(no_dm).await);
+    }
+
+    #[tokio::test]
+    async fn test_set_headless_reports_reboot_required() {
+        use mock::MockExecutor;
+        let exec = Arc::new(
+            MockExecutor::new()
+                .on("set-default", "")
+                .on("is-active", "active\n"),
+        );
+        let device = mock::device("j1", JetsonModel::OrinNano8GB, &exec);
+        assert!(device.set_headless(true).await.unwrap());
+        assert!(!device.set_headless(false).await.unwrap());
+        let calls = exec.calls();
+        assert!(calls[0].contains("set-default multi-user.target"));
+        assert!(calls[2].contains("set-default graphical.target"));
+    }
+
+    #[tokio::test]
+    async fn test_memory_zones_agx() {
+        use mock::MockExecutor;
+        let exec = Arc::new(
+            MockExecutor::new()
+                .on("MemTotal", "MemTotal:       30536640 kB\n")
+                .on("nvmap", "/sys/kernel/debug/nvmap/vpr/size:0x2a000000\n"),
+        );
+        let device = mock::device("agx", JetsonModel::AgxOrin32GB, &exec);
+        let zones = device.memory_zones().await.unwrap();
+        assert_eq!(
+            zones,
+            MemoryZones {
+                system_mb: 29820,
+                gpu_carveout_mb: 672,
+                unified: true,
+            }
+        );
+    }
+
+    /// Names and contents of the files in a ustar archive, following GNU
+    /// long-name entries.
+    fn untar(archive: &[u8]) -> Vec<(String, String)> {
+        let mut files = Vec::new();
+        let mut long_name = None;
+        let mut offset = 0;
+        while archive[offset] != 0 {
+            let header = &archive[offset..offset + 512];
+            let name = String::from_utf8_lossy(&header[..100]);
+            let size = std::str::from_utf8(&header[124..135]).unwrap();
+            let size = usize::from_str_radix(size, 8).unwrap();
+            let data = String::from_utf8_lossy(&archive[offset + 512..offset + 512 + size]);
+            if header[156] == b'L' {
+                long_name = Some(data.trim_end_matches('\0').to_string());
+            } else {
+                let name = long_name
+                    .take()
+                    .unwrap_or_else(|| name.trim_end_matches('\0').to_string());
+                files.push((name, data.into_owned()));
+            }
+            offset += 512 + size.next_multiple_of(512);
+        }
+        files
+    }
+
+    #[test]
+    fn test_tar_archive_long_names() {
+        let long = format!("{}/journal.txt", "j".repeat(120));
+        let entries = vec![
+            ("short.txt".to_string(), b"a".to_vec()),
+            (long.clone(), b"b".to_vec()),
+        ];
+        let files = untar(&tar_archive(&entries, 0));
+        assert_eq!(
+            files,
+            vec![
+                ("short.txt".to_string(), "a".to_string()),
+                (long, "b".to_string()),
+            ]
+        );
+    }
+
+    #[tokio::test]
+    async fn test_support_bundle_contents() {
+        use mock::MockExecutor;
+        let exec = Arc::new(
+            MockExecutor::new()
+                .on(
+                    "/proc/device-tree/model",
+                    "NVIDIA Jetson Orin Nano Developer Kit",
+                )
+                .on("nv_tegra_release", "# R36 (release), REVISION: 4.0")
+                .on("meminfo", "MemTotal:        7620000 kB\n")
+                .on("dmesg", "soctherm: OC ALARM 1\n")
+                .fail("smartctl", 1, "smartctl: command not found"),
+        );
+        let device = mock::device("j1", JetsonModel::OrinNano8GB, &exec);
+        let out_dir = std::env::temp_dir().join(format!("cohete-bundle-{}", std::process::id()));
+
+        let path = device.support_bundle(&out_dir).await.unwrap();
+        assert_eq!(path, out_dir.join("j1-support.tar"));
+        let files = untar(&std::fs::read(&path).unwrap());
+        std::fs::remove_dir_all(&out_dir).unwrap();
+
+        let names: Vec<&str> = files.iter().map(|(n, _)| n.as_str()).collect();
+        for expected in [
+            "device.txt",
+            "model.txt",
+            "jetpack.txt",
+            "cuda.txt",
+            "journal.txt",
+            "thermal.txt",
+            "nvpmodel.txt",
+            "dmesg.txt",
+            "storage.txt",
+            "meminfo.txt",
+        ] {
+            assert!(names.contains(&format!("j1-support/{expected}").as_str()));
+        }
+        let file = |name: &str| {
+            let path = format!("j1-support/{name}");
+            files.iter().find(|(n, _)| *n == path).unwrap().1.clone()
+        };
+        assert!(file("device.txt").contains("Orin Nano 8GB"));
+        assert!(file("jetpack.txt").contains("R36"));
+        assert!(file("meminfo.txt").starts_with("MemTotal:"));
+        assert!(file("dmesg.txt").contains("OC ALARM"));
+        assert!(file("storage.txt").contains("failed"));
+    }
 }
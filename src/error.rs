@@ -101,6 +101,17 @@ pub enum Error {
         timeout_ms: u64,
     },
 
+    /// Remote command exited with a non-zero status
+    #[error("Command `{command}` failed with exit code {exit_code}: {stderr}")]
+    CommandFailed {
+        /// Command that was run
+        command: String,
+        /// Exit status
+        exit_code: i32,
+        /// Captured standard error
+        stderr: String,
+    },
+
     /// I/O error
     #[error("I/O error: {0}")]
     Io(#[from] io::Error),
@@ -114,11 +125,57 @@ pub enum Error {
         reason: String,
     },
 
+    /// Error raised while operating on a subsystem, see [`Error::with_subsystem`]
+    #[error("{subsystem}: {source}")]
+    InSubsystem {
+        /// Subsystem the failed operation belonged to
+        subsystem: Subsystem,
+        /// Underlying error
+        source: Box<Self>,
+    },
+
     /// Internal error
     #[error("Internal error: {0}")]
     Internal(String),
 }
 
+impl Error {
+    /// Tag the error with the subsystem of the operation that failed.
+    ///
+    /// Errors that already name a subsystem are returned unchanged, so the
+    /// innermost tag wins.
+    #[must_use]
+    pub fn with_subsystem(self, subsystem: Subsystem) -> Self {
+        if self.subsystem().is_some() {
+            return self;
+        }
+        Self::InSubsystem {
+            subsystem,
+            source: Box::new(self),
+        }
+    }
+
+    /// Subsystem the error belongs to, if known.
+    #[must_use]
+    pub const fn subsystem(&self) -> Option<Subsystem> {
+        match self {
+            Self::SubsystemUnavailable { subsystem, .. } | Self::InSubsystem { subsystem, .. } => {
+                Some(*subsystem)
+            }
+            _ => None,
+        }
+    }
+
+    /// The error beneath any subsystem tag.
+    #[must_use]
+    pub fn untagged(&self) -> &Self {
+        match self {
+            Self::InSubsystem { source, .. } => source.untagged(),
+            other => other,
+        }
+    }
+}
+
 /// Hardware subsystem identifiers.
 #[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
 #[non_exhaustive]
@@ -286,6 +343,18 @@ mod tests {
         assert!(err.to_string().contains("5000"));
     }
 
+    #[test]
+    fn test_error_command_failed() {
+        let err = Error::CommandFailed {
+            command: "nvpmodel -q".to_string(),
+            exit_code: 127,
+            stderr: "command not found".to_string(),
+        };
+        assert!(err.to_string().contains("nvpmodel -q"));
+        assert!(err.to_string().contains("127"));
+        assert!(err.to_string().contains("command not found"));
+    }
+
     #[test]
     fn test_error_subsystem_unavailable() {
         let err = Error::SubsystemUnavailable {
@@ -316,6 +385,27 @@ mod tests {
         assert!(err.to_string().contains("access denied"));
     }
 
+    #[test]
+    fn test_error_with_subsystem() {
+        let io_err = io::Error::new(io::ErrorKind::BrokenPipe, "broken pipe");
+        let err = Error::Io(io_err).with_subsystem(Subsystem::Nvme);
+        assert_eq!(err.subsystem(), Some(Subsystem::Nvme));
+        assert!(matches!(err.untagged(), Error::Io(_)));
+        assert_eq!(err.to_string(), "NVMe: I/O error: broken pipe");
+
+        // The innermost tag wins
+        let err = err.with_subsystem(Subsystem::Power);
+        assert_eq!(err.subsystem(), Some(Subsystem::Nvme));
+        let err = Error::SubsystemUnavailable {
+            subsystem: Subsystem::Thermal,
+            reason: "missing".to_string(),
+        }
+        .with_subsystem(Subsystem::Power);
+        assert_eq!(err.subsystem(), Some(Subsystem::Thermal));
+
+        assert_eq!(Error::Ssh("reset".to_string()).subsystem(), None);
+    }
+
     #[test]
     fn test_subsystem_display() {
         assert_eq!(Subsystem::TegraStats.to_string(), "tegrastats");
@@ -3,16 +3,89 @@
 //! Provides multi-device management, load balancing, and coordinated deployment.
 
 use crate::{
-    device::JetsonDevice,
-    thermal::ThermalPolicy,
-    Result,
+    config::{
+        CoheteConfig, DeviceYamlConfig, FleetConfig, FleetGroupConfig, PowerProfileYaml,
+        ThermalPoliciesConfig, ThermalPolicyYaml,
+    },
+    device::{blocking, shell_quote, ComputeBackend, ConnectionMethod, DeviceInfo, JetsonDevice},
+    memory::MemoryBudget,
+    power::{
+        AppliedProfile, ConfiguredPowerSwitch, FanController, FanSpeed, PowerController, PowerMode,
+        PowerProfile, PowerSwitch,
+    },
+    quantize::{gguf_quant_level, JetsonQuantizer, QuantLevel},
+    storage::{ManifestEntry, StorageLayout},
+    thermal::{TegraMonitor, TegraStats, ThermalCircuitBreaker, ThermalPolicy},
+    Error, JetsonModel, Result,
 };
-use std::collections::HashMap;
+use futures_util::{future, stream, Stream};
+use serde::{Deserialize, Serialize};
+use std::collections::{BTreeMap, HashMap, HashSet, VecDeque};
+use std::future::Future;
+use std::hash::{Hash, Hasher};
+use std::path::{Path, PathBuf};
+use std::sync::{Arc, Mutex, PoisonError};
+use std::time::{Duration, Instant};
 
 /// Fleet of Jetson devices.
 #[derive(Debug, Default)]
 pub struct Fleet {
+    name: String,
+    thermal: ThermalPoliciesConfig,
     devices: HashMap<String, FleetMember>,
+    heterogeneous_quant: HashSet<String>,
+    power_switch: Option<Arc<dyn PowerSwitch>>,
+    model_fetcher: Option<Arc<dyn ModelFetcher>>,
+    sessions: Mutex<HashMap<String, String>>,
+    deployed: Mutex<HashMap<String, Vec<DeployedModel>>>,
+    retry_budget: Option<RetryBudget>,
+    governed: Mutex<HashMap<String, GovernedMode>>,
+    max_model_size_gb: Option<f64>,
+    groups: HashMap<String, FleetGroupConfig>,
+    reach_retry: Option<(u32, Duration)>,
+    compress_transfers: bool,
+}
+
+/// Default delay before retrying a transient failure.
+pub const DEFAULT_RETRY_BACKOFF: Duration = Duration::from_millis(200);
+
+/// Tag naming a device's role in the fleet.
+pub const ROLE_TAG: &str = "role";
+
+/// [`ROLE_TAG`] value of a warm spare, kept cordoned until promoted.
+pub const STANDBY_ROLE: &str = "standby";
+
+/// Attempts [`Fleet::check_reachable`] makes for an mDNS device.
+pub const DEFAULT_MDNS_ATTEMPTS: u32 = 3;
+
+/// Delay before the first mDNS retry; doubled for each further attempt.
+pub const DEFAULT_MDNS_BACKOFF: Duration = Duration::from_millis(500);
+
+/// Tag naming the group a device belongs to, for per-group settings.
+pub const GROUP_TAG: &str = "group";
+
+/// Tag naming the enclosure a device shares cooling with.
+pub const ENCLOSURE_TAG: &str = "enclosure";
+
+/// Bytes per GB in model size limits.
+const BYTES_PER_GB: f64 = 1024.0 * 1024.0 * 1024.0;
+
+/// Model source and quant level deployed to a device by this fleet.
+#[derive(Debug, Clone, PartialEq, Eq)]
+struct DeployedModel {
+    source: PathBuf,
+    level: QuantLevel,
+}
+
+/// Fraction of the cap total draw must fall to before the power governor
+/// restores a mode; the gap keeps it from oscillating.
+pub const GOVERNOR_RESTORE_RATIO: f32 = 0.8;
+
+/// Mode a device had before the power governor stepped it down.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+struct GovernedMode {
+    original: PowerMode,
+    current: PowerMode,
 }
 
 /// Member of a fleet.
@@ -24,6 +97,40 @@ pub struct FleetMember {
     pub policy: ThermalPolicy,
     /// Device enabled for work
     pub enabled: bool,
+    /// Rollout priority (lowest deploys first)
+    pub priority: i32,
+    /// Device was found by the most recent discovery
+    pub online: bool,
+    /// Memory budget in MB
+    pub memory_budget_mb: u64,
+    /// No new work is scheduled; monitoring and running servers continue
+    pub cordoned: bool,
+    /// Free-form labels, e.g. `role=standby`
+    pub tags: BTreeMap<String, String>,
+    /// Power profile applied by [`Fleet::apply_power_profiles`]
+    pub power_profile: Option<PowerProfile>,
+    /// Consecutive failed attempts of [`Fleet::check_reachable`]
+    pub reach_failures: u32,
+}
+
+impl FleetMember {
+    /// Whether the device is enabled and online, i.e. can take work.
+    #[must_use]
+    pub const fn is_available(&self) -> bool {
+        self.enabled && self.online
+    }
+
+    /// Whether new work may be scheduled on the device.
+    #[must_use]
+    pub const fn is_schedulable(&self) -> bool {
+        self.is_available() && !self.cordoned
+    }
+
+    /// Whether the device is a warm standby (`role=standby`).
+    #[must_use]
+    pub fn is_standby(&self) -> bool {
+        self.tags.get(ROLE_TAG).map(String::as_str) == Some(STANDBY_ROLE)
+    }
 }
 
 impl Fleet {
@@ -39,18 +146,631 @@ impl Fleet {
     ///
     /// Returns an error if device cannot be added.
     pub fn add_device(&mut self, device: JetsonDevice, policy: ThermalPolicy) -> Result<()> {
+        self.add_device_with_priority(device, policy, 0)
+    }
+
+    /// Add a device with a rollout priority (lowest deploys first).
+    ///
+    /// # Errors
+    ///
+    /// Returns an error if device cannot be added.
+    pub fn add_device_with_priority(
+        &mut self,
+        device: JetsonDevice,
+        policy: ThermalPolicy,
+        priority: i32,
+    ) -> Result<()> {
         let id = device.id().to_string();
+        let memory_budget_mb = device.compute_hint().memory_budget_mb;
         self.devices.insert(
             id,
             FleetMember {
                 device,
                 policy,
                 enabled: true,
+                priority,
+                online: true,
+                memory_budget_mb,
+                cordoned: false,
+                tags: BTreeMap::new(),
+                power_profile: None,
+                reach_failures: 0,
             },
         );
         Ok(())
     }
 
+    /// Build a fleet from the `fleet` section of a configuration.
+    ///
+    /// # Errors
+    ///
+    /// Returns `Error::Config` for an unknown connection type or thermal
+    /// policy, or an Ethernet device without a valid IP.
+    pub fn from_config(config: &CoheteConfig) -> Result<Self> {
+        let mut fleet = Self {
+            name: config.fleet.name.clone(),
+            thermal: config.thermal.clone(),
+            max_model_size_gb: config.fleet.max_model_size_gb,
+            groups: config.fleet.groups.clone(),
+            ..Self::new()
+        };
+        for dev in &config.fleet.devices {
+            fleet.add_configured_device(config, dev)?;
+        }
+        if config.fleet.devices.iter().any(|dev| dev.power.is_some()) {
+            fleet.power_switch = Some(Arc::new(ConfiguredPowerSwitch::from_config(config)));
+        }
+        Ok(fleet)
+    }
+
+    /// Add a device declared in `config.fleet.devices`.
+    fn add_configured_device(
+        &mut self,
+        config: &CoheteConfig,
+        dev: &DeviceYamlConfig,
+    ) -> Result<()> {
+        let connection =
+            match dev.connection.as_str() {
+                "" | "usb" => ConnectionMethod::Usb,
+                "ethernet" => {
+                    let ip = dev.ip.as_deref().unwrap_or_default();
+                    ConnectionMethod::Ethernet(ip.parse().map_err(|_| {
+                        Error::Config(format!("device {}: invalid ip '{ip}'", dev.id))
+                    })?)
+                }
+                "mdns" => ConnectionMethod::Mdns(format!("{}.local", dev.id)),
+                other => {
+                    return Err(Error::Config(format!(
+                        "device {}: unknown connection '{other}'",
+                        dev.id
+                    )))
+                }
+            };
+        let policy = configured_policy(config, dev)?;
+        let device = JetsonDevice::new(DeviceInfo {
+            id: dev.id.clone(),
+            model: JetsonModel::Unknown,
+            connection,
+            jetpack_version: None,
+            hostname: None,
+        });
+        self.add_device_with_priority(device, policy, dev.deploy_priority)?;
+        if let Some(member) = self.devices.get_mut(&dev.id) {
+            member.memory_budget_mb = dev.memory_budget_mb;
+            member.tags = dev.tags.clone().into_iter().collect();
+            member.cordoned = member.is_standby();
+            member.power_profile = dev
+                .power_profile
+                .as_ref()
+                .map(PowerProfileYaml::to_profile)
+                .transpose()
+                .map_err(|e| match e {
+                    Error::Config(msg) => Error::Config(format!("device {}: {msg}", dev.id)),
+                    e => e,
+                })?;
+        }
+        Ok(())
+    }
+
+    /// Capture the fleet as a configuration; the inverse of [`Self::from_config`].
+    ///
+    /// Members are written in ID order with their connection, thermal
+    /// policy name, memory budget, rollout priority and power control.
+    ///
+    /// # Errors
+    ///
+    /// Returns `Error::Config` if a member's thermal policy matches neither
+    /// named policy of the fleet.
+    pub fn to_config(&self) -> Result<CoheteConfig> {
+        let conservative = ThermalPolicy::from(self.thermal.conservative.clone());
+        let aggressive = ThermalPolicy::from(self.thermal.aggressive.clone());
+
+        let mut ids: Vec<&String> = self.devices.keys().collect();
+        ids.sort();
+        let mut devices = Vec::with_capacity(ids.len());
+        for id in ids {
+            let member = &self.devices[id];
+            let (connection, ip) = match &member.device.info().connection {
+                ConnectionMethod::Usb => ("usb", None),
+                ConnectionMethod::Ethernet(ip) => ("ethernet", Some(ip.to_string())),
+                ConnectionMethod::Mdns(_) => ("mdns", None),
+            };
+            let thermal_policy = if member.policy == conservative {
+                "conservative"
+            } else if member.policy == aggressive {
+                "aggressive"
+            } else {
+                return Err(Error::Config(format!(
+                    "device {id}: thermal policy {:?} has no name in the fleet config",
+                    member.policy
+                )));
+            };
+            let power_profile = match &member.power_profile {
+                Some(profile) => {
+                    Some(PowerProfileYaml::from_profile(profile).ok_or_else(|| {
+                        Error::Config(format!(
+                            "device {id}: power mode {} has no preset in the fleet config",
+                            profile.mode
+                        ))
+                    })?)
+                }
+                None => None,
+            };
+            devices.push(DeviceYamlConfig {
+                id: id.clone(),
+                connection: connection.to_string(),
+                ip,
+                thermal_policy: thermal_policy.to_string(),
+                memory_budget_mb: member.memory_budget_mb,
+                deploy_priority: member.priority,
+                tags: member.tags.clone().into_iter().collect(),
+                power: self
+                    .power_switch
+                    .as_ref()
+                    .and_then(|switch| switch.power_control(id)),
+                power_profile,
+            });
+        }
+
+        Ok(CoheteConfig {
+            fleet: FleetConfig {
+                name: self.name.clone(),
+                devices,
+                max_model_size_gb: self.max_model_size_gb,
+                groups: self.groups.clone(),
+            },
+            thermal: self.thermal.clone(),
+            ..CoheteConfig::default()
+        })
+    }
+
+    /// Snapshot scheduling state, thermal policy and deployed models per
+    /// device, for diffing with [`FleetState::diff`].
+    ///
+    /// Models are those this fleet deployed, so a fleet built from config
+    /// reports none until it deploys.
+    #[must_use]
+    pub fn to_state(&self) -> FleetState {
+        let deployed = self.deployed.lock().unwrap_or_else(PoisonError::into_inner);
+        let devices = self
+            .devices
+            .iter()
+            .map(|(id, member)| {
+                let models = deployed
+                    .get(id)
+                    .into_iter()
+                    .flatten()
+                    .map(|model| {
+                        let (name, _) = quantized_target(&model.source, model.level);
+                        let state = ModelState {
+                            source: model.source.to_string_lossy().into_owned(),
+                            quant: model.level,
+                        };
+                        (name, state)
+                    })
+                    .collect();
+                let state = DeviceState {
+                    enabled: member.enabled,
+                    cordoned: member.cordoned,
+                    thermal_policy: ThermalPolicyYaml::from(&member.policy),
+                    power_profile: member
+                        .power_profile
+                        .as_ref()
+                        .and_then(PowerProfileYaml::from_profile),
+                    models,
+                };
+                (id.clone(), state)
+            })
+            .collect();
+        drop(deployed);
+        FleetState { devices }
+    }
+
+    /// Converge on `config`, acting only where it differs from `current`,
+    /// typically [`Self::to_state`] or a stored snapshot.
+    ///
+    /// Deploys changed models, applies changed power profiles and thermal
+    /// policies, and adds or removes devices, so applying an unchanged
+    /// config does nothing. Enabled and cordoned state is operational and
+    /// left alone, as are models no longer in the config.
+    ///
+    /// Each changed model's source is fetched once on this host with the
+    /// fleet's [`ModelFetcher`] before any device is touched, then uploaded
+    /// to every device deploying it and quantized there.
+    /// [`CoheteConfig::apply_diff`] does the same for a fleet built from the
+    /// config.
+    ///
+    /// # Errors
+    ///
+    /// Returns `Error::Config` if the config is invalid (see
+    /// [`FleetState::from_config`]) or a source cannot be fetched, or an
+    /// error if an action fails; actions already taken are not rolled back.
+    pub async fn apply_diff(
+        &mut self,
+        config: &CoheteConfig,
+        current: &FleetState,
+    ) -> Result<ApplyReport> {
+        let desired = FleetState::from_config(config)?;
+        let diff = current.diff(&desired);
+        let mut changes = diff.changes;
+        for id in &diff.added {
+            let after = &desired.devices[id];
+            let before = DeviceState {
+                power_profile: None,
+                models: BTreeMap::new(),
+                ..after.clone()
+            };
+            changes.extend(before.changes_to(id, after));
+        }
+
+        let mut artifacts: HashMap<&str, Vec<u8>> = HashMap::new();
+        for change in &changes {
+            let DeviceChange::Model { to: Some(to), .. } = change else {
+                continue;
+            };
+            if !artifacts.contains_key(to.source.as_str()) {
+                artifacts.insert(&to.source, self.fetch_model(&to.source).await?);
+            }
+        }
+
+        let mut report = ApplyReport::default();
+        for id in &diff.removed {
+            self.remove_device(id);
+            report.removed_devices.push(id.clone());
+        }
+        for id in &diff.added {
+            if !self.devices.contains_key(id) {
+                if let Some(dev) = config.fleet.devices.iter().find(|d| &d.id == id) {
+                    self.add_configured_device(config, dev)?;
+                }
+            }
+            report.added_devices.push(id.clone());
+        }
+
+        for change in &changes {
+            let member = self
+                .devices
+                .get_mut(change.device_id())
+                .ok_or_else(|| Error::DeviceNotFound(change.device_id().to_string()))?;
+            match change {
+                DeviceChange::ThermalPolicy { device_id, to, .. } => {
+                    member.policy = to.clone().into();
+                    report.thermal_policies.push(device_id.clone());
+                }
+                DeviceChange::PowerProfile { device_id, to, .. } => {
+                    let profile = to.as_ref().map(PowerProfileYaml::to_profile).transpose()?;
+                    if let Some(profile) = &profile {
+                        PowerController::new()
+                            .apply_profile(&member.device, profile)
+                            .await?;
+                        report.power_profiles.push(device_id.clone());
+                    }
+                    member.power_profile = profile;
+                }
+                DeviceChange::Model {
+                    device_id,
+                    model,
+                    to: Some(state),
+                    ..
+                } => {
+                    let source = Path::new(&state.source);
+                    let device = &self.devices[device_id].device;
+                    let data = &artifacts[state.source.as_str()];
+                    let staged = self.stage_model(device, source, data).await?;
+                    load_quantized(device, &staged, state.quant).await?;
+                    self.record_deployment(device, source, state.quant).await?;
+                    tracing::info!(device = %device_id, model = %model, quant = %state.quant, "Model deployed");
+                    report.deployed.push((device_id.clone(), model.clone()));
+                }
+                DeviceChange::Enabled { .. }
+                | DeviceChange::Cordoned { .. }
+                | DeviceChange::Model { to: None, .. } => {}
+            }
+        }
+        Ok(report)
+    }
+
+    /// Set the fetcher [`Self::apply_diff`] uses for model sources.
+    ///
+    /// The default, [`HostModelFetcher`], only reads paths on this host.
+    #[must_use]
+    pub fn with_model_fetcher(mut self, fetcher: Arc<dyn ModelFetcher>) -> Self {
+        self.model_fetcher = Some(fetcher);
+        self
+    }
+
+    /// Fetch a model source on this host with the fleet's fetcher.
+    async fn fetch_model(&self, source: &str) -> Result<Vec<u8>> {
+        let fetcher = self
+            .model_fetcher
+            .clone()
+            .unwrap_or_else(|| Arc::new(HostModelFetcher));
+        let source = source.to_string();
+        blocking(move || fetcher.fetch(&source)).await
+    }
+
+    /// Upload a fetched F16 model into a device's models directory for
+    /// on-device quantization, returning its path there.
+    async fn stage_model(
+        &self,
+        device: &JetsonDevice,
+        source: &Path,
+        data: &[u8],
+    ) -> Result<PathBuf> {
+        self.check_model_size(device.id(), data.len() as u64)?;
+        let layout = StorageLayout::default();
+        layout.preflight(device, data.len() as u64).await?;
+        let staged = layout
+            .models_dir
+            .join(format!("{}.f16.gguf", model_name(source)));
+        self.with_retries(device.id(), || device.upload(data, &staged))
+            .await?;
+        Ok(staged)
+    }
+
+    /// Compare each declared device's live state with `config`.
+    ///
+    /// Checks the power preset's nvpmodel mode, an explicit fan speed,
+    /// `provision.sysctls`, `provision.packages` and the models targeting
+    /// the device (by name and, if set, quantization) against the device's
+    /// manifest. Declared devices missing from the fleet are reported as
+    /// [`DriftItem::NotInFleet`], and devices that cannot be queried as
+    /// [`DriftItem::QueryFailed`]; offline ones are skipped. Devices without
+    /// drift map to an empty list.
+    ///
+    /// # Errors
+    ///
+    /// Returns `Error::Config` for an invalid power preset, model alias or
+    /// quantization in `config`.
+    pub async fn detect_drift(
+        &self,
+        config: &CoheteConfig,
+    ) -> Result<HashMap<String, Vec<DriftItem>>> {
+        let mut report = HashMap::new();
+        for declared in &config.fleet.devices {
+            let id = &declared.id;
+            let Some(member) = self.devices.get(id) else {
+                report.insert(id.clone(), vec![DriftItem::NotInFleet]);
+                continue;
+            };
+            if !member.online {
+                continue;
+            }
+            let drift = match device_drift(config, declared, &member.device).await {
+                Ok(drift) => drift,
+                Err(e) if matches!(e.untagged(), Error::Config(_)) => return Err(e),
+                Err(e) => {
+                    tracing::warn!(device = %id, error = %e, "Drift check failed");
+                    vec![DriftItem::QueryFailed(e.to_string())]
+                }
+            };
+            report.insert(id.clone(), drift);
+        }
+        Ok(report)
+    }
+
+    /// Set the power switch used by [`Self::hard_reboot`].
+    #[must_use]
+    pub fn with_power_switch(mut self, switch: Arc<dyn PowerSwitch>) -> Self {
+        self.power_switch = Some(switch);
+        self
+    }
+
+    /// Reboot a device, power cycling it if a soft reboot fails.
+    ///
+    /// # Errors
+    ///
+    /// Returns an error if the device is not in the fleet, or the soft
+    /// reboot fails and no power switch is set or power cycling fails.
+    pub async fn hard_reboot(&self, device_id: &str) -> Result<RebootMethod> {
+        let member = self
+            .devices
+            .get(device_id)
+            .ok_or_else(|| Error::DeviceNotFound(device_id.to_string()))?;
+        let Err(soft) = member.device.exec("sudo systemctl reboot").await else {
+            return Ok(RebootMethod::Soft);
+        };
+        let Some(switch) = &self.power_switch else {
+            return Err(soft);
+        };
+        tracing::warn!(device = device_id, error = %soft, "Soft reboot failed");
+        switch.power_cycle(device_id).await?;
+        Ok(RebootMethod::PowerCycle)
+    }
+
+    /// Set the power profile a device should run with.
+    ///
+    /// # Errors
+    ///
+    /// Returns `Error::DeviceNotFound` if the device is not in the fleet.
+    pub fn set_power_profile(&mut self, id: &str, profile: PowerProfile) -> Result<()> {
+        self.devices
+            .get_mut(id)
+            .ok_or_else(|| Error::DeviceNotFound(id.to_string()))?
+            .power_profile = Some(profile);
+        Ok(())
+    }
+
+    /// Apply each available device's power profile.
+    ///
+    /// Devices without a profile are left alone and not reported.
+    pub async fn apply_power_profiles(&self) -> HashMap<String, Result<()>> {
+        let controller = PowerController::new();
+        let mut results = HashMap::new();
+        for (id, member, profile) in self.profiled_members() {
+            let result = controller.apply_profile(&member.device, profile).await;
+            if let Err(e) = &result {
+                tracing::warn!(device = %id, error = %e, "Power profile not applied");
+            }
+            results.insert(id.clone(), result);
+        }
+        results
+    }
+
+    /// Apply each available device's power profile, then read it back.
+    ///
+    /// A device whose mode, clocks or fan disagree with its profile after
+    /// applying reports `Error::PowerMode`; others report what was read.
+    pub async fn apply_power_profiles_verified(&self) -> HashMap<String, Result<AppliedProfile>> {
+        let controller = PowerController::new();
+        let mut results = HashMap::new();
+        for (id, member, profile) in self.profiled_members() {
+            let result = match controller.apply_profile(&member.device, profile).await {
+                Ok(()) => controller.verify_profile(&member.device, profile).await,
+                Err(e) => Err(e),
+            };
+            if let Err(e) = &result {
+                tracing::warn!(device = %id, error = %e, "Power profile not verified");
+            }
+            results.insert(id.clone(), result);
+        }
+        results
+    }
+
+    /// Keep total measured draw under `cap_watts` until the future is dropped.
+    ///
+    /// Runs [`Self::govern_power`] every `interval`; failures are logged
+    /// and the loop carries on.
+    pub async fn run_power_governor(&self, cap_watts: f32, interval: Duration) {
+        loop {
+            if let Err(e) = self.govern_power(cap_watts).await {
+                tracing::warn!(cap_watts, error = %e, "Power governor step failed");
+            }
+            tokio::time::sleep(interval).await;
+        }
+    }
+
+    /// One power governor step: sample draw and adjust at most one device.
+    ///
+    /// Over the cap, the highest-drawing device that can go lower steps down
+    /// one mode (MAXN to 15W to 7W). Once draw falls to
+    /// [`GOVERNOR_RESTORE_RATIO`] of the cap, the lowest-drawing stepped-down
+    /// device steps back up toward the mode it had.
+    ///
+    /// # Errors
+    ///
+    /// Returns an error if no available device could be sampled, or a mode
+    /// cannot be read or set.
+    pub async fn govern_power(&self, cap_watts: f32) -> Result<GovernorStep> {
+        let mut draws: Vec<(String, f32)> = Vec::new();
+        let mut first_error = None;
+        for (id, stats) in self.sample_all().await {
+            match stats {
+                Ok(stats) => draws.push((id, stats.power_watts)),
+                Err(e) => {
+                    first_error.get_or_insert(e);
+                }
+            }
+        }
+        if let (true, Some(e)) = (draws.is_empty(), first_error) {
+            return Err(e);
+        }
+        let total_watts: f32 = draws.iter().map(|(_, w)| w).sum();
+        draws.sort_by(|a, b| b.1.total_cmp(&a.1).then_with(|| a.0.cmp(&b.0)));
+
+        let controller = PowerController::new();
+        let action = if total_watts > cap_watts {
+            self.governor_step_down(&controller, &draws).await?
+        } else if total_watts <= cap_watts * GOVERNOR_RESTORE_RATIO {
+            self.governor_step_up(&controller, &draws).await?
+        } else {
+            GovernorAction::Hold
+        };
+        if action != GovernorAction::Hold {
+            tracing::info!(total_watts, cap_watts, action = ?action, "Power governor");
+        }
+        Ok(GovernorStep {
+            total_watts,
+            action,
+        })
+    }
+
+    async fn governor_step_down(
+        &self,
+        controller: &PowerController,
+        draws: &[(String, f32)],
+    ) -> Result<GovernorAction> {
+        for (id, _) in draws {
+            let device = &self.devices[id].device;
+            let tracked = self.governed_mode(id);
+            let current = match tracked {
+                Some(mode) => mode.current,
+                None => controller.current_mode(device).await?,
+            };
+            let Some(lower) = current.step_down() else {
+                continue;
+            };
+            controller.set_mode(device, lower).await?;
+            self.governed
+                .lock()
+                .unwrap_or_else(PoisonError::into_inner)
+                .insert(
+                    id.clone(),
+                    GovernedMode {
+                        original: tracked.map_or(current, |mode| mode.original),
+                        current: lower,
+                    },
+                );
+            return Ok(GovernorAction::StepDown {
+                device_id: id.clone(),
+                from: current,
+                to: lower,
+            });
+        }
+        Ok(GovernorAction::Hold)
+    }
+
+    async fn governor_step_up(
+        &self,
+        controller: &PowerController,
+        draws: &[(String, f32)],
+    ) -> Result<GovernorAction> {
+        let Some((id, mode)) = draws
+            .iter()
+            .rev()
+            .find_map(|(id, _)| Some((id, self.governed_mode(id)?)))
+        else {
+            return Ok(GovernorAction::Hold);
+        };
+        let higher = mode.current.step_up().unwrap_or(mode.original);
+        controller
+            .set_mode(&self.devices[id].device, higher)
+            .await?;
+        let mut governed = self.governed.lock().unwrap_or_else(PoisonError::into_inner);
+        if higher == mode.original {
+            governed.remove(id);
+        } else {
+            governed.insert(
+                id.clone(),
+                GovernedMode {
+                    current: higher,
+                    ..mode
+                },
+            );
+        }
+        drop(governed);
+        Ok(GovernorAction::StepUp {
+            device_id: id.clone(),
+            from: mode.current,
+            to: higher,
+        })
+    }
+
+    fn governed_mode(&self, id: &str) -> Option<GovernedMode> {
+        self.governed
+            .lock()
+            .unwrap_or_else(PoisonError::into_inner)
+            .get(id)
+            .copied()
+    }
+
+    fn profiled_members(&self) -> impl Iterator<Item = (&String, &FleetMember, &PowerProfile)> {
+        self.devices
+            .iter()
+            .filter(|(_, m)| m.is_available())
+            .filter_map(|(id, m)| m.power_profile.as_ref().map(|p| (id, m, p)))
+    }
+
     /// Remove a device from the fleet.
     pub fn remove_device(&mut self, id: &str) -> Option<FleetMember> {
         self.devices.remove(id)
@@ -85,310 +805,4939 @@ impl Fleet {
         self.devices.get(id)
     }
 
-    /// Deploy model to all fleet devices.
+    /// Stop scheduling new work on a device, leaving it monitored and
+    /// its running servers untouched. Use to drain before maintenance.
     ///
     /// # Errors
     ///
-    /// Returns an error if deployment fails on any device.
-    pub async fn deploy_model(&self, _model: impl AsRef<[u8]>) -> Result<()> {
-        // Would deploy model to each device
-        Ok(())
+    /// Returns `Error::DeviceNotFound` if the device is not in the fleet.
+    pub fn cordon(&mut self, id: &str) -> Result<()> {
+        self.set_cordoned(id, true)
     }
 
-    /// Start inference servers on all devices.
+    /// Allow new work on a cordoned device again.
     ///
     /// # Errors
     ///
-    /// Returns an error if server start fails.
-    pub async fn start_inference_servers(&self) -> Result<()> {
-        // Would start realizar on each device
+    /// Returns `Error::DeviceNotFound` if the device is not in the fleet.
+    pub fn uncordon(&mut self, id: &str) -> Result<()> {
+        self.set_cordoned(id, false)
+    }
+
+    fn set_cordoned(&mut self, id: &str, cordoned: bool) -> Result<()> {
+        let member = self
+            .devices
+            .get_mut(id)
+            .ok_or_else(|| Error::DeviceNotFound(id.to_string()))?;
+        member.cordoned = cordoned;
+        tracing::info!(device = id, cordoned, "Scheduling changed");
         Ok(())
     }
 
-    /// Get fleet health status.
+    /// Retry transient failures of fleet operations from a shared budget.
+    ///
+    /// Without a budget, failures are returned without retrying.
     #[must_use]
-    pub fn health_status(&self) -> FleetHealth {
-        let total = self.len();
-        let enabled = self.enabled_count();
-        FleetHealth {
-            total_devices: total,
-            enabled_devices: enabled,
-            healthy_devices: enabled, // Placeholder
-            degraded_devices: 0,
-            offline_devices: total - enabled,
-        }
+    pub const fn with_retry_budget(mut self, budget: RetryBudget) -> Self {
+        self.retry_budget = Some(budget);
+        self
     }
-}
 
-/// Fleet health summary.
-#[derive(Debug, Clone)]
-pub struct FleetHealth {
-    /// Total devices in fleet
-    pub total_devices: usize,
-    /// Enabled devices
-    pub enabled_devices: usize,
-    /// Healthy devices (thermal OK, memory OK)
-    pub healthy_devices: usize,
-    /// Degraded devices (thermal warning)
-    pub degraded_devices: usize,
-    /// Offline devices
-    pub offline_devices: usize,
-}
+    /// zstd-compress transfers in [`Self::deploy_model`] and
+    /// [`Self::deploy_model_thermal_aware`].
+    ///
+    /// The model is compressed once on the host and decompressed on each
+    /// device, but only when a sample of it shrinks by at least
+    /// [`MIN_TRANSFER_SAVING`]; quantized or already compressed data is sent
+    /// as is.
+    #[must_use]
+    pub const fn with_compressed_transfers(mut self, compress: bool) -> Self {
+        self.compress_transfers = compress;
+        self
+    }
 
-impl FleetHealth {
-    /// Get health percentage.
+    /// Reject models larger than `gb` in [`Self::deploy_model`].
     #[must_use]
-    pub fn health_percent(&self) -> f32 {
-        if self.total_devices == 0 {
-            return 100.0;
-        }
-        (self.healthy_devices as f32 / self.total_devices as f32) * 100.0
+    pub const fn with_max_model_size_gb(mut self, gb: f64) -> Self {
+        self.max_model_size_gb = Some(gb);
+        self
     }
-}
 
-/// Jetson executor for repartir integration.
-#[cfg(feature = "batuta")]
-#[derive(Debug)]
-pub struct JetsonExecutor {
-    /// Target IP address
-    pub ip: String,
-    /// Thermal policy
-    pub policy: ThermalPolicy,
-    /// Memory budget in MB
-    pub memory_budget_mb: u64,
-}
+    /// Override the model size cap for devices tagged `group: {group}`.
+    #[must_use]
+    pub fn with_group_max_model_size_gb(mut self, group: &str, gb: f64) -> Self {
+        self.groups
+            .entry(group.to_string())
+            .or_default()
+            .max_model_size_gb = Some(gb);
+        self
+    }
 
-#[cfg(feature = "batuta")]
-impl JetsonExecutor {
-    /// Create new executor.
+    /// Model size cap applying to a device: its group's, else the fleet's.
     #[must_use]
-    pub fn new(ip: impl Into<String>) -> Self {
-        Self {
-            ip: ip.into(),
-            policy: ThermalPolicy::default(),
-            memory_budget_mb: 6000,
+    pub fn max_model_size_gb_for(&self, device_id: &str) -> Option<f64> {
+        let group_cap = self
+            .devices
+            .get(device_id)
+            .and_then(|member| member.tags.get(GROUP_TAG))
+            .and_then(|group| self.groups.get(group))
+            .and_then(|group| group.max_model_size_gb);
+        group_cap.or(self.max_model_size_gb)
+    }
+
+    /// Refuse a model of `size_bytes` over the device's size cap.
+    fn check_model_size(&self, device_id: &str, size_bytes: u64) -> Result<()> {
+        let Some(cap_gb) = self.max_model_size_gb_for(device_id) else {
+            return Ok(());
+        };
+        #[allow(clippy::cast_precision_loss)]
+        let size_gb = size_bytes as f64 / BYTES_PER_GB;
+        if size_gb > cap_gb {
+            return Err(Error::Config(format!(
+                "device {device_id}: model is {size_gb:.2}GB, over the {cap_gb}GB cap"
+            )));
         }
+        Ok(())
     }
 
-    /// Set thermal policy.
+    /// Set how often [`Self::check_reachable`] tries an mDNS device, and
+    /// the delay before the first retry.
     #[must_use]
-    pub fn with_thermal_policy(mut self, policy: ThermalPolicy) -> Self {
-        self.policy = policy;
+    pub const fn with_mdns_retry(mut self, attempts: u32, backoff: Duration) -> Self {
+        self.reach_retry = Some((attempts, backoff));
         self
     }
 
-    /// Set memory budget.
-    #[must_use]
-    pub fn with_memory_budget_mb(mut self, budget: u64) -> Self {
-        self.memory_budget_mb = budget;
-        self
+    /// Check a device is reachable, updating its online state.
+    ///
+    /// mDNS over Wi-Fi drops answers, so an mDNS device is tried up to
+    /// [`DEFAULT_MDNS_ATTEMPTS`] times (see [`Self::with_mdns_retry`]) with
+    /// doubling backoff; other devices are tried once. Each failed attempt
+    /// adds to [`FleetMember::reach_failures`] and success resets it. The
+    /// device is marked offline only when every attempt fails.
+    ///
+    /// # Errors
+    ///
+    /// Returns `Error::DeviceNotFound` if the device is not in the fleet.
+    pub async fn check_reachable(&mut self, device_id: &str) -> Result<bool> {
+        let (attempts, backoff) = self
+            .reach_retry
+            .unwrap_or((DEFAULT_MDNS_ATTEMPTS, DEFAULT_MDNS_BACKOFF));
+        let member = self
+            .devices
+            .get_mut(device_id)
+            .ok_or_else(|| Error::DeviceNotFound(device_id.to_string()))?;
+        let attempts = match member.device.info().connection {
+            ConnectionMethod::Mdns(_) => attempts.max(1),
+            _ => 1,
+        };
+        for attempt in 0..attempts {
+            if attempt > 0 {
+                tokio::time::sleep(backoff.saturating_mul(1 << (attempt - 1).min(16))).await;
+            }
+            if member.device.is_reachable().await {
+                member.reach_failures = 0;
+                member.online = true;
+                return Ok(true);
+            }
+            member.reach_failures += 1;
+            tracing::debug!(
+                device = device_id,
+                failures = member.reach_failures,
+                "Device unreachable"
+            );
+        }
+        if member.online {
+            tracing::warn!(
+                device = device_id,
+                attempts,
+                "Device unreachable on every attempt, marking offline"
+            );
+        }
+        member.online = false;
+        Ok(false)
     }
-}
 
-/// Deployment configuration.
-#[derive(Debug, Clone)]
-pub struct DeploymentConfig {
-    /// Target devices (empty = all)
-    pub target_devices: Vec<String>,
-    /// Model quantization level
-    pub quantization: Option<String>,
-    /// Memory budget per device in MB
-    pub memory_budget_mb: u64,
-    /// Thermal policy
-    pub thermal_policy: ThermalPolicy,
-}
+    /// Retry budget shared by fleet operations, if set.
+    #[must_use]
+    pub const fn retry_budget(&self) -> Option<&RetryBudget> {
+        self.retry_budget.as_ref()
+    }
 
-impl Default for DeploymentConfig {
-    fn default() -> Self {
-        Self {
-            target_devices: Vec::new(),
-            quantization: Some("q4_0".to_string()),
-            memory_budget_mb: 6000,
-            thermal_policy: ThermalPolicy::default(),
+    /// Run an operation, retrying transient failures while the fleet's
+    /// retry budget lasts.
+    async fn with_retries<T, F, Fut>(&self, device_id: &str, mut op: F) -> Result<T>
+    where
+        F: FnMut() -> Fut,
+        Fut: Future<Output = Result<T>>,
+    {
+        loop {
+            let err = match op().await {
+                Err(e) if is_transient(&e) => e,
+                result => return result,
+            };
+            let Some(budget) = &self.retry_budget else {
+                return Err(err);
+            };
+            if !budget.try_acquire() {
+                tracing::warn!(device = device_id, error = %err, "Retry budget exhausted");
+                return Err(err);
+            }
+            tracing::debug!(device = device_id, error = %err, "Retrying transient failure");
+            tokio::time::sleep(budget.backoff).await;
         }
     }
-}
 
-#[cfg(test)]
-mod tests {
-    use super::*;
-    use crate::device::{ConnectionMethod, DeviceInfo};
-
-    fn make_test_device(id: &str, model: crate::JetsonModel) -> JetsonDevice {
-        JetsonDevice {
-            info: DeviceInfo {
-                id: id.to_string(),
-                model,
-                connection: ConnectionMethod::Usb,
-                jetpack_version: None,
-                hostname: None,
-            },
+    /// Run a command on every available device.
+    ///
+    /// Transient failures are retried from the fleet's retry budget.
+    pub async fn exec_all(&self, command: &str) -> HashMap<String, Result<String>> {
+        let mut results = HashMap::new();
+        for (id, member) in self.devices.iter().filter(|(_, m)| m.is_available()) {
+            let result = self.with_retries(id, || member.device.exec(command)).await;
+            results.insert(id.clone(), result);
         }
+        results
+    }
+
+    /// Write a support bundle for every available device into `out_dir`.
+    ///
+    /// See [`JetsonDevice::support_bundle`]; failures are reported per device.
+    pub async fn support_bundle_all(&self, out_dir: &Path) -> HashMap<String, Result<PathBuf>> {
+        let mut bundles = HashMap::new();
+        for (id, member) in self.devices.iter().filter(|(_, m)| m.is_available()) {
+            bundles.insert(id.clone(), member.device.support_bundle(out_dir).await);
+        }
+        bundles
+    }
+
+    /// Set a tag on a device.
+    ///
+    /// # Errors
+    ///
+    /// Returns `Error::DeviceNotFound` if the device is not in the fleet.
+    pub fn set_tag(
+        &mut self,
+        id: &str,
+        key: impl Into<String>,
+        value: impl Into<String>,
+    ) -> Result<()> {
+        self.devices
+            .get_mut(id)
+            .ok_or_else(|| Error::DeviceNotFound(id.to_string()))?
+            .tags
+            .insert(key.into(), value.into());
+        Ok(())
+    }
+
+    /// Fail over from a device to a warm standby.
+    ///
+    /// Cordons `failed_id`, picks the first available device tagged
+    /// `role=standby` in rollout order, deploys to it any model this fleet
+    /// quantized onto the failed device that it lacks, then uncordons it
+    /// and drops its standby tag. Returns `None` if no standby is available.
+    /// Raw uploads from [`Self::deploy_model`] are not replayed.
+    ///
+    /// # Errors
+    ///
+    /// Returns `Error::DeviceNotFound` if `failed_id` is not in the fleet,
+    /// or an error if the model cannot be deployed to the standby.
+    pub async fn promote_standby(&mut self, failed_id: &str) -> Result<Option<String>> {
+        self.cordon(failed_id)?;
+        let Some(standby) = self
+            .devices
+            .values()
+            .filter(|m| m.is_available() && m.is_standby() && m.device.id() != failed_id)
+            .min_by(|a, b| (a.priority, a.device.id()).cmp(&(b.priority, b.device.id())))
+        else {
+            tracing::error!(device = failed_id, "No standby available for failover");
+            return Ok(None);
+        };
+
+        let models = self
+            .deployed
+            .lock()
+            .unwrap_or_else(PoisonError::into_inner)
+            .get(failed_id)
+            .cloned()
+            .unwrap_or_default();
+        let manifest = StorageLayout::default()
+            .read_manifest(&standby.device)
+            .await?;
+        for model in models {
+            let (name, _) = quantized_target(&model.source, model.level);
+            if manifest.get(&name).is_some_and(|e| e.quant == model.level) {
+                continue;
+            }
+            load_quantized(&standby.device, &model.source, model.level).await?;
+            self.record_deployment(&standby.device, &model.source, model.level)
+                .await?;
+        }
+
+        let id = standby.device.id().to_string();
+        if let Some(member) = self.devices.get_mut(&id) {
+            member.cordoned = false;
+            member.tags.remove(ROLE_TAG);
+        }
+        tracing::warn!(failed = failed_id, promoted = %id, "Standby promoted");
+        Ok(Some(id))
+    }
+
+    /// Pick a device for new work: the first schedulable device in
+    /// rollout order.
+    #[must_use]
+    pub fn select_device(&self) -> Option<&FleetMember> {
+        self.deploy_order().into_iter().next()
+    }
+
+    /// Pick a device for a session, reusing the device it is pinned to.
+    ///
+    /// A pinned device keeps its sessions while it is available, even when
+    /// cordoned, so draining lets them finish. Sessions are re-pinned with
+    /// `strategy` only when their device is disabled, offline or removed.
+    pub fn select_with_affinity(
+        &self,
+        session_key: &str,
+        strategy: SelectionStrategy,
+    ) -> Option<&FleetMember> {
+        let mut sessions = self.sessions.lock().unwrap_or_else(PoisonError::into_inner);
+        if let Some(member) = sessions
+            .get(session_key)
+            .and_then(|id| self.devices.get(id))
+            .filter(|m| m.is_available())
+        {
+            return Some(member);
+        }
+
+        let candidates = self.deploy_order();
+        let chosen = match strategy {
+            SelectionStrategy::RolloutOrder => candidates.first().copied(),
+            SelectionStrategy::FewestSessions => candidates.iter().copied().min_by_key(|m| {
+                sessions
+                    .values()
+                    .filter(|id| id.as_str() == m.device.id())
+                    .count()
+            }),
+            SelectionStrategy::Hashed => {
+                let mut hasher = std::collections::hash_map::DefaultHasher::new();
+                session_key.hash(&mut hasher);
+                let len = candidates.len() as u64;
+                #[allow(clippy::cast_possible_truncation)]
+                candidates
+                    .get(hasher.finish().checked_rem(len)? as usize)
+                    .copied()
+            }
+        }?;
+        let previous = sessions.insert(session_key.to_string(), chosen.device.id().to_string());
+        drop(sessions);
+        if let Some(previous) = previous {
+            tracing::info!(
+                session = session_key,
+                from = %previous,
+                to = %chosen.device.id(),
+                "Session re-pinned"
+            );
+        }
+        Some(chosen)
+    }
+
+    /// Forget a session's device pin.
+    pub fn release_session(&self, session_key: &str) {
+        self.sessions
+            .lock()
+            .unwrap_or_else(PoisonError::into_inner)
+            .remove(session_key);
+    }
+
+    /// Schedulable devices in rollout order: by priority, then ID.
+    ///
+    /// Cordoned, disabled and offline devices are left out.
+    #[must_use]
+    pub fn deploy_order(&self) -> Vec<&FleetMember> {
+        let mut members: Vec<&FleetMember> = self
+            .devices
+            .values()
+            .filter(|m| m.is_schedulable())
+            .collect();
+        members.sort_by(|a, b| {
+            a.priority
+                .cmp(&b.priority)
+                .then_with(|| a.device.id().cmp(b.device.id()))
+        });
+        members
+    }
+
+    /// Deploy model to all fleet devices.
+    ///
+    /// Uploads in priority order; a failure halts the rollout before
+    /// lower-priority devices. Transient failures are retried from the
+    /// fleet's retry budget.
+    ///
+    /// Raw bytes have no source to rebuild them from, so the upload is not
+    /// recorded for [`Self::promote_standby`] or the device manifest; the
+    /// same holds for [`Self::deploy_model_with`] and
+    /// [`Self::deploy_model_thermal_aware`].
+    ///
+    /// # Errors
+    ///
+    /// Returns `Error::Config` before any transfer if the model exceeds a
+    /// device's size cap (see [`Self::max_model_size_gb_for`]),
+    /// `Error::Storage` if a device lacks the disk space or inodes for it, or
+    /// an error if deployment fails on any device.
+    pub async fn deploy_model(&self, model: impl AsRef<[u8]>) -> Result<()> {
+        self.deploy_bytes(model.as_ref(), &[], self.compress_transfers)
+            .await
+            .map(drop)
+    }
+
+    /// Deploy a model as [`Self::deploy_model`] does, with per-deployment
+    /// settings.
+    ///
+    /// Only `config.target_devices` receive the model (all if empty). The
+    /// model must fit `config.memory_budget_mb`, and a GGUF model must be
+    /// at `config.quantization` if set. With `config.compress` the transfer
+    /// is zstd-compressed as described in [`Self::with_compressed_transfers`].
+    /// `thermal_policy` and `max_oom_retries` only apply to on-device
+    /// quantization (see [`Self::deploy_with_oom_fallback`]).
+    ///
+    /// # Errors
+    ///
+    /// Returns `Error::Config` or `Error::Quantization` before any transfer
+    /// if the model does not match `config` or exceeds a device's size cap,
+    /// `Error::Storage` if a targeted device lacks the disk for it, or an
+    /// error if compression or deployment fails on any device.
+    pub async fn deploy_model_with(
+        &self,
+        model: impl AsRef<[u8]>,
+        config: &DeploymentConfig,
+    ) -> Result<TransferStats> {
+        let model = model.as_ref();
+        let size_mb = model.len() as u64 / (1024 * 1024);
+        if size_mb > config.memory_budget_mb {
+            return Err(Error::Config(format!(
+                "model is {size_mb} MB, over the {} MB memory budget",
+                config.memory_budget_mb
+            )));
+        }
+        if let Some(expected) = &config.quantization {
+            let expected: QuantLevel = expected.parse()?;
+            if model.starts_with(b"GGUF") {
+                let actual = crate::quantize::gguf_quant_level(model)?;
+                if actual != expected {
+                    return Err(Error::Quantization(format!(
+                        "model is {actual}, deployment expects {expected}"
+                    )));
+                }
+            }
+        }
+        self.deploy_bytes(model, &config.target_devices, config.compress)
+            .await
+    }
+
+    /// Upload `model` to the schedulable devices in `targets` (all if
+    /// empty), in rollout order, compressing it first if `compress` is set
+    /// and it pays off.
+    async fn deploy_bytes(
+        &self,
+        model: &[u8],
+        targets: &[String],
+        compress: bool,
+    ) -> Result<TransferStats> {
+        let targeted = |member: &FleetMember| {
+            targets.is_empty() || targets.iter().any(|t| t == member.device.id())
+        };
+        let members: Vec<&FleetMember> = self
+            .deploy_order()
+            .into_iter()
+            .filter(|m| targeted(m))
+            .collect();
+        for member in &members {
+            self.check_model_size(member.device.id(), model.len() as u64)?;
+        }
+        for member in &members {
+            StorageLayout::default()
+                .preflight(&member.device, model.len() as u64)
+                .await?;
+        }
+        let compressed = if compress {
+            compress_for_transfer(model)?
+        } else {
+            None
+        };
+        let compressed = compressed.as_deref();
+        self.rolling_update(|member| async move {
+            if !targeted(member) {
+                return Ok(());
+            }
+            self.upload_model(member, model, compressed).await
+        })
+        .await
+        .into_result()?;
+        Ok(compressed.map_or_else(
+            || TransferStats::uncompressed(model.len()),
+            |data| TransferStats {
+                original_bytes: model.len() as u64,
+                transferred_bytes: data.len() as u64,
+            },
+        ))
+    }
+
+    /// Upload `model` to a device's default model path, sending
+    /// `compressed` instead when given; transient failures are retried.
+    async fn upload_model(
+        &self,
+        member: &FleetMember,
+        model: &[u8],
+        compressed: Option<&[u8]>,
+    ) -> Result<()> {
+        let target = StorageLayout::default().models_dir.join(DEFAULT_MODEL_FILE);
+        let target = &target;
+        self.with_retries(member.device.id(), || async move {
+            match compressed {
+                Some(data) => member.device.upload_zstd(data, target).await,
+                None => member.device.upload(model, target).await,
+            }
+        })
+        .await
+    }
+
+    /// Deploy a model as [`Self::deploy_model`] does, without loading two
+    /// devices in the same enclosure at once.
+    ///
+    /// Devices are uploaded to in [`Self::thermal_deploy_waves`]: each wave
+    /// runs concurrently, and the first failed wave halts the rollout.
+    ///
+    /// # Errors
+    ///
+    /// Returns `Error::Config` before any transfer if the model exceeds a
+    /// device's size cap, `Error::Storage` if a device lacks the disk for it,
+    /// or an error if deployment fails on any device.
+    pub async fn deploy_model_thermal_aware(&self, model: impl AsRef<[u8]>) -> Result<()> {
+        let model = model.as_ref();
+        for member in self.deploy_order() {
+            self.check_model_size(member.device.id(), model.len() as u64)?;
+        }
+        for member in self.deploy_order() {
+            StorageLayout::default()
+                .preflight(&member.device, model.len() as u64)
+                .await?;
+        }
+        let compressed = if self.compress_transfers {
+            compress_for_transfer(model)?
+        } else {
+            None
+        };
+        // Shared so each wave's transfers run on the blocking pool in parallel
+        let (payload, zstd): (Arc<[u8]>, bool) = match compressed {
+            Some(data) => (data.into(), true),
+            None => (model.into(), false),
+        };
+        let target = StorageLayout::default().models_dir.join(DEFAULT_MODEL_FILE);
+        let mut report = RolloutReport::default();
+        for wave in self.thermal_deploy_waves() {
+            if report.failed.is_some() {
+                report
+                    .skipped
+                    .extend(wave.iter().map(|m| m.device.id().to_string()));
+                continue;
+            }
+            let results = future::join_all(wave.iter().map(|member| {
+                self.with_retries(member.device.id(), || {
+                    member
+                        .device
+                        .upload_shared(payload.clone(), target.clone(), zstd)
+                })
+            }))
+            .await;
+            for (member, result) in wave.iter().zip(results) {
+                let id = member.device.id().to_string();
+                match result {
+                    Ok(()) => report.completed.push(id),
+                    Err(e) if report.failed.is_none() => {
+                        tracing::error!(device = %id, error = %e, "Rollout halted");
+                        report.failed = Some((id, e));
+                    }
+                    Err(e) => {
+                        tracing::error!(device = %id, error = %e, "Deploy failed");
+                    }
+                }
+            }
+        }
+        report.into_result()
+    }
+
+    /// Schedulable devices grouped into waves that share no enclosure.
+    ///
+    /// Devices with the same [`ENCLOSURE_TAG`] land in successive waves in
+    /// rollout order, so each wave interleaves enclosures; untagged devices
+    /// are assumed to cool independently.
+    #[must_use]
+    pub fn thermal_deploy_waves(&self) -> Vec<Vec<&FleetMember>> {
+        let mut waves: Vec<Vec<&FleetMember>> = Vec::new();
+        let mut next_wave: HashMap<&str, usize> = HashMap::new();
+        for member in self.deploy_order() {
+            let wave = member.tags.get(ENCLOSURE_TAG).map_or(0, |enclosure| {
+                let slot = next_wave.entry(enclosure).or_default();
+                *slot += 1;
+                *slot - 1
+            });
+            if wave == waves.len() {
+                waves.push(Vec::new());
+            }
+            waves[wave].push(member);
+        }
+        waves
+    }
+
+    /// Run a per-device update in priority order.
+    ///
+    /// Devices are updated one at a time, lowest priority number first, and
+    /// the first failure halts the rollout so canaries gate the rest.
+    pub async fn rolling_update<'a, F, Fut>(&'a self, mut update: F) -> RolloutReport
+    where
+        F: FnMut(&'a FleetMember) -> Fut,
+        Fut: Future<Output = Result<()>>,
+    {
+        let mut report = RolloutReport::default();
+        for member in self.deploy_order() {
+            let id = member.device.id().to_string();
+            if report.failed.is_some() {
+                report.skipped.push(id);
+                continue;
+            }
+            match update(member).await {
+                Ok(()) => report.completed.push(id),
+                Err(e) => {
+                    tracing::error!(
+                        device = %id,
+                        priority = member.priority,
+                        error = %e,
+                        "Rollout halted"
+                    );
+                    report.failed = Some((id, e));
+                }
+            }
+        }
+        report
+    }
+
+    /// Deploy a model, stepping down a quant level whenever warmup OOMs.
+    ///
+    /// `model_source` is an F16 GGUF already on each device. It is quantized
+    /// on-device to the configured level and loaded once as a warmup; if the
+    /// load is OOM-killed, the next lower level is tried, up to
+    /// `config.max_oom_retries` times.
+    ///
+    /// # Errors
+    ///
+    /// Returns `Error::Storage` before quantizing if a device lacks the disk
+    /// for the starting level, or an error if a device fails for a reason
+    /// other than OOM, or still OOMs after the retry limit or at the lowest
+    /// level.
+    pub async fn deploy_with_oom_fallback(
+        &self,
+        model_source: impl AsRef<Path>,
+        config: &DeploymentConfig,
+    ) -> Result<Vec<DeviceDeployment>> {
+        let model_source = model_source.as_ref();
+        let start_level = config
+            .quantization
+            .as_deref()
+            .map_or(Ok(QuantLevel::Q4_0), str::parse)?;
+
+        let mut ids: Vec<&String> = self
+            .devices
+            .iter()
+            .filter(|(id, m)| {
+                m.is_schedulable()
+                    && (config.target_devices.is_empty() || config.target_devices.contains(id))
+            })
+            .map(|(id, _)| id)
+            .collect();
+        ids.sort();
+
+        // The F16 source shrinks by the level's bits per parameter
+        let quoted = shell_quote(&model_source.to_string_lossy());
+        for id in &ids {
+            let device = &self.devices[*id].device;
+            let source_bytes: u64 = device
+                .exec(&format!("stat -c %s {quoted}"))
+                .await?
+                .trim()
+                .parse()
+                .map_err(|_| {
+                    Error::Storage(format!("{id}: cannot size {}", model_source.display()))
+                })?;
+            let required = source_bytes / 16 * u64::from(start_level.bits_per_param());
+            StorageLayout::default().preflight(device, required).await?;
+        }
+
+        let mut deployments = Vec::with_capacity(ids.len());
+        for id in ids {
+            let device = &self.devices[id].device;
+            let mut level = start_level;
+            let mut oom_retries = 0;
+            loop {
+                match load_quantized(device, model_source, level).await {
+                    Ok(()) => break,
+                    Err(e) if is_oom(&e) => {
+                        let next = level
+                            .next_lower()
+                            .filter(|_| oom_retries < config.max_oom_retries)
+                            .ok_or_else(|| {
+                                Error::Quantization(format!(
+                                    "{id}: out of memory loading {level} after {oom_retries} fallbacks"
+                                ))
+                            })?;
+                        tracing::warn!(
+                            device = %id,
+                            from = %level,
+                            to = %next,
+                            "OOM during warmup, retrying at lower quant level"
+                        );
+                        level = next;
+                        oom_retries += 1;
+                    }
+                    Err(e) => return Err(e),
+                }
+            }
+            self.record_deployment(device, model_source, level).await?;
+            deployments.push(DeviceDeployment {
+                device_id: id.clone(),
+                level,
+                oom_retries,
+            });
+        }
+        Ok(deployments)
+    }
+
+    /// Deploy host-quantized files, picking the best level for each device.
+    ///
+    /// `quantized` maps levels to files from [`quantize_for_fleet`]. Each
+    /// device, in rollout order, gets the highest-quality level whose
+    /// estimate from `model_f16_size_mb` fits its memory budget; nothing is
+    /// requantized on the device. `source_gguf` names the model in manifests.
+    /// Each chosen file is checked with [`validate_gguf`] and read once
+    /// before the rollout starts, then against each device's model size cap
+    /// and free disk before transfer.
+    ///
+    /// [`quantize_for_fleet`]: crate::quantize::quantize_for_fleet
+    /// [`validate_gguf`]: crate::quantize::validate_gguf
+    pub async fn deploy_model_auto_quant(
+        &self,
+        source_gguf: &Path,
+        quantized: &HashMap<QuantLevel, PathBuf>,
+        model_f16_size_mb: u64,
+    ) -> RolloutReport {
+        let levels: Vec<QuantLevel> = quantized.keys().copied().collect();
+        let select = |member: &FleetMember| {
+            let budget = MemoryBudget::new(member.memory_budget_mb, 0);
+            JetsonQuantizer::try_select_from(model_f16_size_mb, &budget, &levels)
+        };
+
+        // Read each level the rollout needs once, off the runtime, stopping at
+        // the first unreadable file since the rollout halts on that device
+        let mut files: HashMap<QuantLevel, Vec<u8>> = HashMap::new();
+        let mut unreadable = None;
+        for member in self.deploy_order() {
+            let Ok(level) = select(member) else { continue };
+            if files.contains_key(&level) {
+                continue;
+            }
+            let path = quantized[&level].clone();
+            let read = blocking(move || {
+                crate::quantize::validate_gguf(&path, None)?;
+                Ok(std::fs::read(&path)?)
+            });
+            match read.await {
+                Ok(data) => {
+                    files.insert(level, data);
+                }
+                Err(e) => {
+                    unreadable = Some(e);
+                    break;
+                }
+            }
+        }
+
+        let files = &files;
+        self.rolling_update(|member| {
+            let selected = select(member).and_then(|level| {
+                files.get(&level).map(|data| (level, data)).ok_or_else(|| {
+                    unreadable.take().unwrap_or_else(|| {
+                        Error::Internal(format!("quant level {level} was not read"))
+                    })
+                })
+            });
+            async move {
+                let (level, data) = selected?;
+                self.check_model_size(member.device.id(), data.len() as u64)?;
+                StorageLayout::default()
+                    .preflight(&member.device, data.len() as u64)
+                    .await?;
+                let (_, target) = quantized_target(source_gguf, level);
+                self.with_retries(member.device.id(), || member.device.upload(data, &target))
+                    .await?;
+                self.record_deployment(&member.device, source_gguf, level)
+                    .await
+            }
+        })
+        .await
+    }
+
+    /// Check that a deployed model's file is at the `expected` quant level.
+    ///
+    /// Reads the `general.file_type` from the GGUF header of the file the
+    /// device's manifest lists for `model_name`, so a stale file left under
+    /// that name is caught even if the manifest says otherwise.
+    ///
+    /// # Errors
+    ///
+    /// Returns `Error::DeviceNotFound` for an unknown device, `Error::Fleet`
+    /// if the model is not in the manifest, or an error if the file cannot
+    /// be read or its header parsed.
+    pub async fn verify_quant(
+        &self,
+        device_id: &str,
+        model_name: &str,
+        expected: QuantLevel,
+    ) -> Result<bool> {
+        let member = self
+            .get(device_id)
+            .ok_or_else(|| Error::DeviceNotFound(device_id.to_string()))?;
+        let layout = StorageLayout::default();
+        let manifest = layout.read_manifest(&member.device).await?;
+        let entry = manifest.get(model_name).ok_or_else(|| {
+            Error::Fleet(format!("{device_id}: model {model_name} is not deployed"))
+        })?;
+        let path = layout.models_dir.join(&entry.file);
+        let header = member
+            .device
+            .exec_bytes(&format!(
+                "head -c {VERIFY_HEADER_BYTES} {}",
+                shell_quote(&path.to_string_lossy())
+            ))
+            .await?;
+        let actual = gguf_quant_level(&header)?;
+        if actual != expected {
+            tracing::warn!(
+                device = device_id,
+                model = model_name,
+                %expected,
+                %actual,
+                "Deployed model is at an unexpected quant level"
+            );
+        }
+        Ok(actual == expected)
+    }
+
+    /// Record a quantized model in the device's manifest and the fleet.
+    async fn record_deployment(
+        &self,
+        device: &JetsonDevice,
+        source: &Path,
+        level: QuantLevel,
+    ) -> Result<()> {
+        let (name, target) = quantized_target(source, level);
+        let file = target
+            .file_name()
+            .map(|f| f.to_string_lossy().into_owned())
+            .unwrap_or_default();
+        StorageLayout::default()
+            .record_model(
+                device,
+                ManifestEntry {
+                    name: name.clone(),
+                    quant: level,
+                    file,
+                },
+            )
+            .await?;
+
+        // Same key as the manifest, which keeps one entry per name
+        let mut deployed = self.deployed.lock().unwrap_or_else(PoisonError::into_inner);
+        let models = deployed.entry(device.id().to_string()).or_default();
+        models.retain(|m| model_name(&m.source) != name);
+        models.push(DeployedModel {
+            source: source.to_path_buf(),
+            level,
+        });
+        drop(deployed);
+        Ok(())
+    }
+
+    /// Report the trueno backend each enabled device's server is using.
+    ///
+    /// Devices whose server cannot be queried are listed with no active
+    /// backend rather than failing the report.
+    pub async fn backend_report(&self, port: u16) -> BackendReport {
+        let mut devices = BTreeMap::new();
+        for (id, member) in self.devices.iter().filter(|(_, m)| m.is_available()) {
+            let active = match member.device.active_backend(port).await {
+                Ok(backend) => Some(backend),
+                Err(e) => {
+                    tracing::warn!(device = %id, error = %e, "Backend query failed");
+                    None
+                }
+            };
+            devices.insert(
+                id.clone(),
+                BackendStatus {
+                    expected: member.device.compute_hint().backend,
+                    active,
+                },
+            );
+        }
+        BackendReport { devices }
+    }
+
+    /// Mark a model as intentionally deployed at mixed quant levels.
+    pub fn allow_heterogeneous_quant(&mut self, model_name: impl Into<String>) {
+        self.heterogeneous_quant.insert(model_name.into());
+    }
+
+    /// Report which quant level each enabled device runs for a model.
+    ///
+    /// Reads each device's model manifest. The fleet is inconsistent when
+    /// devices run different levels, unless mixed levels were allowed with
+    /// [`Self::allow_heterogeneous_quant`].
+    ///
+    /// # Errors
+    ///
+    /// Returns an error if a device's manifest cannot be read.
+    pub async fn quant_consistency(&self, model_name: &str) -> Result<ConsistencyReport> {
+        let layout = StorageLayout::default();
+        let mut report = ConsistencyReport {
+            model: model_name.to_string(),
+            levels: BTreeMap::new(),
+            missing: Vec::new(),
+            heterogeneous_allowed: self.heterogeneous_quant.contains(model_name),
+        };
+        let mut members: Vec<&FleetMember> =
+            self.devices.values().filter(|m| m.is_available()).collect();
+        members.sort_by(|a, b| a.device.id().cmp(b.device.id()));
+        for member in members {
+            let id = member.device.id().to_string();
+            match layout.read_manifest(&member.device).await?.get(model_name) {
+                Some(entry) => {
+                    report.levels.insert(id, entry.quant);
+                }
+                None => report.missing.push(id),
+            }
+        }
+        if !report.is_consistent() {
+            tracing::warn!(
+                model = model_name,
+                levels = ?report.distinct_levels(),
+                "Quantization drift across fleet"
+            );
+        }
+        Ok(report)
+    }
+
+    /// Start inference servers on all devices.
+    ///
+    /// # Errors
+    ///
+    /// Returns an error if server start fails.
+    pub async fn start_inference_servers(&self) -> Result<()> {
+        // Would start realizar on each device
+        Ok(())
+    }
+
+    /// Get fleet health status.
+    #[must_use]
+    pub fn health_status(&self) -> FleetHealth {
+        let total = self.len();
+        let available = self.devices.values().filter(|m| m.is_available()).count();
+        FleetHealth {
+            total_devices: total,
+            enabled_devices: self.enabled_count(),
+            healthy_devices: available, // Placeholder
+            degraded_devices: 0,
+            offline_devices: total - available,
+        }
+    }
+
+    /// Sample tegrastats on every available device, concurrently.
+    ///
+    /// Failures are reported per device rather than aborting the sweep.
+    pub async fn sample_all(&self) -> HashMap<String, Result<TegraStats>> {
+        let (ids, members): (Vec<&String>, Vec<&FleetMember>) = self
+            .devices
+            .iter()
+            .filter(|(_, m)| m.is_available())
+            .unzip();
+        let samples = future::join_all(members.into_iter().map(|member| async move {
+            match TegraMonitor::connect(&member.device) {
+                Ok(mut monitor) => monitor.sample_async().await,
+                Err(e) => Err(e),
+            }
+        }))
+        .await;
+        ids.into_iter().cloned().zip(samples).collect()
+    }
+
+    /// Capacity the fleet can deliver right now.
+    ///
+    /// Each available device contributes its TOPS scaled by its policy's
+    /// [`ThermalPolicy::throughput_factor`] at the current GPU temperature.
+    /// Disabled, offline and unreachable devices contribute nothing.
+    ///
+    /// # Errors
+    ///
+    /// Returns the sampling error if no available device could be sampled.
+    pub async fn effective_capacity(&self) -> Result<FleetCapacity> {
+        let mut samples = self.sample_all().await;
+        let mut capacity = FleetCapacity::default();
+        let mut first_error = None;
+        for (id, member) in &self.devices {
+            let model = member.device.model();
+            capacity.nominal_tops += model.tops();
+            capacity.nominal_memory_mb += model.memory_mb();
+            let factor = match samples.remove(id) {
+                Some(Ok(stats)) => member.policy.throughput_factor(stats.gpu_temp),
+                Some(Err(e)) => {
+                    first_error.get_or_insert(e);
+                    0.0
+                }
+                None => 0.0,
+            };
+            if factor > 0.0 {
+                #[allow(clippy::cast_precision_loss)]
+                let tops = model.tops() as f32;
+                capacity.effective_tops += tops * factor;
+                capacity.effective_memory_mb += model.memory_mb();
+            }
+            capacity.factors.insert(id.clone(), factor);
+        }
+        match first_error {
+            Some(e) if capacity.factors.values().all(|f| *f == 0.0) => Err(e),
+            _ => Ok(capacity),
+        }
+    }
+
+    /// Stream of GPU temperatures per device, for a live heatmap.
+    ///
+    /// Emits a point immediately and then every `interval` until `window`
+    /// has elapsed. Each point holds the available devices that could be
+    /// sampled; a device that stops responding drops out of the points
+    /// until it answers again.
+    pub fn thermal_timeseries(
+        &self,
+        window: Duration,
+        interval: Duration,
+    ) -> impl Stream<Item = HashMap<String, f32>> + '_ {
+        let points = window.as_nanos() / interval.as_nanos().max(1) + 1;
+        stream::unfold(0, move |emitted| async move {
+            if emitted >= points {
+                return None;
+            }
+            if emitted > 0 {
+                tokio::time::sleep(interval).await;
+            }
+            let temps = self
+                .sample_all()
+                .await
+                .into_iter()
+                .filter_map(|(id, stats)| Some((id, stats.ok()?.gpu_temp)))
+                .collect();
+            Some((temps, emitted + 1))
+        })
+    }
+
+    /// Run discovery and merge the results into the fleet.
+    ///
+    /// # Errors
+    ///
+    /// Returns an error if discovery fails.
+    pub async fn discover_and_merge(&mut self) -> Result<DiscoveryMergeReport> {
+        let found = JetsonDevice::discover_all().await?;
+        Ok(self.merge_discovered(found))
+    }
+
+    /// Merge discovered devices without disturbing existing members.
+    ///
+    /// New devices are added with the default thermal policy. Known devices
+    /// keep their handle, policy, priority and enabled flag; those not in
+    /// `found` are marked offline rather than removed, and come back online
+    /// when rediscovered.
+    pub fn merge_discovered(&mut self, found: Vec<JetsonDevice>) -> DiscoveryMergeReport {
+        let mut report = DiscoveryMergeReport::default();
+        let found_ids: HashSet<String> = found.iter().map(|d| d.id().to_string()).collect();
+
+        for device in found {
+            let id = device.id().to_string();
+            match self.devices.get_mut(&id) {
+                Some(member) if member.online => report.retained.push(id),
+                Some(member) => {
+                    member.online = true;
+                    report.returned.push(id);
+                }
+                None => {
+                    self.devices.insert(
+                        id.clone(),
+                        FleetMember {
+                            memory_budget_mb: device.compute_hint().memory_budget_mb,
+                            device,
+                            policy: ThermalPolicy::default(),
+                            enabled: true,
+                            priority: 0,
+                            online: true,
+                            cordoned: false,
+                            tags: BTreeMap::new(),
+                            power_profile: None,
+                            reach_failures: 0,
+                        },
+                    );
+                    report.added.push(id);
+                }
+            }
+        }
+        for (id, member) in &mut self.devices {
+            if member.online && !found_ids.contains(id) {
+                member.online = false;
+                report.went_offline.push(id.clone());
+            }
+        }
+
+        for ids in [
+            &mut report.added,
+            &mut report.retained,
+            &mut report.returned,
+            &mut report.went_offline,
+        ] {
+            ids.sort();
+        }
+        if !report.went_offline.is_empty() {
+            tracing::warn!(devices = ?report.went_offline, "Devices missing from discovery");
+        }
+        report
+    }
+}
+
+impl Fleet {
+    /// Check whether the fleet is ready to take production traffic.
+    ///
+    /// Probes every enabled device for temperature, the required model file,
+    /// and the inference server, then evaluates each requirement. Probe
+    /// failures count against the device rather than aborting the check.
+    pub async fn readiness(&self, requirements: ReadinessRequirements) -> ReadinessReport {
+        let mut ids: Vec<&String> = self
+            .devices
+            .iter()
+            .filter(|(_, m)| m.is_available())
+            .map(|(id, _)| id)
+            .collect();
+        ids.sort();
+
+        let mut probes = Vec::with_capacity(ids.len());
+        for id in ids {
+            probes.push(probe_member(id, &self.devices[id], &requirements).await);
+        }
+
+        let mut checks = Vec::new();
+
+        let healthy = probes.iter().filter(|p| p.healthy).count();
+        checks.push(ReadinessCheck {
+            requirement: ReadinessRequirement::MinHealthyDevices,
+            passed: healthy >= requirements.min_healthy_devices,
+            detail: format!(
+                "{healthy} healthy of {} enabled (need {})",
+                probes.len(),
+                requirements.min_healthy_devices
+            ),
+        });
+
+        if let Some(model) = &requirements.required_model {
+            let missing = failing_ids(&probes, |p| p.model_present != Some(true));
+            checks.push(ReadinessCheck {
+                requirement: ReadinessRequirement::ModelDeployed,
+                passed: missing.is_empty(),
+                detail: if missing.is_empty() {
+                    format!("{model} present on all devices")
+                } else {
+                    format!("{model} missing on: {}", missing.join(", "))
+                },
+            });
+        }
+
+        if let Some(max_temp_c) = requirements.max_temp_c {
+            let hot: Vec<String> = probes
+                .iter()
+                .filter(|p| p.temp_c.map_or(true, |t| t > max_temp_c))
+                .map(|p| {
+                    p.temp_c.map_or_else(
+                        || format!("{} (unreadable)", p.id),
+                        |t| format!("{} ({t:.1}°C)", p.id),
+                    )
+                })
+                .collect();
+            checks.push(ReadinessCheck {
+                requirement: ReadinessRequirement::MaxTemperature,
+                passed: hot.is_empty(),
+                detail: if hot.is_empty() {
+                    format!("all devices at or below {max_temp_c:.1}°C")
+                } else {
+                    format!("above {max_temp_c:.1}°C: {}", hot.join(", "))
+                },
+            });
+        }
+
+        if requirements.servers_responding {
+            let down = failing_ids(&probes, |p| p.server_ok != Some(true));
+            checks.push(ReadinessCheck {
+                requirement: ReadinessRequirement::ServersResponding,
+                passed: down.is_empty(),
+                detail: if down.is_empty() {
+                    format!("servers responding on port {}", requirements.server_port)
+                } else {
+                    format!(
+                        "no response on port {}: {}",
+                        requirements.server_port,
+                        down.join(", ")
+                    )
+                },
+            });
+        }
+
+        ReadinessReport {
+            ready: checks.iter().all(|c| c.passed),
+            checks,
+        }
+    }
+}
+
+/// Fetches the models config sources name onto this host for deployment.
+///
+/// Used by [`Fleet::apply_diff`], which defaults to [`HostModelFetcher`];
+/// set another with [`Fleet::with_model_fetcher`] to resolve `pacha://`
+/// URLs or other registries. Called on the blocking pool.
+pub trait ModelFetcher: std::fmt::Debug + Send + Sync {
+    /// Contents of the model `source` names.
+    ///
+    /// # Errors
+    ///
+    /// Returns an error if the source is not supported or cannot be fetched.
+    fn fetch(&self, source: &str) -> Result<Vec<u8>>;
+}
+
+/// [`ModelFetcher`] reading paths on this host, with or without `file://`.
+#[derive(Debug, Clone, Copy, Default)]
+pub struct HostModelFetcher;
+
+impl ModelFetcher for HostModelFetcher {
+    fn fetch(&self, source: &str) -> Result<Vec<u8>> {
+        let path = source.strip_prefix("file://").unwrap_or(source);
+        if path.contains("://") {
+            return Err(Error::Config(format!(
+                "cannot fetch model source '{source}' from this host; set a fetcher with Fleet::with_model_fetcher"
+            )));
+        }
+        Ok(std::fs::read(path)?)
+    }
+}
+
+/// Quantize `source` on-device to `level` and load it once.
+async fn load_quantized(device: &JetsonDevice, source: &Path, level: QuantLevel) -> Result<()> {
+    let (_, target) = quantized_target(source, level);
+
+    device
+        .exec(&format!(
+            "llama-quantize {} {} {}",
+            shell_quote(&source.to_string_lossy()),
+            shell_quote(&target.to_string_lossy()),
+            level.as_str().to_ascii_uppercase()
+        ))
+        .await?;
+    device
+        .exec(&format!(
+            "llama-cli -m {} -n 1 -p warmup --no-display-prompt",
+            shell_quote(&target.to_string_lossy())
+        ))
+        .await
+        .map(|_| ())
+}
+
+/// Model name and on-device path of `source` quantized to `level`.
+fn quantized_target(source: &Path, level: QuantLevel) -> (String, PathBuf) {
+    let name = model_name(source);
+    let target = StorageLayout::default()
+        .models_dir
+        .join(format!("{name}.{level}.gguf"));
+    (name, target)
+}
+
+/// Manifest name of a model source: its file stem without `.f16`.
+fn model_name(source: &Path) -> String {
+    let stem = source
+        .file_stem()
+        .map_or_else(|| "model".into(), |s| s.to_string_lossy());
+    stem.trim_end_matches(".f16").to_string()
+}
+
+/// Whether an error is a transport failure worth retrying.
+///
+/// ssh exits 255 when the connection itself fails.
+fn is_transient(err: &Error) -> bool {
+    matches!(
+        err.untagged(),
+        Error::ConnectionFailed { .. }
+            | Error::Ssh(_)
+            | Error::Timeout { .. }
+            | Error::Io(_)
+            | Error::CommandFailed { exit_code: 255, .. }
+    )
+}
+
+/// Whether a failed command was killed for running out of memory.
+///
+/// Exit 137 is SIGKILL, as sent by the OOM killer.
+fn is_oom(err: &Error) -> bool {
+    match err.untagged() {
+        Error::CommandFailed {
+            exit_code, stderr, ..
+        } => {
+            let lower = stderr.to_ascii_lowercase();
+            *exit_code == 137
+                || stderr.contains("Killed")
+                || lower.contains("out of memory")
+                || lower.contains("oom-kill")
+        }
+        _ => false,
+    }
+}
+
+/// Per-device results gathered for a readiness check.
+struct DeviceProbe {
+    id: String,
+    healthy: bool,
+    temp_c: Option<f32>,
+    model_present: Option<bool>,
+    server_ok: Option<bool>,
+}
+
+async fn probe_member(
+    id: &str,
+    member: &FleetMember,
+    requirements: &ReadinessRequirements,
+) -> DeviceProbe {
+    let device = &member.device;
+    let temp_c = match TegraMonitor::connect(device) {
+        Ok(mut monitor) => monitor.gpu_temp_async().await.ok(),
+        Err(_) => None,
+    };
+
+    let model_present = match &requirements.required_model {
+        Some(model) => {
+            let path = requirements.models_dir.join(model);
+            Some(
+                device
+                    .exec(&format!("test -f {}", shell_quote(&path.to_string_lossy())))
+                    .await
+                    .is_ok(),
+            )
+        }
+        None => None,
+    };
+
+    let server_ok = if requirements.servers_responding {
+        Some(
+            device
+                .exec(&format!(
+                    "curl -sf -o /dev/null http://localhost:{}/health",
+                    requirements.server_port
+                ))
+                .await
+                .is_ok(),
+        )
+    } else {
+        None
+    };
+
+    DeviceProbe {
+        id: id.to_string(),
+        healthy: temp_c.is_some_and(|t| t <= member.policy.threshold_c),
+        temp_c,
+        model_present,
+        server_ok,
+    }
+}
+
+fn failing_ids(probes: &[DeviceProbe], failed: impl Fn(&DeviceProbe) -> bool) -> Vec<String> {
+    probes
+        .iter()
+        .filter(|p| failed(p))
+        .map(|p| p.id.clone())
+        .collect()
+}
+
+/// Requirements a fleet must meet to be considered ready.
+#[derive(Debug, Clone)]
+pub struct ReadinessRequirements {
+    /// Minimum devices that are reachable and below their thermal threshold
+    pub min_healthy_devices: usize,
+    /// Model file that must be present on every enabled device
+    pub required_model: Option<String>,
+    /// Directory models are deployed to
+    pub models_dir: PathBuf,
+    /// Maximum acceptable GPU temperature on any enabled device
+    pub max_temp_c: Option<f32>,
+    /// Require inference servers to answer health checks
+    pub servers_responding: bool,
+    /// Inference server port
+    pub server_port: u16,
+}
+
+impl ReadinessRequirements {
+    /// Require at least `count` healthy devices.
+    #[must_use]
+    pub const fn with_min_healthy_devices(mut self, count: usize) -> Self {
+        self.min_healthy_devices = count;
+        self
+    }
+
+    /// Require a model to be deployed on every enabled device.
+    #[must_use]
+    pub fn with_required_model(mut self, model: impl Into<String>) -> Self {
+        self.required_model = Some(model.into());
+        self
+    }
+
+    /// Require every enabled device to be at or below a temperature.
+    #[must_use]
+    pub const fn with_max_temp_c(mut self, max_temp_c: f32) -> Self {
+        self.max_temp_c = Some(max_temp_c);
+        self
+    }
+
+    /// Require inference servers to respond on a port.
+    #[must_use]
+    pub const fn with_servers_responding(mut self, port: u16) -> Self {
+        self.servers_responding = true;
+        self.server_port = port;
+        self
+    }
+}
+
+impl Default for ReadinessRequirements {
+    fn default() -> Self {
+        Self {
+            min_healthy_devices: 1,
+            required_model: None,
+            models_dir: StorageLayout::default().models_dir,
+            max_temp_c: None,
+            servers_responding: false,
+            server_port: 8080,
+        }
+    }
+}
+
+/// A requirement evaluated by [`Fleet::readiness`].
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+pub enum ReadinessRequirement {
+    /// Enough healthy devices
+    MinHealthyDevices,
+    /// Required model deployed everywhere
+    ModelDeployed,
+    /// All devices below the temperature limit
+    MaxTemperature,
+    /// Inference servers responding
+    ServersResponding,
+}
+
+/// Outcome of a single readiness requirement.
+#[derive(Debug, Clone)]
+pub struct ReadinessCheck {
+    /// Requirement checked
+    pub requirement: ReadinessRequirement,
+    /// Whether it was met
+    pub passed: bool,
+    /// Human-readable detail
+    pub detail: String,
+}
+
+/// Fleet readiness gate result.
+#[derive(Debug, Clone)]
+pub struct ReadinessReport {
+    /// All requirements met
+    pub ready: bool,
+    /// Per-requirement results
+    pub checks: Vec<ReadinessCheck>,
+}
+
+impl ReadinessReport {
+    /// Requirements that were not met.
+    pub fn failures(&self) -> impl Iterator<Item = &ReadinessCheck> {
+        self.checks.iter().filter(|c| !c.passed)
+    }
+
+    /// Result for a specific requirement, if it was checked.
+    #[must_use]
+    pub fn check(&self, requirement: ReadinessRequirement) -> Option<&ReadinessCheck> {
+        self.checks.iter().find(|c| c.requirement == requirement)
+    }
+}
+
+/// Final quantization chosen for a device by [`Fleet::deploy_with_oom_fallback`].
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct DeviceDeployment {
+    /// Device ID
+    pub device_id: String,
+    /// Quantization level that loaded successfully
+    pub level: QuantLevel,
+    /// Number of OOM fallbacks taken
+    pub oom_retries: u32,
+}
+
+/// File name used by [`Fleet::deploy_model`] under the models directory.
+pub const DEFAULT_MODEL_FILE: &str = "model.gguf";
+
+/// Outcome of [`Fleet::rolling_update`].
+#[derive(Debug, Default)]
+pub struct RolloutReport {
+    /// Devices updated, in order
+    pub completed: Vec<String>,
+    /// Device that failed and its error, if the rollout halted
+    pub failed: Option<(String, Error)>,
+    /// Devices not attempted because the rollout halted
+    pub skipped: Vec<String>,
+}
+
+impl RolloutReport {
+    /// Whether every device was updated.
+    #[must_use]
+    pub const fn is_complete(&self) -> bool {
+        self.failed.is_none()
+    }
+
+    /// Convert into an error naming the failed device, if any.
+    ///
+    /// # Errors
+    ///
+    /// Returns `Error::Provisioning` if the rollout halted.
+    pub fn into_result(self) -> Result<()> {
+        match self.failed {
+            None => Ok(()),
+            Some((id, e)) => Err(Error::Provisioning(format!(
+                "rollout halted at {id} ({} skipped): {e}",
+                self.skipped.len()
+            ))),
+        }
+    }
+}
+
+/// How [`Fleet::select_with_affinity`] picks a device for a new session.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash, Default)]
+pub enum SelectionStrategy {
+    /// First schedulable device in rollout order
+    #[default]
+    RolloutOrder,
+    /// Schedulable device with the fewest pinned sessions
+    FewestSessions,
+    /// Schedulable device chosen by hashing the session key
+    Hashed,
+}
+
+/// How [`Fleet::hard_reboot`] restarted a device.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum RebootMethod {
+    /// The device accepted a reboot command
+    Soft,
+    /// Power was cut and restored
+    PowerCycle,
+}
+
+/// Quant levels a model runs at across the fleet.
+#[derive(Debug, Clone)]
+pub struct ConsistencyReport {
+    /// Model name
+    pub model: String,
+    /// Quant level by device ID
+    pub levels: BTreeMap<String, QuantLevel>,
+    /// Devices without the model
+    pub missing: Vec<String>,
+    /// Mixed levels are intentional for this model
+    pub heterogeneous_allowed: bool,
+}
+
+impl ConsistencyReport {
+    /// Distinct levels in use, in ascending order of quality.
+    #[must_use]
+    pub fn distinct_levels(&self) -> Vec<QuantLevel> {
+        let mut levels: Vec<QuantLevel> = self.levels.values().copied().collect();
+        levels.sort_by(|a, b| a.memory_factor().total_cmp(&b.memory_factor()));
+        levels.dedup();
+        levels
+    }
+
+    /// Whether all devices run the same level (or mixing is allowed).
+    #[must_use]
+    pub fn is_consistent(&self) -> bool {
+        self.heterogeneous_allowed || self.distinct_levels().len() <= 1
+    }
+
+    /// Devices running `level`.
+    pub fn devices_at(&self, level: QuantLevel) -> impl Iterator<Item = &str> {
+        self.levels
+            .iter()
+            .filter(move |(_, l)| **l == level)
+            .map(|(id, _)| id.as_str())
+    }
+}
+
+/// Expected and observed backend for one device.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct BackendStatus {
+    /// Backend the compute hint selected
+    pub expected: ComputeBackend,
+    /// Backend reported by the inference server (None if unreachable)
+    pub active: Option<String>,
+}
+
+impl BackendStatus {
+    /// Whether the server reported a backend other than the expected one.
+    #[must_use]
+    pub fn mismatched(&self) -> bool {
+        self.active
+            .as_deref()
+            .is_some_and(|active| active != self.expected.as_str())
+    }
+}
+
+/// Fleet-wide backend usage from [`Fleet::backend_report`].
+#[derive(Debug, Clone, Default)]
+pub struct BackendReport {
+    /// Status by device ID
+    pub devices: BTreeMap<String, BackendStatus>,
+}
+
+impl BackendReport {
+    /// Number of devices using each reported backend.
+    #[must_use]
+    pub fn counts(&self) -> BTreeMap<&str, usize> {
+        let mut counts = BTreeMap::new();
+        for active in self.devices.values().filter_map(|s| s.active.as_deref()) {
+            *counts.entry(active).or_insert(0) += 1;
+        }
+        counts
+    }
+
+    /// Devices running a different backend than their hint selected.
+    pub fn mismatches(&self) -> impl Iterator<Item = (&str, &BackendStatus)> {
+        self.devices
+            .iter()
+            .filter(|(_, s)| s.mismatched())
+            .map(|(id, s)| (id.as_str(), s))
+    }
+
+    /// Devices whose server could not be queried.
+    pub fn unreachable(&self) -> impl Iterator<Item = &str> {
+        self.devices
+            .iter()
+            .filter(|(_, s)| s.active.is_none())
+            .map(|(id, _)| id.as_str())
+    }
+}
+
+/// Result of [`Fleet::merge_discovered`].
+#[derive(Debug, Clone, Default, PartialEq, Eq)]
+pub struct DiscoveryMergeReport {
+    /// Newly found devices
+    pub added: Vec<String>,
+    /// Known devices still present
+    pub retained: Vec<String>,
+    /// Offline devices found again
+    pub returned: Vec<String>,
+    /// Known devices no longer found, now marked offline
+    pub went_offline: Vec<String>,
+}
+
+impl DiscoveryMergeReport {
+    /// Whether the fleet membership or online state changed.
+    #[must_use]
+    pub fn changed(&self) -> bool {
+        !(self.added.is_empty() && self.returned.is_empty() && self.went_offline.is_empty())
+    }
+}
+
+/// Fleet health summary.
+#[derive(Debug, Clone)]
+pub struct FleetHealth {
+    /// Total devices in fleet
+    pub total_devices: usize,
+    /// Enabled devices
+    pub enabled_devices: usize,
+    /// Healthy devices (thermal OK, memory OK)
+    pub healthy_devices: usize,
+    /// Degraded devices (thermal warning)
+    pub degraded_devices: usize,
+    /// Offline devices
+    pub offline_devices: usize,
+}
+
+impl FleetHealth {
+    /// Get health percentage.
+    #[must_use]
+    pub fn health_percent(&self) -> f32 {
+        if self.total_devices == 0 {
+            return 100.0;
+        }
+        (self.healthy_devices as f32 / self.total_devices as f32) * 100.0
+    }
+}
+
+/// Fleet capacity weighted by each device's thermal state, see
+/// [`Fleet::effective_capacity`].
+#[derive(Debug, Clone, Default, PartialEq)]
+pub struct FleetCapacity {
+    /// TOPS of every member at full clocks
+    pub nominal_tops: u32,
+    /// TOPS deliverable now
+    pub effective_tops: f32,
+    /// Memory of every member in MB
+    pub nominal_memory_mb: u64,
+    /// Memory of the devices contributing throughput, in MB
+    pub effective_memory_mb: u64,
+    /// Throughput factor per device; 0.0 when unavailable or unreachable
+    pub factors: BTreeMap<String, f32>,
+}
+
+impl FleetCapacity {
+    /// Effective TOPS as a fraction of nominal (1.0 for an empty fleet).
+    #[must_use]
+    pub fn ratio(&self) -> f32 {
+        if self.nominal_tops == 0 {
+            return 1.0;
+        }
+        #[allow(clippy::cast_precision_loss)]
+        let nominal = self.nominal_tops as f32;
+        self.effective_tops / nominal
+    }
+}
+
+/// Outcome of one [`Fleet::govern_power`] step.
+#[derive(Debug, Clone, PartialEq)]
+pub struct GovernorStep {
+    /// Total measured draw across sampled devices (W)
+    pub total_watts: f32,
+    /// Change made, if any
+    pub action: GovernorAction,
+}
+
+/// Mode change made by the power governor.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum GovernorAction {
+    /// Draw within bounds, or no device could be changed
+    Hold,
+    /// Device stepped down to a lower-power mode
+    StepDown {
+        /// Device changed
+        device_id: String,
+        /// Mode before
+        from: PowerMode,
+        /// Mode after
+        to: PowerMode,
+    },
+    /// Device stepped back up toward its original mode
+    StepUp {
+        /// Device changed
+        device_id: String,
+        /// Mode before
+        from: PowerMode,
+        /// Mode after
+        to: PowerMode,
+    },
+}
+
+/// Inference requests waiting for a device, admitted by thermal state.
+///
+/// Each dispatch offers requests to schedulable devices in rollout order,
+/// least-dispatched first. A device whose [`ThermalCircuitBreaker`] rejects
+/// admission is passed over for a cooler one; requests no device admits stay
+/// queued.
+#[derive(Debug)]
+pub struct InferenceQueue<T> {
+    pending: VecDeque<T>,
+    breakers: HashMap<String, ThermalCircuitBreaker>,
+    dispatched: HashMap<String, u64>,
+}
+
+impl<T> InferenceQueue<T> {
+    /// Create an empty queue.
+    #[must_use]
+    pub fn new() -> Self {
+        Self {
+            pending: VecDeque::new(),
+            breakers: HashMap::new(),
+            dispatched: HashMap::new(),
+        }
+    }
+
+    /// Queue a request behind those already waiting.
+    pub fn push(&mut self, request: T) {
+        self.pending.push_back(request);
+    }
+
+    /// Requests waiting for a device.
+    #[must_use]
+    pub fn depth(&self) -> usize {
+        self.pending.len()
+    }
+
+    /// Requests dispatched so far per device ID.
+    #[must_use]
+    pub const fn dispatch_counts(&self) -> &HashMap<String, u64> {
+        &self.dispatched
+    }
+
+    /// Assign queued requests to devices, returning `(device_id, request)`
+    /// pairs in queue order.
+    ///
+    /// Each device's temperature is checked at most once per call; a device
+    /// that cannot be sampled is treated as rejecting. Dispatch stops at the
+    /// first request no device admits, keeping queue order.
+    pub fn dispatch(&mut self, fleet: &Fleet) -> Vec<(String, T)> {
+        let mut admitted: HashMap<&str, bool> = HashMap::new();
+        let mut assigned = Vec::new();
+        while !self.pending.is_empty() {
+            let mut candidates = fleet.deploy_order();
+            candidates.sort_by_key(|m| self.dispatched.get(m.device.id()).copied().unwrap_or(0));
+            let Some(member) = candidates.into_iter().find(|member| {
+                let id = member.device.id();
+                *admitted.entry(id).or_insert_with(|| self.admit(member))
+            }) else {
+                break;
+            };
+            let id = member.device.id().to_string();
+            *self.dispatched.entry(id.clone()).or_default() += 1;
+            if let Some(request) = self.pending.pop_front() {
+                assigned.push((id, request));
+            }
+        }
+        if !self.pending.is_empty() {
+            tracing::warn!(
+                depth = self.pending.len(),
+                "No device admitted queued requests"
+            );
+        }
+        assigned
+    }
+
+    fn admit(&mut self, member: &FleetMember) -> bool {
+        let id = member.device.id();
+        let breaker = match self.breakers.entry(id.to_string()) {
+            std::collections::hash_map::Entry::Occupied(entry) => entry.into_mut(),
+            std::collections::hash_map::Entry::Vacant(entry) => {
+                match TegraMonitor::connect(&member.device) {
+                    Ok(monitor) => entry.insert(ThermalCircuitBreaker::new(
+                        monitor.with_policy(member.policy.clone()),
+                    )),
+                    Err(e) => {
+                        tracing::warn!(device = %id, error = %e, "Cannot monitor device");
+                        return false;
+                    }
+                }
+            }
+        };
+        breaker.try_admit().unwrap_or_else(|e| {
+            tracing::warn!(device = %id, error = %e, "Thermal admission check failed");
+            false
+        })
+    }
+}
+
+impl<T> Default for InferenceQueue<T> {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+/// Jetson executor for repartir integration.
+#[cfg(feature = "batuta")]
+#[derive(Debug)]
+pub struct JetsonExecutor {
+    /// Target IP address
+    pub ip: String,
+    /// Thermal policy
+    pub policy: ThermalPolicy,
+    /// Memory budget in MB
+    pub memory_budget_mb: u64,
+}
+
+#[cfg(feature = "batuta")]
+impl JetsonExecutor {
+    /// Create new executor.
+    #[must_use]
+    pub fn new(ip: impl Into<String>) -> Self {
+        Self {
+            ip: ip.into(),
+            policy: ThermalPolicy::default(),
+            memory_budget_mb: 6000,
+        }
+    }
+
+    /// Set thermal policy.
+    #[must_use]
+    pub fn with_thermal_policy(mut self, policy: ThermalPolicy) -> Self {
+        self.policy = policy;
+        self
+    }
+
+    /// Set memory budget.
+    #[must_use]
+    pub fn with_memory_budget_mb(mut self, budget: u64) -> Self {
+        self.memory_budget_mb = budget;
+        self
+    }
+}
+
+/// Point-in-time snapshot of a fleet, from [`Fleet::to_state`].
+///
+/// Serializable so snapshots can be stored and compared later.
+#[derive(Debug, Clone, Default, PartialEq, Serialize, Deserialize)]
+pub struct FleetState {
+    /// State per device ID
+    pub devices: BTreeMap<String, DeviceState>,
+}
+
+impl FleetState {
+    /// Changes from `self` (older) to `other` (newer).
+    #[must_use]
+    pub fn diff(&self, other: &Self) -> FleetDiff {
+        let mut diff = FleetDiff::default();
+        for (id, before) in &self.devices {
+            let Some(after) = other.devices.get(id) else {
+                diff.removed.push(id.clone());
+                continue;
+            };
+            diff.changes.extend(before.changes_to(id, after));
+        }
+        diff.added = other
+            .devices
+            .keys()
+            .filter(|id| !self.devices.contains_key(*id))
+            .cloned()
+            .collect();
+        diff
+    }
+
+    /// State `config` declares: its devices, their thermal policies and
+    /// power profiles, and the models targeting each at the configured
+    /// quant level.
+    ///
+    /// Devices are enabled, and cordoned only if tagged standby.
+    ///
+    /// # Errors
+    ///
+    /// Returns `Error::Config` for an unknown thermal policy, power preset,
+    /// model alias or quantization.
+    pub fn from_config(config: &CoheteConfig) -> Result<Self> {
+        let mut devices = BTreeMap::new();
+        for dev in &config.fleet.devices {
+            let power_profile = dev
+                .power_profile
+                .as_ref()
+                .map(|yaml| {
+                    let profile = yaml.to_profile().map_err(|e| match e {
+                        Error::Config(msg) => Error::Config(format!("device {}: {msg}", dev.id)),
+                        e => e,
+                    })?;
+                    Ok::<_, Error>(PowerProfileYaml::from_profile(&profile))
+                })
+                .transpose()?
+                .flatten();
+            let mut models = BTreeMap::new();
+            for model in config
+                .models
+                .iter()
+                .filter(|m| targets_device(&m.devices, &dev.id))
+            {
+                let source = config.resolve_source(&model.source)?;
+                let quant = config.quant_for_model_device(&model.name, &dev.id)?;
+                let (name, _) = quantized_target(Path::new(source), quant);
+                models.insert(
+                    name,
+                    ModelState {
+                        source: source.to_string(),
+                        quant,
+                    },
+                );
+            }
+            let state = DeviceState {
+                enabled: true,
+                cordoned: dev.tags.get(ROLE_TAG).map(String::as_str) == Some(STANDBY_ROLE),
+                thermal_policy: ThermalPolicyYaml::from(&configured_policy(config, dev)?),
+                power_profile,
+                models,
+            };
+            devices.insert(dev.id.clone(), state);
+        }
+        Ok(Self { devices })
+    }
+}
+
+impl DeviceState {
+    /// Changes to device `id` from `self` (older) to `after` (newer).
+    fn changes_to(&self, id: &str, after: &Self) -> Vec<DeviceChange> {
+        let device_id = || id.to_string();
+        let mut changes = Vec::new();
+        if self.enabled != after.enabled {
+            changes.push(DeviceChange::Enabled {
+                device_id: device_id(),
+                enabled: after.enabled,
+            });
+        }
+        if self.cordoned != after.cordoned {
+            changes.push(DeviceChange::Cordoned {
+                device_id: device_id(),
+                cordoned: after.cordoned,
+            });
+        }
+        if self.thermal_policy != after.thermal_policy {
+            changes.push(DeviceChange::ThermalPolicy {
+                device_id: device_id(),
+                from: self.thermal_policy.clone(),
+                to: after.thermal_policy.clone(),
+            });
+        }
+        if self.power_profile != after.power_profile {
+            changes.push(DeviceChange::PowerProfile {
+                device_id: device_id(),
+                from: self.power_profile.clone(),
+                to: after.power_profile.clone(),
+            });
+        }
+        let names: std::collections::BTreeSet<&String> =
+            self.models.keys().chain(after.models.keys()).collect();
+        for name in names {
+            let (from, to) = (self.models.get(name), after.models.get(name));
+            if from != to {
+                changes.push(DeviceChange::Model {
+                    device_id: device_id(),
+                    model: name.clone(),
+                    from: from.cloned(),
+                    to: to.cloned(),
+                });
+            }
+        }
+        changes
+    }
+}
+
+/// Snapshot of one device in a [`FleetState`].
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub struct DeviceState {
+    /// Device enabled for work
+    pub enabled: bool,
+    /// No new work is scheduled
+    pub cordoned: bool,
+    /// Thermal policy
+    pub thermal_policy: ThermalPolicyYaml,
+    /// Power profile, if set and expressible as a preset
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub power_profile: Option<PowerProfileYaml>,
+    /// Deployed models by name
+    pub models: BTreeMap<String, ModelState>,
+}
+
+/// Version of a deployed model: its F16 source and quant level.
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+pub struct ModelState {
+    /// F16 source path on the device
+    pub source: String,
+    /// Quantization level
+    pub quant: QuantLevel,
+}
+
+/// Differences between two [`FleetState`] snapshots.
+#[derive(Debug, Clone, Default, PartialEq)]
+pub struct FleetDiff {
+    /// Devices only in the newer snapshot, in ID order
+    pub added: Vec<String>,
+    /// Devices only in the older snapshot, in ID order
+    pub removed: Vec<String>,
+    /// Changes to devices in both, in ID order
+    pub changes: Vec<DeviceChange>,
+}
+
+impl FleetDiff {
+    /// Whether nothing changed.
+    #[must_use]
+    pub fn is_empty(&self) -> bool {
+        self.added.is_empty() && self.removed.is_empty() && self.changes.is_empty()
+    }
+
+    /// Changes to one device.
+    pub fn for_device<'a>(&'a self, id: &'a str) -> impl Iterator<Item = &'a DeviceChange> {
+        self.changes.iter().filter(move |c| c.device_id() == id)
+    }
+}
+
+/// Actions taken by [`Fleet::apply_diff`].
+#[derive(Debug, Clone, Default, PartialEq, Eq)]
+pub struct ApplyReport {
+    /// Devices in the config but not the current state
+    pub added_devices: Vec<String>,
+    /// Devices in the current state but no longer in the config
+    pub removed_devices: Vec<String>,
+    /// Devices whose thermal policy was updated
+    pub thermal_policies: Vec<String>,
+    /// Devices whose power profile was applied
+    pub power_profiles: Vec<String>,
+    /// `(device_id, model)` pairs deployed
+    pub deployed: Vec<(String, String)>,
+}
+
+impl ApplyReport {
+    /// Whether the fleet already matched the config.
+    #[must_use]
+    pub fn is_noop(&self) -> bool {
+        self.added_devices.is_empty()
+            && self.removed_devices.is_empty()
+            && self.thermal_policies.is_empty()
+            && self.power_profiles.is_empty()
+            && self.deployed.is_empty()
+    }
+}
+
+/// One change to a device between two snapshots.
+#[derive(Debug, Clone, PartialEq)]
+pub enum DeviceChange {
+    /// Device was enabled or disabled
+    Enabled {
+        /// Device ID
+        device_id: String,
+        /// New value
+        enabled: bool,
+    },
+    /// Device was cordoned or uncordoned
+    Cordoned {
+        /// Device ID
+        device_id: String,
+        /// New value
+        cordoned: bool,
+    },
+    /// Thermal policy changed
+    ThermalPolicy {
+        /// Device ID
+        device_id: String,
+        /// Old policy
+        from: ThermalPolicyYaml,
+        /// New policy
+        to: ThermalPolicyYaml,
+    },
+    /// Power profile set, cleared or changed
+    PowerProfile {
+        /// Device ID
+        device_id: String,
+        /// Old profile
+        from: Option<PowerProfileYaml>,
+        /// New profile
+        to: Option<PowerProfileYaml>,
+    },
+    /// Model deployed, removed, or changed source or quant level
+    Model {
+        /// Device ID
+        device_id: String,
+        /// Model name
+        model: String,
+        /// Old version (`None` if newly deployed)
+        from: Option<ModelState>,
+        /// New version (`None` if removed)
+        to: Option<ModelState>,
+    },
+}
+
+impl DeviceChange {
+    /// Device the change applies to.
+    #[must_use]
+    pub fn device_id(&self) -> &str {
+        match self {
+            Self::Enabled { device_id, .. }
+            | Self::Cordoned { device_id, .. }
+            | Self::ThermalPolicy { device_id, .. }
+            | Self::PowerProfile { device_id, .. }
+            | Self::Model { device_id, .. } => device_id,
+        }
+    }
+}
+
+/// Difference between a device's live state and the declared config,
+/// reported by [`Fleet::detect_drift`].
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum DriftItem {
+    /// Declared in config but not a fleet member
+    NotInFleet,
+    /// nvpmodel mode differs from the power preset's
+    PowerMode {
+        /// Mode of the declared preset
+        expected: PowerMode,
+        /// Mode the device runs
+        actual: PowerMode,
+    },
+    /// Fan speed differs from the declared one
+    FanSpeed {
+        /// Declared speed
+        expected: FanSpeed,
+        /// Current speed
+        actual: FanSpeed,
+    },
+    /// Kernel parameter from `provision.sysctls` differs or is unknown
+    Sysctl {
+        /// Parameter key, e.g. `vm.swappiness`
+        key: String,
+        /// Declared value
+        expected: String,
+        /// Live value; `None` if the kernel has no such key
+        actual: Option<String>,
+    },
+    /// Package from `provision.packages` is not installed
+    MissingPackage(String),
+    /// Model targeting the device is not in its manifest
+    MissingModel(String),
+    /// Model is deployed at another quantization
+    ModelQuant {
+        /// Model name
+        model: String,
+        /// Declared level
+        expected: QuantLevel,
+        /// Deployed level
+        actual: QuantLevel,
+    },
+    /// Device could not be queried; other drift is unknown
+    QueryFailed(String),
+}
+
+impl std::fmt::Display for DriftItem {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        match self {
+            Self::NotInFleet => write!(f, "declared but not in the fleet"),
+            Self::PowerMode { expected, actual } => {
+                write!(f, "power mode is {actual}, expected {expected}")
+            }
+            Self::FanSpeed { expected, actual } => {
+                write!(f, "fan speed is {actual}, expected {expected}")
+            }
+            Self::Sysctl {
+                key,
+                expected,
+                actual: Some(actual),
+            } => write!(f, "sysctl {key} is {actual}, expected {expected}"),
+            Self::Sysctl { key, .. } => write!(f, "sysctl {key} not present"),
+            Self::MissingPackage(package) => write!(f, "package {package} not installed"),
+            Self::MissingModel(model) => write!(f, "model {model} not deployed"),
+            Self::ModelQuant {
+                model,
+                expected,
+                actual,
+            } => write!(f, "model {model} is {actual}, expected {expected}"),
+            Self::QueryFailed(error) => write!(f, "could not be queried: {error}"),
+        }
+    }
+}
+
+/// Drift of one declared, online device; see [`Fleet::detect_drift`].
+async fn device_drift(
+    config: &CoheteConfig,
+    declared: &DeviceYamlConfig,
+    device: &JetsonDevice,
+) -> Result<Vec<DriftItem>> {
+    let id = &declared.id;
+    let mut drift = Vec::new();
+
+    // Resolve models up front so a bad config entry fails the call even
+    // when the device cannot be queried
+    let models = config
+        .models
+        .iter()
+        .filter(|m| targets_device(&m.devices, id))
+        .map(|model| {
+            let expected = model
+                .quantization
+                .as_deref()
+                .map(|quant| {
+                    quant.parse::<QuantLevel>().map_err(|_| {
+                        Error::Config(format!(
+                            "model {}: unknown quantization '{quant}'",
+                            model.name
+                        ))
+                    })
+                })
+                .transpose()?;
+            // Manifest entries are keyed like `record_deployment` keys them
+            let key = model_name(Path::new(config.resolve_source(&model.source)?));
+            Ok((model, key, expected))
+        })
+        .collect::<Result<Vec<_>>>()?;
+
+    if let Some(yaml) = &declared.power_profile {
+        let profile = yaml.to_profile().map_err(|e| match e {
+            Error::Config(msg) => Error::Config(format!("device {id}: {msg}")),
+            e => e,
+        })?;
+        let actual = PowerController::new().current_mode(device).await?;
+        if actual != profile.mode {
+            drift.push(DriftItem::PowerMode {
+                expected: profile.mode,
+                actual,
+            });
+        }
+        if let Some(expected) = yaml.fan_speed {
+            let actual = FanController::new().speed(device).await?;
+            if actual != expected {
+                drift.push(DriftItem::FanSpeed { expected, actual });
+            }
+        }
+    }
+
+    let sysctls = &config.provision.sysctls;
+    if !sysctls.is_empty() {
+        let keys: Vec<String> = sysctls.keys().map(|k| shell_quote(k)).collect();
+        let listing = device
+            .exec(&format!("sysctl -e {} || true", keys.join(" ")))
+            .await?;
+        let live = parse_sysctls(&listing);
+        for (key, expected) in sysctls {
+            let actual = live.get(key.as_str()).cloned();
+            if actual.as_deref() != Some(normalize_sysctl(expected).as_str()) {
+                drift.push(DriftItem::Sysctl {
+                    key: key.clone(),
+                    expected: expected.clone(),
+                    actual,
+                });
+            }
+        }
+    }
+
+    let packages = &config.provision.packages;
+    if !packages.is_empty() {
+        let names: Vec<String> = packages.iter().map(|p| shell_quote(p)).collect();
+        let listing = device
+            .exec(&format!(
+                "dpkg-query -W -f='${{db:Status-Status}} ${{Package}}\\n' {} 2>/dev/null || true",
+                names.join(" ")
+            ))
+            .await?;
+        let installed = parse_installed_packages(&listing);
+        drift.extend(
+            packages
+                .iter()
+                .filter(|p| !installed.contains(p.as_str()))
+                .map(|p| DriftItem::MissingPackage(p.clone())),
+        );
+    }
+
+    if !models.is_empty() {
+        let manifest = StorageLayout::default().read_manifest(device).await?;
+        for (model, key, expected) in models {
+            match (manifest.get(&key), expected) {
+                (None, _) => drift.push(DriftItem::MissingModel(model.name.clone())),
+                (Some(entry), Some(expected)) if entry.quant != expected => {
+                    drift.push(DriftItem::ModelQuant {
+                        model: model.name.clone(),
+                        expected,
+                        actual: entry.quant,
+                    });
+                }
+                _ => {}
+            }
+        }
+    }
+    Ok(drift)
+}
+
+/// Parse `key = value` lines of `sysctl` output, normalizing whitespace.
+fn parse_sysctls(output: &str) -> HashMap<&str, String> {
+    output
+        .lines()
+        .filter_map(|line| line.split_once(" = "))
+        .map(|(key, value)| (key.trim(), normalize_sysctl(value)))
+        .collect()
+}
+
+/// Collapse the tab and space runs multi-field sysctls print with.
+fn normalize_sysctl(value: &str) -> String {
+    value.split_whitespace().collect::<Vec<_>>().join(" ")
+}
+
+/// Thermal policy named by a declared device.
+fn configured_policy(config: &CoheteConfig, dev: &DeviceYamlConfig) -> Result<ThermalPolicy> {
+    match dev.thermal_policy.as_str() {
+        "conservative" => Ok(config.thermal.conservative.clone().into()),
+        "aggressive" => Ok(config.thermal.aggressive.clone().into()),
+        other => Err(Error::Config(format!(
+            "device {}: unknown thermal policy '{other}'",
+            dev.id
+        ))),
+    }
+}
+
+/// Whether a model's `devices` selector ("all" or a list of IDs) covers `id`.
+fn targets_device(selector: &str, id: &str) -> bool {
+    selector.trim() == "all"
+        || selector
+            .split(|c: char| c == ',' || c.is_whitespace())
+            .any(|target| target == id)
+}
+
+/// Names of installed packages in `dpkg-query` "status name" lines.
+fn parse_installed_packages(listing: &str) -> HashSet<&str> {
+    listing
+        .lines()
+        .filter_map(|line| line.trim().strip_prefix("installed "))
+        .collect()
+}
+
+/// Bytes of a model file read by [`Fleet::verify_quant`]; enough for the
+/// general metadata ahead of the tokenizer.
+const VERIFY_HEADER_BYTES: usize = 1024 * 1024;
+
+/// Smallest fractional size reduction worth compressing a transfer for.
+pub const MIN_TRANSFER_SAVING: f64 = 0.1;
+
+/// Bytes of a model compressed to decide whether compression pays off.
+const TRANSFER_SAMPLE_BYTES: usize = 4 * 1024 * 1024;
+
+/// zstd level for transfers; fast enough to beat slow links.
+const TRANSFER_ZSTD_LEVEL: i32 = 3;
+
+/// Bytes sent per device by [`Fleet::deploy_model_with`].
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct TransferStats {
+    /// Model size
+    pub original_bytes: u64,
+    /// Bytes sent over the link
+    pub transferred_bytes: u64,
+}
+
+impl TransferStats {
+    const fn uncompressed(len: usize) -> Self {
+        Self {
+            original_bytes: len as u64,
+            transferred_bytes: len as u64,
+        }
+    }
+
+    /// Whether the transfer was compressed.
+    #[must_use]
+    pub const fn compressed(&self) -> bool {
+        self.transferred_bytes != self.original_bytes
+    }
+
+    /// Original over transferred size (1.0 when not compressed).
+    #[must_use]
+    #[allow(clippy::cast_precision_loss)]
+    pub fn ratio(&self) -> f64 {
+        if self.transferred_bytes == 0 {
+            return 1.0;
+        }
+        self.original_bytes as f64 / self.transferred_bytes as f64
+    }
+}
+
+/// zstd-compress `data`, or `None` if it would not save [`MIN_TRANSFER_SAVING`].
+fn compress_for_transfer(data: &[u8]) -> Result<Option<Vec<u8>>> {
+    let saves = |original: usize, compressed: usize| {
+        #[allow(clippy::cast_precision_loss)]
+        let saving = 1.0 - compressed as f64 / original.max(1) as f64;
+        saving >= MIN_TRANSFER_SAVING
+    };
+    let sample = &data[..data.len().min(TRANSFER_SAMPLE_BYTES)];
+    if !saves(
+        sample.len(),
+        zstd::bulk::compress(sample, TRANSFER_ZSTD_LEVEL)?.len(),
+    ) {
+        return Ok(None);
+    }
+    let compressed = zstd::bulk::compress(data, TRANSFER_ZSTD_LEVEL)?;
+    Ok(saves(data.len(), compressed.len()).then_some(compressed))
+}
+
+/// Token bucket bounding the retries of fleet operations.
+///
+/// Holds up to `max_retries` tokens, refilled evenly over `window`. Each
+/// retry spends one; once empty, failures surface immediately, so a flaky
+/// network cannot turn per-device retries into a retry storm.
+#[derive(Debug)]
+pub struct RetryBudget {
+    max_retries: u32,
+    window: Duration,
+    backoff: Duration,
+    state: Mutex<(f64, Instant)>,
+}
+
+impl RetryBudget {
+    /// Allow `max_retries` retries per `window`, starting full.
+    #[must_use]
+    pub fn new(max_retries: u32, window: Duration) -> Self {
+        Self {
+            max_retries,
+            window,
+            backoff: DEFAULT_RETRY_BACKOFF,
+            state: Mutex::new((f64::from(max_retries), Instant::now())),
+        }
+    }
+
+    /// Set the delay before each retry.
+    #[must_use]
+    pub const fn with_backoff(mut self, backoff: Duration) -> Self {
+        self.backoff = backoff;
+        self
+    }
+
+    /// Spend one retry, if any remain.
+    pub fn try_acquire(&self) -> bool {
+        let mut state = self.refilled();
+        if state.0 < 1.0 {
+            return false;
+        }
+        state.0 -= 1.0;
+        true
+    }
+
+    /// Whole retries currently available.
+    #[must_use]
+    #[allow(clippy::cast_possible_truncation, clippy::cast_sign_loss)]
+    pub fn remaining(&self) -> u32 {
+        self.refilled().0 as u32
+    }
+
+    fn refilled(&self) -> std::sync::MutexGuard<'_, (f64, Instant)> {
+        let mut state = self.state.lock().unwrap_or_else(PoisonError::into_inner);
+        let now = Instant::now();
+        if !self.window.is_zero() {
+            let earned = now.duration_since(state.1).as_secs_f64() / self.window.as_secs_f64()
+                * f64::from(self.max_retries);
+            state.0 = (state.0 + earned).min(f64::from(self.max_retries));
+        }
+        state.1 = now;
+        state
+    }
+}
+
+/// Downgrades needed to step from the highest quant level, `F32`, to `Q4_0`.
+pub const DEFAULT_MAX_OOM_RETRIES: u32 = 6;
+
+/// Deployment configuration.
+#[derive(Debug, Clone)]
+pub struct DeploymentConfig {
+    /// Target devices (empty = all)
+    pub target_devices: Vec<String>,
+    /// Model quantization level
+    pub quantization: Option<String>,
+    /// Memory budget per device in MB
+    pub memory_budget_mb: u64,
+    /// Thermal policy
+    pub thermal_policy: ThermalPolicy,
+    /// Quant level downgrades allowed when a model OOMs on load; the
+    /// default, [`DEFAULT_MAX_OOM_RETRIES`], covers the whole quant ladder
+    pub max_oom_retries: u32,
+    /// zstd-compress the transfer in [`Fleet::deploy_model_with`] when it
+    /// saves bytes
+    pub compress: bool,
+}
+
+impl Default for DeploymentConfig {
+    fn default() -> Self {
+        Self {
+            target_devices: Vec::new(),
+            quantization: Some("q4_0".to_string()),
+            memory_budget_mb: 6000,
+            thermal_policy: ThermalPolicy::default(),
+            max_oom_retries: DEFAULT_MAX_OOM_RETRIES,
+            compress: false,
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::device::{
+        mock::{self, MockExecutor},
+        ConnectionMethod, DeviceInfo,
+    };
+    use std::sync::Arc;
+
+    const COOL: &str =
+        "RAM 2048/7620MB CPU [5%@729] GR3D_FREQ 0% CPU@45C GPU@44.5C VDD_IN 4000mW/4000mW";
+    const HOT: &str =
+        "RAM 2048/7620MB CPU [95%@1510] GR3D_FREQ 99% CPU@70C GPU@68C VDD_IN 14000mW/9000mW";
+
+    fn make_test_device(id: &str, model: crate::JetsonModel) -> JetsonDevice {
+        JetsonDevice::new(DeviceInfo {
+            id: id.to_string(),
+            model,
+            connection: ConnectionMethod::Usb,
+            jetpack_version: None,
+            hostname: None,
+        })
+    }
+
+    #[test]
+    fn test_fleet_empty() {
+        let fleet = Fleet::new();
+        assert!(fleet.is_empty());
+        assert_eq!(fleet.len(), 0);
+        assert_eq!(fleet.enabled_count(), 0);
+    }
+
+    #[test]
+    fn test_fleet_add_device() {
+        let mut fleet = Fleet::new();
+        let device = make_test_device("jetson-01", crate::JetsonModel::OrinNano8GB);
+        fleet.add_device(device, ThermalPolicy::conservative()).unwrap();
+        assert_eq!(fleet.len(), 1);
+        assert!(!fleet.is_empty());
+        assert_eq!(fleet.enabled_count(), 1);
+    }
+
+    #[test]
+    fn test_fleet_remove_device() {
+        let mut fleet = Fleet::new();
+        let device = make_test_device("jetson-01", crate::JetsonModel::OrinNano8GB);
+        fleet.add_device(device, ThermalPolicy::conservative()).unwrap();
+        assert_eq!(fleet.len(), 1);
+
+        let removed = fleet.remove_device("jetson-01");
+        assert!(removed.is_some());
+        assert!(fleet.is_empty());
+
+        let not_found = fleet.remove_device("nonexistent");
+        assert!(not_found.is_none());
+    }
+
+    #[test]
+    fn test_fleet_get_device() {
+        let mut fleet = Fleet::new();
+        let device = make_test_device("jetson-02", crate::JetsonModel::OrinNX16GB);
+        fleet.add_device(device, ThermalPolicy::aggressive()).unwrap();
+
+        let member = fleet.get("jetson-02");
+        assert!(member.is_some());
+        assert!(member.unwrap().enabled);
+
+        let not_found = fleet.get("nonexistent");
+        assert!(not_found.is_none());
+    }
+
+    #[test]
+    fn test_fleet_devices_iterator() {
+        let mut fleet = Fleet::new();
+        fleet.add_device(
+            make_test_device("j1", crate::JetsonModel::OrinNano4GB),
+            ThermalPolicy::conservative()
+        ).unwrap();
+        fleet.add_device(
+            make_test_device("j2", crate::JetsonModel::OrinNano8GB),
+            ThermalPolicy::aggressive()
+        ).unwrap();
+
+        let count = fleet.devices().count();
+        assert_eq!(count, 2);
+    }
+
+    #[test]
+    fn test_fleet_health() {
+        let fleet = Fleet::new();
+        let health = fleet.health_status();
+        assert_eq!(health.total_devices, 0);
+        assert_eq!(health.enabled_devices, 0);
+        assert_eq!(health.healthy_devices, 0);
+        assert_eq!(health.degraded_devices, 0);
+        assert_eq!(health.offline_devices, 0);
+        assert_eq!(health.health_percent(), 100.0);
+    }
+
+    #[test]
+    fn test_fleet_health_with_devices() {
+        let mut fleet = Fleet::new();
+        fleet.add_device(
+            make_test_device("j1", crate::JetsonModel::OrinNano8GB),
+            ThermalPolicy::conservative()
+        ).unwrap();
+        fleet.add_device(
+            make_test_device("j2", crate::JetsonModel::OrinNano8GB),
+            ThermalPolicy::conservative()
+        ).unwrap();
+
+        let health = fleet.health_status();
+        assert_eq!(health.total_devices, 2);
+        assert_eq!(health.enabled_devices, 2);
+        assert_eq!(health.healthy_devices, 2);
+        assert_eq!(health.health_percent(), 100.0);
+    }
+
+    #[test]
+    fn test_fleet_health_clone() {
+        let health = FleetHealth {
+            total_devices: 5,
+            enabled_devices: 4,
+            healthy_devices: 3,
+            degraded_devices: 1,
+            offline_devices: 1,
+        };
+        let cloned = health.clone();
+        assert_eq!(cloned.total_devices, 5);
+        assert!((cloned.health_percent() - 60.0).abs() < 0.001);
+    }
+
+    #[test]
+    fn test_deployment_config() {
+        let config = DeploymentConfig::default();
+        assert_eq!(config.quantization, Some("q4_0".to_string()));
+        assert_eq!(config.memory_budget_mb, 6000);
+        assert!(config.target_devices.is_empty());
+    }
+
+    #[test]
+    fn test_deployment_config_clone() {
+        let config = DeploymentConfig {
+            target_devices: vec!["j1".to_string(), "j2".to_string()],
+            quantization: Some("q8_0".to_string()),
+            memory_budget_mb: 4000,
+            thermal_policy: ThermalPolicy::aggressive(),
+            max_oom_retries: 1,
+            compress: true,
+        };
+        let cloned = config.clone();
+        assert_eq!(cloned.target_devices.len(), 2);
+        assert_eq!(cloned.quantization, Some("q8_0".to_string()));
+    }
+
+    #[test]
+    fn test_fleet_member_fields() {
+        let mut fleet = Fleet::new();
+        let device = make_test_device("j1", crate::JetsonModel::AgxOrin64GB);
+        fleet.add_device(device, ThermalPolicy::aggressive()).unwrap();
+
+        let member = fleet.get("j1").unwrap();
+        assert!(member.enabled);
+        assert_eq!(member.policy.threshold_c, 75.0);
+        assert_eq!(member.device.id(), "j1");
+    }
+
+    #[tokio::test]
+    async fn test_fleet_deploy_model() {
+        let fleet = Fleet::new();
+        let result = fleet.deploy_model(&[1, 2, 3]).await;
+        assert!(result.is_ok());
+    }
+
+    #[tokio::test]
+    async fn test_fleet_start_inference_servers() {
+        let fleet = Fleet::new();
+        let result = fleet.start_inference_servers().await;
+        assert!(result.is_ok());
+    }
+
+    #[cfg(feature = "batuta")]
+    #[test]
+    fn test_jetson_executor() {
+        let executor = JetsonExecutor::new("192.168.1.100")
+            .with_thermal_policy(ThermalPolicy::aggressive())
+            .with_memory_budget_mb(4000);
+        assert_eq!(executor.ip, "192.168.1.100");
+        assert_eq!(executor.memory_budget_mb, 4000);
+        assert_eq!(executor.policy.threshold_c, 75.0);
+    }
+
+    fn readiness_fleet(lines: &[(&str, &str)]) -> (Fleet, Vec<Arc<MockExecutor>>) {
+        let mut fleet = Fleet::new();
+        let mut execs = Vec::new();
+        for (id, tegrastats) in lines {
+            let exec = Arc::new(MockExecutor::new().on("tegrastats", tegrastats));
+            let device = mock::device(id, crate::JetsonModel::OrinNano8GB, &exec);
+            fleet
+                .add_device(device, ThermalPolicy::conservative())
+                .unwrap();
+            execs.push(exec);
+        }
+        (fleet, execs)
+    }
+
+    fn full_requirements() -> ReadinessRequirements {
+        ReadinessRequirements::default()
+            .with_min_healthy_devices(2)
+            .with_required_model("llama-3.2-1b-q4_0.gguf")
+            .with_max_temp_c(65.0)
+            .with_servers_responding(8080)
+    }
+
+    #[tokio::test]
+    async fn test_readiness_passes() {
+        let (fleet, execs) = readiness_fleet(&[("j1", COOL), ("j2", COOL)]);
+        let report = fleet.readiness(full_requirements()).await;
+
+        assert!(report.ready, "{report:?}");
+        assert_eq!(report.checks.len(), 4);
+        assert_eq!(report.failures().count(), 0);
+        assert_eq!(
+            execs[0].count("test -f '/mnt/nvme/models/llama-3.2-1b-q4_0.gguf'"),
+            1
+        );
+        assert_eq!(execs[1].count("http://localhost:8080/health"), 1);
+    }
+
+    #[tokio::test]
+    async fn test_readiness_fails_on_hot_device() {
+        let (fleet, _) = readiness_fleet(&[("j1", COOL), ("j2", HOT)]);
+        let report = fleet
+            .readiness(full_requirements().with_min_healthy_devices(1))
+            .await;
+
+        assert!(!report.ready);
+        let failures: Vec<_> = report.failures().collect();
+        assert_eq!(failures.len(), 1);
+        assert_eq!(
+            failures[0].requirement,
+            ReadinessRequirement::MaxTemperature
+        );
+        assert!(failures[0].detail.contains("j2 (68.0°C)"));
+    }
+
+    #[tokio::test]
+    async fn test_readiness_fails_on_missing_model() {
+        let mut fleet = Fleet::new();
+        let exec = Arc::new(
+            MockExecutor::new()
+                .on("tegrastats", COOL)
+                .fail("test -f", 1, ""),
+        );
+        fleet
+            .add_device(
+                mock::device("j1", crate::JetsonModel::OrinNano8GB, &exec),
+                ThermalPolicy::conservative(),
+            )
+            .unwrap();
+
+        let report = fleet
+            .readiness(ReadinessRequirements::default().with_required_model("model.gguf"))
+            .await;
+        assert!(!report.ready);
+        let check = report.check(ReadinessRequirement::ModelDeployed).unwrap();
+        assert!(!check.passed);
+        assert_eq!(check.detail, "model.gguf missing on: j1");
+        assert!(
+            report
+                .check(ReadinessRequirement::MinHealthyDevices)
+                .unwrap()
+                .passed
+        );
+    }
+
+    #[tokio::test]
+    async fn test_readiness_empty_fleet() {
+        let report = Fleet::new()
+            .readiness(ReadinessRequirements::default())
+            .await;
+        assert!(!report.ready);
+        assert_eq!(
+            report.failures().next().unwrap().requirement,
+            ReadinessRequirement::MinHealthyDevices
+        );
+    }
+
+    fn oom_config() -> DeploymentConfig {
+        DeploymentConfig {
+            quantization: Some("q8_0".to_string()),
+            ..DeploymentConfig::default()
+        }
+    }
+
+    #[tokio::test]
+    async fn test_deploy_oom_fallback_to_q4() {
+        let exec = Arc::new(
+            disk_mock()
+                .fail("-m '/mnt/nvme/models/llama.q8_0.gguf'", 137, "Killed")
+                .fail("-m '/mnt/nvme/models/llama.q5_", 1, "ggml: out of memory"),
+        );
+        let mut fleet = Fleet::new();
+        fleet
+            .add_device(
+                mock::device("j1", crate::JetsonModel::OrinNano8GB, &exec),
+                ThermalPolicy::conservative(),
+            )
+            .unwrap();
+
+        let deployments = fleet
+            .deploy_with_oom_fallback("/mnt/nvme/models/llama.f16.gguf", &oom_config())
+            .await
+            .unwrap();
+
+        assert_eq!(
+            deployments,
+            vec![DeviceDeployment {
+                device_id: "j1".to_string(),
+                level: QuantLevel::Q4_1,
+                oom_retries: 3,
+            }]
+        );
+        assert_eq!(exec.count("llama-quantize"), 4);
+        assert_eq!(
+            exec.count(
+                "llama-quantize '/mnt/nvme/models/llama.f16.gguf' '/mnt/nvme/models/llama.q4_1.gguf' Q4_1"
+            ),
+            1
+        );
+    }
+
+    #[tokio::test]
+    async fn test_deploy_oom_fallback_retry_limit() {
+        let exec = Arc::new(disk_mock().fail("llama-cli", 137, "Killed"));
+        let mut fleet = Fleet::new();
+        fleet
+            .add_device(
+                mock::device("j1", crate::JetsonModel::OrinNano4GB, &exec),
+                ThermalPolicy::conservative(),
+            )
+            .unwrap();
+        let config = DeploymentConfig {
+            max_oom_retries: 1,
+            ..oom_config()
+        };
+
+        let err = fleet
+            .deploy_with_oom_fallback("/tmp/llama.f16.gguf", &config)
+            .await
+            .unwrap_err();
+        assert!(matches!(err, Error::Quantization(_)));
+        assert_eq!(exec.count("llama-cli"), 2);
     }
 
     #[test]
-    fn test_fleet_empty() {
-        let fleet = Fleet::new();
-        assert!(fleet.is_empty());
-        assert_eq!(fleet.len(), 0);
-        assert_eq!(fleet.enabled_count(), 0);
+    fn test_is_oom_ignores_lookalike_words() {
+        let failed = |exit_code, stderr: &str| Error::CommandFailed {
+            command: "llama-cli".to_string(),
+            exit_code,
+            stderr: stderr.to_string(),
+        };
+        assert!(is_oom(&failed(137, "")));
+        assert!(is_oom(&failed(1, "Killed")));
+        assert!(is_oom(&failed(1, "ggml: Out of memory")));
+        assert!(is_oom(&failed(1, "kernel: oom-kill: constraint=none")));
+        assert!(!is_oom(&failed(1, "no room on device")));
+        assert!(!is_oom(&failed(1, "bloom filter load failed")));
+        assert!(!is_oom(&failed(1, "zoom level invalid")));
+
+        // The default budget reaches Q4_0 from the top of the ladder
+        let mut level = QuantLevel::F32;
+        for _ in 0..DeploymentConfig::default().max_oom_retries {
+            level = level.next_lower().unwrap();
+        }
+        assert_eq!(level, QuantLevel::Q4_0);
+    }
+
+    #[tokio::test]
+    async fn test_deploy_oom_fallback_other_error_not_retried() {
+        let exec = Arc::new(disk_mock().fail("llama-quantize", 2, "No such file"));
+        let mut fleet = Fleet::new();
+        fleet
+            .add_device(
+                mock::device("j1", crate::JetsonModel::OrinNano8GB, &exec),
+                ThermalPolicy::conservative(),
+            )
+            .unwrap();
+
+        let err = fleet
+            .deploy_with_oom_fallback("/tmp/missing.gguf", &oom_config())
+            .await
+            .unwrap_err();
+        assert!(matches!(err, Error::CommandFailed { exit_code: 2, .. }));
+        assert_eq!(exec.count("llama-quantize"), 1);
+    }
+
+    #[tokio::test]
+    async fn test_backend_report() {
+        let mut fleet = Fleet::new();
+        for (id, telemetry) in [
+            ("j1", Some(r#"{"backend":"neon"}"#)),
+            ("j2", Some(r#"{"backend":"cuda"}"#)),
+            ("j3", None),
+        ] {
+            let exec = telemetry.map_or_else(
+                || MockExecutor::new().fail("/telemetry", 7, ""),
+                |body| MockExecutor::new().on("/telemetry", body),
+            );
+            let device = mock::device(id, crate::JetsonModel::OrinNano8GB, &Arc::new(exec));
+            fleet.add_device(device, ThermalPolicy::default()).unwrap();
+        }
+
+        let report = fleet.backend_report(8080).await;
+
+        assert_eq!(report.devices.len(), 3);
+        assert_eq!(report.counts().get("neon"), Some(&1));
+        assert_eq!(report.counts().get("cuda"), Some(&1));
+        // Hint selects NEON on Orin, so CUDA is the unexpected one
+        let mismatches: Vec<_> = report.mismatches().map(|(id, _)| id).collect();
+        assert_eq!(mismatches, vec!["j2"]);
+        assert_eq!(report.unreachable().collect::<Vec<_>>(), vec!["j3"]);
+    }
+
+    fn priority_fleet() -> Fleet {
+        let mut fleet = Fleet::new();
+        for (id, priority) in [
+            ("worker-b", 10),
+            ("canary", 0),
+            ("worker-a", 10),
+            ("early", 5),
+        ] {
+            fleet
+                .add_device_with_priority(
+                    make_test_device(id, crate::JetsonModel::OrinNano8GB),
+                    ThermalPolicy::default(),
+                    priority,
+                )
+                .unwrap();
+        }
+        fleet
+    }
+
+    #[tokio::test]
+    async fn test_rolling_update_follows_priority() {
+        let fleet = priority_fleet();
+        let order = std::sync::Mutex::new(Vec::new());
+
+        let report = fleet
+            .rolling_update(|member| {
+                order.lock().unwrap().push(member.device.id().to_string());
+                async { Ok(()) }
+            })
+            .await;
+
+        assert!(report.is_complete());
+        let expected = vec!["canary", "early", "worker-a", "worker-b"];
+        assert_eq!(order.into_inner().unwrap(), expected);
+        assert_eq!(report.completed, expected);
+    }
+
+    #[tokio::test]
+    async fn test_rolling_update_canary_failure_halts() {
+        let fleet = priority_fleet();
+        let attempted = std::sync::Mutex::new(Vec::new());
+
+        let report = fleet
+            .rolling_update(|member| {
+                let id = member.device.id().to_string();
+                attempted.lock().unwrap().push(id.clone());
+                async move {
+                    if id == "canary" {
+                        Err(Error::Provisioning("warmup failed".to_string()))
+                    } else {
+                        Ok(())
+                    }
+                }
+            })
+            .await;
+
+        assert!(!report.is_complete());
+        assert_eq!(attempted.into_inner().unwrap(), vec!["canary"]);
+        assert_eq!(report.failed.as_ref().unwrap().0, "canary");
+        assert_eq!(report.skipped, vec!["early", "worker-a", "worker-b"]);
+        let err = report.into_result().unwrap_err();
+        let message = err.to_string();
+        assert!(message.contains("rollout halted at canary (3 skipped)"));
+    }
+
+    #[tokio::test]
+    async fn test_deploy_model_uploads_in_priority_order() {
+        let canary = Arc::new(disk_mock().fail("cat >", 1, "No space left on device"));
+        let worker = Arc::new(disk_mock());
+        let mut fleet = Fleet::new();
+        fleet
+            .add_device_with_priority(
+                mock::device("canary", crate::JetsonModel::OrinNano8GB, &canary),
+                ThermalPolicy::default(),
+                -1,
+            )
+            .unwrap();
+        fleet
+            .add_device(
+                mock::device("worker", crate::JetsonModel::OrinNano8GB, &worker),
+                ThermalPolicy::default(),
+            )
+            .unwrap();
+
+        let err = fleet.deploy_model(b"GGUF").await.unwrap_err();
+        assert!(err.to_string().contains("canary"));
+        assert_eq!(canary.inputs().len(), 1);
+        assert!(worker.inputs().is_empty());
+    }
+
+    fn queue_fleet(hot_temps: &[&str]) -> (Fleet, Arc<MockExecutor>, Arc<MockExecutor>) {
+        let hot = Arc::new(MockExecutor::new().on_seq("tegrastats", hot_temps));
+        let cool = Arc::new(MockExecutor::new().on("tegrastats", "GPU@42C"));
+        let mut fleet = Fleet::new();
+        for (id, exec) in [("hot", &hot), ("cool", &cool)] {
+            fleet
+                .add_device(
+                    mock::device(id, crate::JetsonModel::OrinNano8GB, exec),
+                    ThermalPolicy::conservative(),
+                )
+                .unwrap();
+        }
+        (fleet, hot, cool)
+    }
+
+    #[test]
+    fn test_inference_queue_routes_around_hot_device() {
+        let (fleet, hot, cool) = queue_fleet(&["GPU@80C", "GPU@50C"]);
+        let mut queue = InferenceQueue::new();
+        for request in 0..4 {
+            queue.push(request);
+        }
+        assert_eq!(queue.depth(), 4);
+
+        // The hot device rejects admission; everything goes to the cool one
+        let assigned = queue.dispatch(&fleet);
+        assert_eq!(
+            assigned,
+            vec![
+                ("cool".to_string(), 0),
+                ("cool".to_string(), 1),
+                ("cool".to_string(), 2),
+                ("cool".to_string(), 3),
+            ]
+        );
+        assert_eq!(queue.depth(), 0);
+        assert_eq!(queue.dispatch_counts().get("hot"), None);
+        assert_eq!(queue.dispatch_counts()["cool"], 4);
+        assert_eq!((hot.count("tegrastats"), cool.count("tegrastats")), (1, 1));
+
+        // Once cooled, the idle device catches up
+        for request in 4..8 {
+            queue.push(request);
+        }
+        let assigned = queue.dispatch(&fleet);
+        assert!(assigned.iter().all(|(id, _)| id == "hot"));
+        assert_eq!(queue.dispatch_counts()["hot"], 4);
+        assert_eq!(queue.dispatch_counts()["cool"], 4);
+    }
+
+    #[test]
+    fn test_inference_queue_holds_requests_when_all_hot() {
+        let (mut fleet, _, _) = queue_fleet(&["GPU@80C"]);
+        fleet.cordon("cool").unwrap();
+        let mut queue = InferenceQueue::new();
+        queue.push("a");
+        queue.push("b");
+
+        assert!(queue.dispatch(&fleet).is_empty());
+        assert_eq!(queue.depth(), 2);
+        assert!(queue.dispatch_counts().is_empty());
+    }
+
+    #[tokio::test]
+    async fn test_deploy_model_thermal_aware_interleaves_enclosures() {
+        let mut fleet = Fleet::new();
+        let mut execs = HashMap::new();
+        for (id, enclosure) in [
+            ("a1", Some("A")),
+            ("a2", Some("A")),
+            ("b1", Some("B")),
+            ("b2", Some("B")),
+            ("b3", Some("B")),
+            ("solo", None),
+        ] {
+            let exec = Arc::new(if id == "b2" {
+                disk_mock().fail("cat >", 1, "No space left on device")
+            } else {
+                disk_mock()
+            });
+            fleet
+                .add_device(
+                    mock::device(id, crate::JetsonModel::OrinNano8GB, &exec),
+                    ThermalPolicy::default(),
+                )
+                .unwrap();
+            if let Some(enclosure) = enclosure {
+                fleet.set_tag(id, ENCLOSURE_TAG, enclosure).unwrap();
+            }
+            execs.insert(id, exec);
+        }
+
+        // Same-enclosure devices are serialized across waves; each wave
+        // runs one device per enclosure concurrently
+        let waves: Vec<Vec<&str>> = fleet
+            .thermal_deploy_waves()
+            .iter()
+            .map(|wave| wave.iter().map(|m| m.device.id()).collect())
+            .collect();
+        assert_eq!(
+            waves,
+            vec![vec!["a1", "b1", "solo"], vec!["a2", "b2"], vec!["b3"]]
+        );
+
+        // b2 fails: its wave finishes, later waves are skipped
+        let err = fleet.deploy_model_thermal_aware(b"GGUF").await.unwrap_err();
+        assert!(err.to_string().contains("rollout halted at b2 (1 skipped)"));
+        for id in ["a1", "a2", "b1", "b2", "solo"] {
+            assert_eq!(execs[id].inputs().len(), 1, "{id}");
+        }
+        assert!(execs["b3"].inputs().is_empty());
+    }
+
+    #[tokio::test]
+    async fn test_deploy_model_thermal_aware_overlaps_enclosures() {
+        let latency = Duration::from_millis(100);
+        let mut fleet = Fleet::new();
+        let mut execs = HashMap::new();
+        for (id, enclosure) in [("a1", "A"), ("a2", "A"), ("b1", "B")] {
+            let exec = Arc::new(disk_mock().with_latency(latency));
+            fleet
+                .add_device(
+                    mock::device(id, crate::JetsonModel::OrinNano8GB, &exec),
+                    ThermalPolicy::default(),
+                )
+                .unwrap();
+            fleet.set_tag(id, ENCLOSURE_TAG, enclosure).unwrap();
+            execs.insert(id, exec);
+        }
+
+        fleet.deploy_model_thermal_aware(b"GGUF").await.unwrap();
+
+        let upload = |id: &str| execs[id].spans("cat >")[0];
+        let ((a1_start, a1_end), (a2_start, _), (b1_start, b1_end)) =
+            (upload("a1"), upload("a2"), upload("b1"));
+        // Different enclosures transfer at the same time
+        assert!(a1_start < b1_end && b1_start < a1_end);
+        // The same enclosure waits for the previous transfer to finish
+        assert!(a2_start >= a1_end);
+    }
+
+    #[tokio::test]
+    async fn test_deploy_model_size_cap_rejects_before_transfer() {
+        let yaml = "
+fleet:
+  max_model_size_gb: 0.000001
+  groups:
+    edge:
+      max_model_size_gb: 0.00001
+  devices:
+    - id: core
+    - id: edge
+      tags:
+        group: edge
+";
+        let config = CoheteConfig::from_yaml(yaml).unwrap();
+        let fleet = Fleet::from_config(&config).unwrap();
+        assert_eq!(fleet.max_model_size_gb_for("core"), Some(0.000_001));
+        assert_eq!(fleet.max_model_size_gb_for("edge"), Some(0.000_01));
+
+        let mut fleet = Fleet::new()
+            .with_max_model_size_gb(0.000_001)
+            .with_group_max_model_size_gb("edge", 0.000_01);
+        let core = Arc::new(disk_mock());
+        let edge = Arc::new(disk_mock());
+        for (id, exec) in [("core", &core), ("edge", &edge)] {
+            fleet
+                .add_device(
+                    mock::device(id, crate::JetsonModel::OrinNano8GB, exec),
+                    ThermalPolicy::default(),
+                )
+                .unwrap();
+        }
+        fleet.set_tag("edge", GROUP_TAG, "edge").unwrap();
+
+        // ~1KB cap: a 5KB model fits the edge override but not the core
+        // device, so nothing is transferred anywhere
+        let err = fleet.deploy_model(vec![0u8; 5000]).await.unwrap_err();
+        assert!(matches!(&err, Error::Config(m) if m.contains("core") && m.contains("cap")));
+        assert!(core.inputs().is_empty());
+        assert!(edge.inputs().is_empty());
+
+        fleet.deploy_model(vec![0u8; 500]).await.unwrap();
+        assert_eq!(core.inputs().len(), 1);
+        assert_eq!(edge.inputs().len(), 1);
+    }
+
+    #[tokio::test]
+    async fn test_deploy_preflight_rejects_full_disk_before_transfer() {
+        let full = Arc::new(
+            MockExecutor::new()
+                .on(
+                    "df -P -B1",
+                    "Filesystem 1-blocks Used Available Capacity Mounted on\n\
+             /dev/nvme0n1p1 500000000000 499999999000 1000 100% /mnt/nvme\n",
+                )
+                .on(
+                    "df -P -i",
+                    "Filesystem Inodes IUsed IFree IUse% Mounted on\n\
+             /dev/nvme0n1p1 30000000 1000 29999000 1% /mnt/nvme\n",
+                )
+                .on("stat -c %s", "16000000000\n"),
+        );
+        let roomy = Arc::new(disk_mock());
+        let mut fleet = Fleet::new();
+        for (id, exec) in [("full", &full), ("roomy", &roomy)] {
+            fleet
+                .add_device(
+                    mock::device(id, crate::JetsonModel::OrinNano8GB, exec),
+                    ThermalPolicy::default(),
+                )
+                .unwrap();
+        }
+
+        let err = fleet.deploy_model(vec![0u8; 5000]).await.unwrap_err();
+        assert!(matches!(&err, Error::Storage(m) if m.contains("full")));
+        assert!(full.inputs().is_empty());
+        assert!(roomy.inputs().is_empty());
+
+        let err = fleet
+            .deploy_with_oom_fallback("/mnt/nvme/models/llama.f16.gguf", &oom_config())
+            .await
+            .unwrap_err();
+        assert!(matches!(err, Error::Storage(_)));
+        assert_eq!(full.count("-m '"), 0);
+        assert_eq!(roomy.count("-m '"), 0);
+    }
+
+    #[tokio::test]
+    async fn test_detect_drift_reports_power_mode_packages_and_models() {
+        let yaml = "
+fleet:
+  devices:
+    - id: j1
+      power_profile:
+        preset: balanced
+    - id: j2
+      power_profile:
+        preset: max_performance
+    - id: ghost
+models:
+  - name: llama
+    source: pacha://llama
+    quantization: q4_0
+    devices: j2
+provision:
+  packages: [curl, htop]
+";
+        let config = CoheteConfig::from_yaml(yaml).unwrap();
+        let manifest = serde_json::to_string(&crate::storage::ModelManifest {
+            models: vec![ManifestEntry {
+                name: "llama".to_string(),
+                quant: QuantLevel::Q8_0,
+                file: "llama.q8_0.gguf".to_string(),
+            }],
+        })
+        .unwrap();
+        let j1 = Arc::new(
+            MockExecutor::new()
+                .on("nvpmodel -q", "NV Power Mode: MAXN\n0\n")
+                .on("dpkg-query", "installed curl\nnot-installed htop\n"),
+        );
+        let j2 = Arc::new(
+            MockExecutor::new()
+                .on("nvpmodel -q", "NV Power Mode: MAXN\n0\n")
+                .on("dpkg-query", "installed curl\ninstalled htop\n")
+                .on("manifest.json", &manifest),
+        );
+        let mut fleet = Fleet::new();
+        for (id, exec) in [("j1", &j1), ("j2", &j2)] {
+            fleet
+                .add_device(
+                    mock::device(id, crate::JetsonModel::OrinNano8GB, exec),
+                    ThermalPolicy::default(),
+                )
+                .unwrap();
+        }
+
+        let drift = fleet.detect_drift(&config).await.unwrap();
+        assert_eq!(
+            drift["j1"],
+            [
+                DriftItem::PowerMode {
+                    expected: PowerMode::Power15W,
+                    actual: PowerMode::Maxn,
+                },
+                DriftItem::MissingPackage("htop".to_string()),
+            ]
+        );
+        assert_eq!(
+            drift["j2"],
+            [DriftItem::ModelQuant {
+                model: "llama".to_string(),
+                expected: QuantLevel::Q4_0,
+                actual: QuantLevel::Q8_0,
+            }]
+        );
+        assert_eq!(drift["ghost"], [DriftItem::NotInFleet]);
+        assert_eq!(
+            drift["j1"][0].to_string(),
+            "power mode is MAXN, expected 15W"
+        );
+        // Read-only: nothing was changed on the devices
+        assert_eq!(j1.count("sudo"), 0);
+        assert_eq!(j2.count("sudo"), 0);
+        assert!(j1.calls().iter().all(|c| !c.contains("manifest")));
+    }
+
+    #[tokio::test]
+    async fn test_detect_drift_checks_sysctls_and_manifest_names() {
+        let yaml = "
+fleet:
+  devices:
+    - id: j1
+    - id: j2
+models:
+  - name: chat
+    source: /models/llama-3.f16.gguf
+    devices: all
+provision:
+  sysctls:
+    vm.swappiness: \"10\"
+    net.ipv4.tcp_rmem: \"4096 131072 6291456\"
+    kernel.sched_rt_runtime_us: \"-1\"
+";
+        let config = CoheteConfig::from_yaml(yaml).unwrap();
+        // Deployments are recorded under the source's file stem, not the
+        // config's model name
+        let manifest = serde_json::to_string(&crate::storage::ModelManifest {
+            models: vec![ManifestEntry {
+                name: "llama-3".to_string(),
+                quant: QuantLevel::Q4_0,
+                file: "llama-3.q4_0.gguf".to_string(),
+            }],
+        })
+        .unwrap();
+        let j1 = Arc::new(
+            MockExecutor::new()
+                .on(
+                    "sysctl -e",
+                    "net.ipv4.tcp_rmem = 4096\t131072\t6291456\nvm.swappiness = 60\n",
+                )
+                .on("manifest.json", &manifest),
+        );
+        let j2 = Arc::new(MockExecutor::new().fail("sysctl", 255, "Connection timed out"));
+        let mut fleet = Fleet::new();
+        for (id, exec) in [("j1", &j1), ("j2", &j2)] {
+            fleet
+                .add_device(
+                    mock::device(id, crate::JetsonModel::OrinNano8GB, exec),
+                    ThermalPolicy::default(),
+                )
+                .unwrap();
+        }
+
+        let drift = fleet.detect_drift(&config).await.unwrap();
+        assert_eq!(
+            drift["j1"],
+            [
+                DriftItem::Sysctl {
+                    key: "kernel.sched_rt_runtime_us".to_string(),
+                    expected: "-1".to_string(),
+                    actual: None,
+                },
+                DriftItem::Sysctl {
+                    key: "vm.swappiness".to_string(),
+                    expected: "10".to_string(),
+                    actual: Some("60".to_string()),
+                },
+            ]
+        );
+        assert_eq!(
+            drift["j1"][1].to_string(),
+            "sysctl vm.swappiness is 60, expected 10"
+        );
+        // One unreachable device doesn't hide the others' drift
+        assert!(matches!(
+            &drift["j2"][..],
+            [DriftItem::QueryFailed(e)] if e.contains("Connection timed out")
+        ));
+    }
+
+    #[tokio::test]
+    async fn test_detect_drift_fails_on_invalid_config() {
+        let yaml = "
+fleet:
+  devices:
+    - id: j1
+models:
+  - name: llama
+    source: /models/llama.gguf
+    devices: all
+";
+        let mut config = CoheteConfig::from_yaml(yaml).unwrap();
+        config.models[0].source = "@missing".to_string();
+        let exec = Arc::new(MockExecutor::new());
+        let mut fleet = Fleet::new();
+        fleet
+            .add_device(
+                mock::device("j1", crate::JetsonModel::OrinNano8GB, &exec),
+                ThermalPolicy::default(),
+            )
+            .unwrap();
+
+        let err = fleet.detect_drift(&config).await.unwrap_err();
+        assert!(matches!(err, Error::Config(m) if m.contains("@missing")));
+    }
+
+    #[tokio::test]
+    async fn test_detect_drift_fails_on_unknown_quantization() {
+        let yaml = "
+fleet:
+  devices:
+    - id: j1
+models:
+  - name: llama
+    source: /models/llama.gguf
+    quantization: q3_k
+    devices: all
+";
+        let config = CoheteConfig::from_yaml(yaml).unwrap();
+        // Unreachable, so only config validation can fail the call
+        let exec = Arc::new(MockExecutor::new().fail("manifest", 255, "Connection timed out"));
+        let mut fleet = Fleet::new();
+        fleet
+            .add_device(
+                mock::device("j1", crate::JetsonModel::OrinNano8GB, &exec),
+                ThermalPolicy::default(),
+            )
+            .unwrap();
+
+        let err = fleet.detect_drift(&config).await.unwrap_err();
+        assert!(matches!(err, Error::Config(m) if m.contains("llama") && m.contains("q3_k")));
+    }
+
+    #[tokio::test]
+    async fn test_deploy_model_with_compresses_when_it_saves_bytes() {
+        let exec = Arc::new(disk_mock());
+        let mut fleet = Fleet::new();
+        fleet
+            .add_device(
+                mock::device("j1", crate::JetsonModel::OrinNano8GB, &exec),
+                ThermalPolicy::default(),
+            )
+            .unwrap();
+        let config = DeploymentConfig {
+            compress: true,
+            quantization: None,
+            ..DeploymentConfig::default()
+        };
+
+        // F16-like weights: highly repetitive, so compression pays off
+        let model: Vec<u8> = b"GGUF".iter().copied().cycle().take(1 << 20).collect();
+        let stats = fleet.deploy_model_with(&model, &config).await.unwrap();
+        assert!(stats.compressed());
+        assert!(stats.ratio() > 10.0);
+        assert_eq!(stats.original_bytes, model.len() as u64);
+
+        let (command, data) = exec.inputs().pop().unwrap();
+        assert!(command.contains("zstd -d"));
+        assert_eq!(data.len() as u64, stats.transferred_bytes);
+        assert_eq!(zstd::decode_all(data.as_slice()).unwrap(), model);
+    }
+
+    #[tokio::test]
+    async fn test_deploy_model_with_skips_incompressible_payload() {
+        let exec = Arc::new(disk_mock());
+        let mut fleet = Fleet::new();
+        fleet
+            .add_device(
+                mock::device("j1", crate::JetsonModel::OrinNano8GB, &exec),
+                ThermalPolicy::default(),
+            )
+            .unwrap();
+        let config = DeploymentConfig {
+            compress: true,
+            ..DeploymentConfig::default()
+        };
+
+        // Already compressed data does not shrink again
+        let raw: Vec<u8> = (0..1u32 << 18)
+            .flat_map(|i| i.wrapping_mul(2_654_435_761).to_le_bytes())
+            .collect();
+        let model = zstd::bulk::compress(&raw, 19).unwrap();
+        let stats = fleet.deploy_model_with(&model, &config).await.unwrap();
+        assert!(!stats.compressed());
+        assert!((stats.ratio() - 1.0).abs() < f64::EPSILON);
+
+        let (command, data) = exec.inputs().pop().unwrap();
+        assert!(command.contains("cat >"));
+        assert_eq!(data, model);
+    }
+
+    #[tokio::test]
+    async fn test_deploy_model_compresses_when_fleet_enables_it() {
+        let exec = Arc::new(disk_mock());
+        let mut fleet = Fleet::new().with_compressed_transfers(true);
+        fleet
+            .add_device(
+                mock::device("j1", crate::JetsonModel::OrinNano8GB, &exec),
+                ThermalPolicy::default(),
+            )
+            .unwrap();
+
+        let model = vec![0u8; 1 << 20];
+        fleet.deploy_model(&model).await.unwrap();
+        fleet.deploy_model_thermal_aware(&model).await.unwrap();
+        for (command, data) in exec.inputs() {
+            assert!(command.contains("zstd -d"));
+            assert_eq!(zstd::decode_all(data.as_slice()).unwrap(), model);
+        }
+        assert_eq!(exec.inputs().len(), 2);
+    }
+
+    #[tokio::test]
+    async fn test_deploy_model_with_honors_targets_budget_and_quant() {
+        let j1 = Arc::new(disk_mock());
+        let j2 = Arc::new(disk_mock());
+        let mut fleet = Fleet::new();
+        for (id, exec) in [("j1", &j1), ("j2", &j2)] {
+            fleet
+                .add_device(
+                    mock::device(id, crate::JetsonModel::OrinNano8GB, exec),
+                    ThermalPolicy::default(),
+                )
+                .unwrap();
+        }
+        let config = DeploymentConfig {
+            target_devices: vec!["j2".to_string()],
+            quantization: Some("q8_0".to_string()),
+            ..DeploymentConfig::default()
+        };
+
+        fleet.deploy_model_with(gguf(7), &config).await.unwrap();
+        assert!(j1.inputs().is_empty());
+        assert_eq!(j2.inputs().len(), 1);
+
+        // A Q4_0 file is rejected before any transfer
+        let err = fleet.deploy_model_with(gguf(2), &config).await.unwrap_err();
+        assert!(matches!(err, Error::Quantization(m) if m.contains("q8_0")));
+        let tight = DeploymentConfig {
+            memory_budget_mb: 1,
+            ..config
+        };
+        let err = fleet
+            .deploy_model_with(vec![0u8; 2 << 20], &tight)
+            .await
+            .unwrap_err();
+        assert!(matches!(err, Error::Config(m) if m.contains("memory budget")));
+        assert_eq!(j2.inputs().len(), 1);
+    }
+
+    /// GGUF header with only a `general.file_type` of `ftype`.
+    /// A mock with disk room for any deploy, so storage preflight passes.
+    fn disk_mock() -> MockExecutor {
+        MockExecutor::new()
+            .on(
+                "df -P -B1",
+                "Filesystem 1-blocks Used Available Capacity Mounted on\n\
+                 /dev/nvme0n1p1 500000000000 250000000000 250000000000 50% /mnt/nvme\n",
+            )
+            .on(
+                "df -P -i",
+                "Filesystem Inodes IUsed IFree IUse% Mounted on\n\
+                 /dev/nvme0n1p1 30000000 1000 29999000 1% /mnt/nvme\n",
+            )
+            .on("stat -c %s", "16000000000\n")
+    }
+
+    fn gguf(ftype: u32) -> Vec<u8> {
+        let mut out = b"GGUF".to_vec();
+        out.extend_from_slice(&3u32.to_le_bytes());
+        out.extend_from_slice(&0u64.to_le_bytes());
+        out.extend_from_slice(&1u64.to_le_bytes());
+        let key = b"general.file_type";
+        out.extend_from_slice(&(key.len() as u64).to_le_bytes());
+        out.extend_from_slice(key);
+        out.extend_from_slice(&4u32.to_le_bytes());
+        out.extend_from_slice(&ftype.to_le_bytes());
+        out
+    }
+
+    #[tokio::test]
+    async fn test_verify_quant_reads_deployed_gguf() {
+        let manifest = serde_json::to_string(&crate::storage::ModelManifest {
+            models: vec![ManifestEntry {
+                name: "llama".to_string(),
+                quant: QuantLevel::Q5_0,
+                file: "llama.q5_0.gguf".to_string(),
+            }],
+        })
+        .unwrap();
+        // j2 serves a stale Q4_0 file under the Q5_0 name
+        let j1 = Arc::new(
+            MockExecutor::new()
+                .on("manifest.json", &manifest)
+                .on_bytes("head -c", &gguf(8)),
+        );
+        let j2 = Arc::new(
+            MockExecutor::new()
+                .on("manifest.json", &manifest)
+                .on_bytes("head -c", &gguf(2)),
+        );
+        let mut fleet = Fleet::new();
+        for (id, exec) in [("j1", &j1), ("j2", &j2)] {
+            fleet
+                .add_device(
+                    mock::device(id, crate::JetsonModel::OrinNano8GB, exec),
+                    ThermalPolicy::default(),
+                )
+                .unwrap();
+        }
+
+        assert!(fleet
+            .verify_quant("j1", "llama", QuantLevel::Q5_0)
+            .await
+            .unwrap());
+        assert!(!fleet
+            .verify_quant("j2", "llama", QuantLevel::Q5_0)
+            .await
+            .unwrap());
+        assert!(j1
+            .calls()
+            .iter()
+            .any(|c| c.contains("head -c 1048576 '/mnt/nvme/models/llama.q5_0.gguf'")));
+
+        assert!(matches!(
+            fleet.verify_quant("j1", "mistral", QuantLevel::Q5_0).await,
+            Err(Error::Fleet(_))
+        ));
+        assert!(matches!(
+            fleet.verify_quant("j9", "llama", QuantLevel::Q5_0).await,
+            Err(Error::DeviceNotFound(_))
+        ));
+    }
+
+    fn mdns_member(fleet: &mut Fleet, exec: &Arc<MockExecutor>) {
+        let device = JetsonDevice::new(DeviceInfo {
+            id: "wifi".to_string(),
+            model: crate::JetsonModel::OrinNano8GB,
+            connection: ConnectionMethod::Mdns("jetson.local".to_string()),
+            jetpack_version: None,
+            hostname: None,
+        })
+        .with_executor(exec.clone())
+        .with_resolver(exec.clone());
+        fleet.add_device(device, ThermalPolicy::default()).unwrap();
+    }
+
+    #[tokio::test]
+    async fn test_check_reachable_retries_transient_mdns_failures() {
+        let exec = Arc::new(
+            MockExecutor::new().on_seq("getent hosts", &["", "", "192.168.1.7     jetson.local\n"]),
+        );
+        let mut fleet = Fleet::new().with_mdns_retry(3, Duration::ZERO);
+        mdns_member(&mut fleet, &exec);
+
+        assert!(fleet.check_reachable("wifi").await.unwrap());
+        assert_eq!(exec.count("getent hosts 'jetson.local'"), 3);
+        let member = fleet.get("wifi").unwrap();
+        assert!(member.online);
+        assert_eq!(member.reach_failures, 0);
+    }
+
+    #[tokio::test]
+    async fn test_check_reachable_marks_consistently_absent_offline() {
+        let exec = Arc::new(MockExecutor::new().fail("getent", 2, ""));
+        let mut fleet = Fleet::new().with_mdns_retry(4, Duration::ZERO);
+        mdns_member(&mut fleet, &exec);
+
+        assert!(!fleet.check_reachable("wifi").await.unwrap());
+        let member = fleet.get("wifi").unwrap();
+        assert!(!member.online);
+        assert_eq!(member.reach_failures, 4);
+        // Never got as far as the device itself
+        assert_eq!(exec.count("true"), 0);
+
+        // Failures keep counting across checks until one succeeds
+        assert!(!fleet.check_reachable("wifi").await.unwrap());
+        assert_eq!(fleet.get("wifi").unwrap().reach_failures, 8);
+        assert!(matches!(
+            fleet.check_reachable("nope").await,
+            Err(Error::DeviceNotFound(_))
+        ));
+    }
+
+    #[test]
+    fn test_fleet_from_config_priority() {
+        let yaml = "
+fleet:
+  name: lab
+  devices:
+    - id: canary-01
+      connection: ethernet
+      ip: 192.168.1.50
+      deploy_priority: -10
+    - id: jetson-02
+      connection: usb
+      thermal_policy: aggressive
+";
+        let config = CoheteConfig::from_yaml(yaml).unwrap();
+        let fleet = Fleet::from_config(&config).unwrap();
+
+        let order: Vec<&str> = fleet.deploy_order().iter().map(|m| m.device.id()).collect();
+        assert_eq!(order, vec!["canary-01", "jetson-02"]);
+        assert_eq!(fleet.get("canary-01").unwrap().priority, -10);
+        assert_eq!(fleet.get("jetson-02").unwrap().policy.threshold_c, 75.0);
+    }
+
+    #[test]
+    fn test_fleet_from_config_invalid() {
+        let yaml = "fleet:\n  devices:\n    - id: j1\n      connection: ethernet\n";
+        let config = CoheteConfig::from_yaml(yaml).unwrap();
+        assert!(matches!(Fleet::from_config(&config), Err(Error::Config(_))));
+
+        let yaml = "fleet:\n  devices:\n    - id: j1\n      thermal_policy: reckless\n";
+        let config = CoheteConfig::from_yaml(yaml).unwrap();
+        assert!(matches!(Fleet::from_config(&config), Err(Error::Config(_))));
+
+        let yaml =
+            "fleet:\n  devices:\n    - id: j1\n      power_profile:\n        preset: turbo\n";
+        let config = CoheteConfig::from_yaml(yaml).unwrap();
+        assert!(matches!(Fleet::from_config(&config), Err(Error::Config(m)) if m.contains("j1")));
+    }
+
+    #[test]
+    fn test_fleet_to_config_round_trip() {
+        let yaml = "
+fleet:
+  name: lab
+  devices:
+    - id: canary-01
+      connection: ethernet
+      ip: 192.168.1.50
+      memory_budget_mb: 5000
+      deploy_priority: -10
+      power:
+        type: pdu
+        url: http://pdu.lab
+        outlet: 3
+    - id: jetson-02
+      connection: usb
+      thermal_policy: aggressive
+      power_profile:
+        preset: balanced
+        fan_speed: 75%
+    - id: jetson-03
+      connection: mdns
+      tags:
+        group: edge
+  max_model_size_gb: 20.0
+  groups:
+    edge:
+      max_model_size_gb: 4.0
+thermal:
+  conservative:
+    threshold_c: 60.0
+    cooldown_c: 50.0
+    check_interval_ms: 250
+";
+        let config = CoheteConfig::from_yaml(yaml).unwrap();
+        let fleet = Fleet::from_config(&config).unwrap();
+        let captured = fleet.to_config().unwrap();
+
+        let profile = fleet
+            .get("jetson-02")
+            .unwrap()
+            .power_profile
+            .clone()
+            .unwrap();
+        assert_eq!(profile.mode, PowerMode::Power15W);
+        assert_eq!(profile.fan_speed.to_pwm(), 191);
+        assert_eq!(captured.to_yaml().unwrap(), config.to_yaml().unwrap());
+        let again = Fleet::from_config(&captured).unwrap().to_config().unwrap();
+        assert_eq!(again.to_yaml().unwrap(), captured.to_yaml().unwrap());
+    }
+
+    #[test]
+    fn test_fleet_to_config_unnamed_policy() {
+        let mut fleet = Fleet::new();
+        fleet
+            .add_device(
+                make_test_device("j1", crate::JetsonModel::OrinNano8GB),
+                ThermalPolicy::custom(90.0, 80.0, 100),
+            )
+            .unwrap();
+        assert!(matches!(fleet.to_config(), Err(Error::Config(_))));
+    }
+
+    fn manifest_fleet(levels: &[(&str, Option<&str>)]) -> Fleet {
+        let mut fleet = Fleet::new();
+        for (id, quant) in levels {
+            let exec = quant.map_or_else(MockExecutor::new, |quant| {
+                disk_mock().on(
+                    "manifest.json",
+                    &format!(
+                        r#"{{"models":[{{"name":"llama","quant":"{quant}","file":"llama.{quant}.gguf"}}]}}"#
+                    ),
+                )
+            });
+            let device = mock::device(id, crate::JetsonModel::OrinNano8GB, &Arc::new(exec));
+            fleet.add_device(device, ThermalPolicy::default()).unwrap();
+        }
+        fleet
+    }
+
+    #[tokio::test]
+    async fn test_quant_consistency_flags_drift() {
+        let fleet = manifest_fleet(&[
+            ("j1", Some("q4_0")),
+            ("j2", Some("q4_0")),
+            ("j3", Some("q5_1")),
+            ("j4", None),
+        ]);
+        let report = fleet.quant_consistency("llama").await.unwrap();
+
+        assert!(!report.is_consistent());
+        assert_eq!(
+            report.distinct_levels(),
+            vec![QuantLevel::Q4_0, QuantLevel::Q5_1]
+        );
+        assert_eq!(
+            report.devices_at(QuantLevel::Q4_0).collect::<Vec<_>>(),
+            vec!["j1", "j2"]
+        );
+        assert_eq!(
+            report.devices_at(QuantLevel::Q5_1).collect::<Vec<_>>(),
+            vec!["j3"]
+        );
+        assert_eq!(report.missing, vec!["j4"]);
+    }
+
+    #[tokio::test]
+    async fn test_quant_consistency_uniform_or_allowed() {
+        let fleet = manifest_fleet(&[("j1", Some("q4_0")), ("j2", Some("q4_0"))]);
+        assert!(fleet
+            .quant_consistency("llama")
+            .await
+            .unwrap()
+            .is_consistent());
+
+        let mut fleet = manifest_fleet(&[("j1", Some("q4_0")), ("j2", Some("q8_0"))]);
+        fleet.allow_heterogeneous_quant("llama");
+        let report = fleet.quant_consistency("llama").await.unwrap();
+        assert!(report.heterogeneous_allowed);
+        assert!(report.is_consistent());
+    }
+
+    #[tokio::test]
+    async fn test_deploy_oom_fallback_records_manifest() {
+        let exec = Arc::new(disk_mock().fail("llama-cli -m '/mnt/nvme/models/llama.q8_0", 137, ""));
+        let mut fleet = Fleet::new();
+        fleet
+            .add_device(
+                mock::device("j1", crate::JetsonModel::OrinNano8GB, &exec),
+                ThermalPolicy::conservative(),
+            )
+            .unwrap();
+
+        fleet
+            .deploy_with_oom_fallback("/mnt/nvme/models/llama.f16.gguf", &oom_config())
+            .await
+            .unwrap();
+
+        let (command, manifest) = exec.inputs().pop().unwrap();
+        assert!(command.contains("manifest.json"));
+        let manifest: crate::storage::ModelManifest = serde_json::from_slice(&manifest).unwrap();
+        let entry = manifest.get("llama").unwrap();
+        assert_eq!(entry.quant, QuantLevel::Q5_1);
+        assert_eq!(entry.file, "llama.q5_1.gguf");
+    }
+
+    #[tokio::test]
+    async fn test_hard_reboot_falls_back_to_power_cycle() {
+        let hung = Arc::new(MockExecutor::new().fail("reboot", 255, "Connection timed out"));
+        let healthy = Arc::new(MockExecutor::new());
+        let pdu = Arc::new(MockExecutor::new());
+        let switch = ConfiguredPowerSwitch::new()
+            .with_control(
+                "j1",
+                crate::config::PowerControlConfig::Pdu {
+                    url: "http://pdu.lab".to_string(),
+                    outlet: 2,
+                },
+            )
+            .with_local_executor(pdu.clone())
+            .with_off_time(std::time::Duration::ZERO);
+        let mut fleet = Fleet::new().with_power_switch(Arc::new(switch));
+        fleet
+            .add_device(
+                mock::device("j1", crate::JetsonModel::OrinNano8GB, &hung),
+                ThermalPolicy::default(),
+            )
+            .unwrap();
+        fleet
+            .add_device(
+                mock::device("j2", crate::JetsonModel::OrinNano8GB, &healthy),
+                ThermalPolicy::default(),
+            )
+            .unwrap();
+
+        assert_eq!(fleet.hard_reboot("j2").await.unwrap(), RebootMethod::Soft);
+        assert!(pdu.calls().is_empty());
+
+        assert_eq!(
+            fleet.hard_reboot("j1").await.unwrap(),
+            RebootMethod::PowerCycle
+        );
+        assert_eq!(
+            pdu.calls(),
+            vec![
+                "curl -sf -X POST 'http://pdu.lab/outlets/2/off'",
+                "curl -sf -X POST 'http://pdu.lab/outlets/2/on'",
+            ]
+        );
+        assert!(matches!(
+            fleet.hard_reboot("j9").await,
+            Err(Error::DeviceNotFound(_))
+        ));
+    }
+
+    #[tokio::test]
+    async fn test_hard_reboot_without_switch() {
+        let hung = Arc::new(MockExecutor::new().fail("reboot", 255, "Connection timed out"));
+        let mut fleet = Fleet::new();
+        fleet
+            .add_device(
+                mock::device("j1", crate::JetsonModel::OrinNano8GB, &hung),
+                ThermalPolicy::default(),
+            )
+            .unwrap();
+        assert!(matches!(
+            fleet.hard_reboot("j1").await,
+            Err(Error::CommandFailed { .. })
+        ));
+    }
+
+    #[test]
+    fn test_merge_discovered() {
+        let mut fleet = Fleet::new();
+        fleet
+            .add_device_with_priority(
+                make_test_device("j1", crate::JetsonModel::OrinNano8GB),
+                ThermalPolicy::aggressive(),
+                -1,
+            )
+            .unwrap();
+        fleet
+            .add_device(
+                make_test_device("j2", crate::JetsonModel::OrinNano8GB),
+                ThermalPolicy::conservative(),
+            )
+            .unwrap();
+        fleet.devices.get_mut("j1").unwrap().enabled = false;
+
+        let report = fleet.merge_discovered(vec![
+            make_test_device("j1", crate::JetsonModel::OrinNano8GB),
+            make_test_device("j3", crate::JetsonModel::OrinNX16GB),
+        ]);
+
+        assert_eq!(report.added, vec!["j3"]);
+        assert_eq!(report.retained, vec!["j1"]);
+        assert_eq!(report.went_offline, vec!["j2"]);
+        assert!(report.changed());
+        assert_eq!(fleet.len(), 3);
+
+        let j1 = fleet.get("j1").unwrap();
+        assert!(!j1.enabled);
+        assert_eq!(j1.priority, -1);
+        assert_eq!(j1.policy.threshold_c, 75.0);
+
+        let j2 = fleet.get("j2").unwrap();
+        assert!(!j2.online);
+        assert!(!j2.is_available());
+        let order: Vec<&str> = fleet.deploy_order().iter().map(|m| m.device.id()).collect();
+        assert_eq!(order, vec!["j3"]);
+        assert_eq!(fleet.health_status().offline_devices, 2);
+
+        let report = fleet.merge_discovered(vec![
+            make_test_device("j1", crate::JetsonModel::OrinNano8GB),
+            make_test_device("j2", crate::JetsonModel::OrinNano8GB),
+            make_test_device("j3", crate::JetsonModel::OrinNX16GB),
+        ]);
+        assert_eq!(report.returned, vec!["j2"]);
+        assert!(report.went_offline.is_empty());
+        assert!(fleet.get("j2").unwrap().is_available());
     }
 
-    #[test]
-    fn test_fleet_add_device() {
+    #[tokio::test]
+    async fn test_thermal_timeseries() {
+        use futures_util::StreamExt;
+
         let mut fleet = Fleet::new();
-        let device = make_test_device("jetson-01", crate::JetsonModel::OrinNano8GB);
-        fleet.add_device(device, ThermalPolicy::conservative()).unwrap();
-        assert_eq!(fleet.len(), 1);
-        assert!(!fleet.is_empty());
-        assert_eq!(fleet.enabled_count(), 1);
+        for (id, temps) in [
+            ("j1", ["GPU@40C", "GPU@45C", "GPU@50C"]),
+            ("j2", ["GPU@60C", "GPU@62C", "GPU@64C"]),
+        ] {
+            let exec = Arc::new(MockExecutor::new().on_seq("tegrastats", &temps));
+            fleet
+                .add_device(
+                    mock::device(id, crate::JetsonModel::OrinNano8GB, &exec),
+                    ThermalPolicy::default(),
+                )
+                .unwrap();
+        }
+        // Disabled devices are not sampled
+        let idle = Arc::new(MockExecutor::new().on("tegrastats", "GPU@30C"));
+        fleet
+            .add_device(
+                mock::device("j3", crate::JetsonModel::OrinNano8GB, &idle),
+                ThermalPolicy::default(),
+            )
+            .unwrap();
+        fleet.devices.get_mut("j3").unwrap().enabled = false;
+
+        let points: Vec<HashMap<String, f32>> = fleet
+            .thermal_timeseries(Duration::from_millis(20), Duration::from_millis(10))
+            .collect()
+            .await;
+
+        assert_eq!(points.len(), 3);
+        for point in &points {
+            let mut ids: Vec<&str> = point.keys().map(String::as_str).collect();
+            ids.sort_unstable();
+            assert_eq!(ids, vec!["j1", "j2"]);
+        }
+        assert_eq!(points[2]["j1"], 50.0);
+        assert_eq!(points[2]["j2"], 64.0);
+        assert_eq!(idle.count("tegrastats"), 0);
     }
 
-    #[test]
-    fn test_fleet_remove_device() {
+    #[tokio::test]
+    async fn test_thermal_timeseries_device_drops_out() {
+        use futures_util::StreamExt;
+
+        let flaky = Arc::new(MockExecutor::new().on_seq("tegrastats", &["GPU@40C", "", "GPU@42C"]));
         let mut fleet = Fleet::new();
-        let device = make_test_device("jetson-01", crate::JetsonModel::OrinNano8GB);
-        fleet.add_device(device, ThermalPolicy::conservative()).unwrap();
-        assert_eq!(fleet.len(), 1);
+        fleet
+            .add_device(
+                mock::device("j1", crate::JetsonModel::OrinNano8GB, &flaky),
+                ThermalPolicy::default(),
+            )
+            .unwrap();
 
-        let removed = fleet.remove_device("jetson-01");
-        assert!(removed.is_some());
-        assert!(fleet.is_empty());
+        let points: Vec<HashMap<String, f32>> = fleet
+            .thermal_timeseries(Duration::from_millis(2), Duration::from_millis(1))
+            .collect()
+            .await;
 
-        let not_found = fleet.remove_device("nonexistent");
-        assert!(not_found.is_none());
+        assert_eq!(points.len(), 3);
+        assert!(points[0].contains_key("j1"));
+        assert!(points[1].is_empty());
+        assert_eq!(points[2]["j1"], 42.0);
     }
 
-    #[test]
-    fn test_fleet_get_device() {
+    #[tokio::test]
+    async fn test_cordoned_device_skips_scheduling_but_is_monitored() {
         let mut fleet = Fleet::new();
-        let device = make_test_device("jetson-02", crate::JetsonModel::OrinNX16GB);
-        fleet.add_device(device, ThermalPolicy::aggressive()).unwrap();
+        let mut executors = Vec::new();
+        for (id, priority) in [("j1", 0), ("j2", 1)] {
+            let exec = Arc::new(disk_mock().on("tegrastats", COOL));
+            fleet
+                .add_device_with_priority(
+                    mock::device(id, crate::JetsonModel::OrinNano8GB, &exec),
+                    ThermalPolicy::default(),
+                    priority,
+                )
+                .unwrap();
+            executors.push(exec);
+        }
 
-        let member = fleet.get("jetson-02");
-        assert!(member.is_some());
-        assert!(member.unwrap().enabled);
+        fleet.cordon("j1").unwrap();
+        assert!(fleet.get("j1").unwrap().cordoned);
+        assert_eq!(fleet.select_device().unwrap().device.id(), "j2");
 
-        let not_found = fleet.get("nonexistent");
-        assert!(not_found.is_none());
+        fleet.deploy_model(b"GGUF").await.unwrap();
+        assert!(executors[0].inputs().is_empty());
+        assert_eq!(executors[1].inputs().len(), 1);
+
+        // Still monitored and counted healthy
+        assert!(fleet.sample_all().await["j1"].is_ok());
+        assert_eq!(fleet.health_status().healthy_devices, 2);
+
+        fleet.uncordon("j1").unwrap();
+        assert_eq!(fleet.select_device().unwrap().device.id(), "j1");
+        assert!(matches!(fleet.cordon("j9"), Err(Error::DeviceNotFound(_))));
     }
 
-    #[test]
-    fn test_fleet_devices_iterator() {
+    #[tokio::test]
+    async fn test_sample_all_samples_devices_concurrently() {
+        let latency = Duration::from_millis(200);
         let mut fleet = Fleet::new();
-        fleet.add_device(
-            make_test_device("j1", crate::JetsonModel::OrinNano4GB),
-            ThermalPolicy::conservative()
-        ).unwrap();
-        fleet.add_device(
-            make_test_device("j2", crate::JetsonModel::OrinNano8GB),
-            ThermalPolicy::aggressive()
-        ).unwrap();
+        for id in ["j1", "j2", "j3"] {
+            let exec = Arc::new(
+                MockExecutor::new()
+                    .on("tegrastats", COOL)
+                    .with_latency(latency),
+            );
+            fleet
+                .add_device(
+                    mock::device(id, crate::JetsonModel::OrinNano8GB, &exec),
+                    ThermalPolicy::default(),
+                )
+                .unwrap();
+        }
 
-        let count = fleet.devices().count();
-        assert_eq!(count, 2);
+        let started = std::time::Instant::now();
+        let samples = fleet.sample_all().await;
+        assert_eq!(samples.len(), 3);
+        assert!(samples.values().all(Result::is_ok));
+        assert!(started.elapsed() < latency * 2);
     }
 
-    #[test]
-    fn test_fleet_health() {
-        let fleet = Fleet::new();
-        let health = fleet.health_status();
-        assert_eq!(health.total_devices, 0);
-        assert_eq!(health.enabled_devices, 0);
-        assert_eq!(health.healthy_devices, 0);
-        assert_eq!(health.degraded_devices, 0);
-        assert_eq!(health.offline_devices, 0);
-        assert_eq!(health.health_percent(), 100.0);
+    #[tokio::test]
+    async fn test_effective_capacity_weights_throttled_and_offline() {
+        let mut fleet = Fleet::new();
+        for (id, stats) in [("cool", COOL), ("hot", HOT), ("gone", COOL)] {
+            let exec = Arc::new(MockExecutor::new().on("tegrastats", stats));
+            fleet
+                .add_device(
+                    mock::device(id, crate::JetsonModel::OrinNano8GB, &exec),
+                    ThermalPolicy::conservative(),
+                )
+                .unwrap();
+        }
+        fleet.devices.get_mut("gone").unwrap().online = false;
+
+        let capacity = fleet.effective_capacity().await.unwrap();
+        assert_eq!(capacity.nominal_tops, 120);
+        assert_eq!(capacity.factors["cool"], 1.0);
+        // 68C is 3C past the 65C threshold of a 10C band
+        assert!((capacity.factors["hot"] - 0.85).abs() < 0.001);
+        assert_eq!(capacity.factors["gone"], 0.0);
+        assert!((capacity.effective_tops - 74.0).abs() < 0.01);
+        assert_eq!(capacity.effective_memory_mb, 2 * 8192);
+        assert!(capacity.ratio() < 1.0);
+    }
+
+    #[tokio::test]
+    async fn test_effective_capacity_unreachable() {
+        let exec = Arc::new(MockExecutor::new().fail("tegrastats", 255, "no route"));
+        let mut fleet = Fleet::new();
+        fleet
+            .add_device(
+                mock::device("j1", crate::JetsonModel::OrinNano8GB, &exec),
+                ThermalPolicy::default(),
+            )
+            .unwrap();
+        assert!(fleet.effective_capacity().await.is_err());
     }
 
     #[test]
-    fn test_fleet_health_with_devices() {
+    fn test_select_with_affinity_is_sticky() {
         let mut fleet = Fleet::new();
-        fleet.add_device(
-            make_test_device("j1", crate::JetsonModel::OrinNano8GB),
-            ThermalPolicy::conservative()
-        ).unwrap();
-        fleet.add_device(
-            make_test_device("j2", crate::JetsonModel::OrinNano8GB),
-            ThermalPolicy::conservative()
-        ).unwrap();
+        for id in ["j1", "j2", "j3"] {
+            fleet
+                .add_device(
+                    make_test_device(id, crate::JetsonModel::OrinNano8GB),
+                    ThermalPolicy::default(),
+                )
+                .unwrap();
+        }
 
-        let health = fleet.health_status();
-        assert_eq!(health.total_devices, 2);
-        assert_eq!(health.enabled_devices, 2);
-        assert_eq!(health.healthy_devices, 2);
-        assert_eq!(health.health_percent(), 100.0);
+        let pick = |fleet: &Fleet, key| {
+            fleet
+                .select_with_affinity(key, SelectionStrategy::FewestSessions)
+                .map(|m| m.device.id().to_string())
+                .unwrap()
+        };
+        let alice = pick(&fleet, "alice");
+        let bob = pick(&fleet, "bob");
+        assert_ne!(alice, bob);
+        for _ in 0..3 {
+            assert_eq!(pick(&fleet, "alice"), alice);
+        }
+
+        // Draining keeps existing sessions in place
+        fleet.cordon(&alice).unwrap();
+        assert_eq!(pick(&fleet, "alice"), alice);
+
+        // Disabling re-pins onto another device, which then sticks
+        fleet.devices.get_mut(&alice).unwrap().enabled = false;
+        let repinned = pick(&fleet, "alice");
+        assert_ne!(repinned, alice);
+        assert_eq!(pick(&fleet, "alice"), repinned);
+
+        fleet.release_session("alice");
+        assert_eq!(fleet.sessions.lock().unwrap().len(), 1);
     }
 
     #[test]
-    fn test_fleet_health_clone() {
-        let health = FleetHealth {
-            total_devices: 5,
-            enabled_devices: 4,
-            healthy_devices: 3,
-            degraded_devices: 1,
-            offline_devices: 1,
+    fn test_select_with_affinity_strategies() {
+        let mut fleet = Fleet::new();
+        assert!(fleet
+            .select_with_affinity("s", SelectionStrategy::Hashed)
+            .is_none());
+        for (id, priority) in [("j1", 5), ("j2", 1)] {
+            fleet
+                .add_device_with_priority(
+                    make_test_device(id, crate::JetsonModel::OrinNano8GB),
+                    ThermalPolicy::default(),
+                    priority,
+                )
+                .unwrap();
+        }
+        let first = fleet
+            .select_with_affinity("s1", SelectionStrategy::RolloutOrder)
+            .unwrap();
+        assert_eq!(first.device.id(), "j2");
+
+        let hashed = fleet
+            .select_with_affinity("s2", SelectionStrategy::Hashed)
+            .unwrap()
+            .device
+            .id()
+            .to_string();
+        fleet.release_session("s2");
+        let again = fleet
+            .select_with_affinity("s2", SelectionStrategy::Hashed)
+            .unwrap();
+        assert_eq!(again.device.id(), hashed);
+    }
+
+    #[tokio::test]
+    async fn test_promote_standby_ensures_model() {
+        let primary = Arc::new(disk_mock());
+        let spare = Arc::new(disk_mock());
+        let mut fleet = Fleet::new();
+        for (id, exec) in [("j1", &primary), ("j2", &spare)] {
+            fleet
+                .add_device(
+                    mock::device(id, crate::JetsonModel::OrinNano8GB, exec),
+                    ThermalPolicy::conservative(),
+                )
+                .unwrap();
+        }
+        fleet.set_tag("j2", ROLE_TAG, STANDBY_ROLE).unwrap();
+        fleet.cordon("j2").unwrap();
+
+        fleet
+            .deploy_with_oom_fallback("/mnt/nvme/models/llama.f16.gguf", &oom_config())
+            .await
+            .unwrap();
+        assert_eq!(spare.count("llama-quantize"), 0);
+
+        // Primary fails
+        fleet.devices.get_mut("j1").unwrap().online = false;
+        let promoted = fleet.promote_standby("j1").await.unwrap();
+        assert_eq!(promoted.as_deref(), Some("j2"));
+
+        assert_eq!(
+            spare.count(
+                "llama-quantize '/mnt/nvme/models/llama.f16.gguf' '/mnt/nvme/models/llama.q8_0.gguf' Q8_0"
+            ),
+            1
+        );
+        let (_, manifest) = spare.inputs().pop().unwrap();
+        let manifest: crate::storage::ModelManifest = serde_json::from_slice(&manifest).unwrap();
+        assert_eq!(manifest.get("llama").unwrap().quant, QuantLevel::Q8_0);
+
+        let j2 = fleet.get("j2").unwrap();
+        assert!(j2.is_schedulable());
+        assert!(!j2.is_standby());
+        assert!(fleet.get("j1").unwrap().cordoned);
+        assert_eq!(fleet.select_device().unwrap().device.id(), "j2");
+    }
+
+    #[tokio::test]
+    async fn test_record_deployment_keeps_one_entry_per_model_name() {
+        let exec = Arc::new(MockExecutor::new());
+        let mut fleet = Fleet::new();
+        fleet
+            .add_device(
+                mock::device("j1", crate::JetsonModel::OrinNano8GB, &exec),
+                ThermalPolicy::default(),
+            )
+            .unwrap();
+        let device = &fleet.get("j1").unwrap().device;
+
+        // Two sources with the same stem share one manifest entry
+        for source in ["/mnt/nvme/models/llama.f16.gguf", "/data/llama.gguf"] {
+            fleet
+                .record_deployment(device, Path::new(source), QuantLevel::Q4_0)
+                .await
+                .unwrap();
+        }
+        assert_eq!(
+            fleet.deployed.lock().unwrap()["j1"],
+            [DeployedModel {
+                source: PathBuf::from("/data/llama.gguf"),
+                level: QuantLevel::Q4_0,
+            }]
+        );
+    }
+
+    #[tokio::test]
+    async fn test_promote_standby_skips_present_model_and_none_left() {
+        let manifest = r#"{"models":[{"name":"llama","quant":"q8_0","file":"llama.q8_0.gguf"}]}"#;
+        let primary = Arc::new(disk_mock());
+        let spare = Arc::new(disk_mock().on("manifest.json", manifest));
+        let mut fleet = Fleet::new();
+        for (id, exec) in [("j1", &primary), ("j2", &spare)] {
+            fleet
+                .add_device(
+                    mock::device(id, crate::JetsonModel::OrinNano8GB, exec),
+                    ThermalPolicy::conservative(),
+                )
+                .unwrap();
+        }
+        fleet.set_tag("j2", ROLE_TAG, STANDBY_ROLE).unwrap();
+        fleet.cordon("j2").unwrap();
+        fleet
+            .deploy_with_oom_fallback("/mnt/nvme/models/llama.f16.gguf", &oom_config())
+            .await
+            .unwrap();
+
+        let promoted = fleet.promote_standby("j1").await.unwrap();
+        assert_eq!(promoted.as_deref(), Some("j2"));
+        assert_eq!(spare.count("llama-quantize"), 0);
+
+        assert_eq!(fleet.promote_standby("j2").await.unwrap(), None);
+        assert!(matches!(
+            fleet.promote_standby("j9").await,
+            Err(Error::DeviceNotFound(_))
+        ));
+    }
+
+    #[tokio::test]
+    async fn test_apply_power_profiles_verified_reports_refused_mode() {
+        const LOCKED: &str = "1020000000\n\x1e1020000000\n\x1e";
+        let readback = |mode: &str| {
+            Arc::new(
+                MockExecutor::new()
+                    .on("nvpmodel -q", mode)
+                    .on("devfreq", LOCKED)
+                    .on("cat /sys/devices/platform/pwm-fan", "255\n"),
+            )
         };
-        let cloned = health.clone();
-        assert_eq!(cloned.total_devices, 5);
-        assert!((cloned.health_percent() - 60.0).abs() < 0.001);
+        let execs = [
+            ("j1", readback("NV Power Mode: MAXN\n0\n")),
+            ("j2", readback("NV Power Mode: MAXN\n0\n")),
+            // nvpmodel accepted the command but stayed in 15W
+            ("j3", readback("NV Power Mode: 15W\n1\n")),
+            ("j4", readback("NV Power Mode: 15W\n1\n")),
+        ];
+        let mut fleet = Fleet::new();
+        for (id, exec) in &execs {
+            fleet
+                .add_device(
+                    mock::device(id, crate::JetsonModel::OrinNano8GB, exec),
+                    ThermalPolicy::default(),
+                )
+                .unwrap();
+        }
+        for id in ["j1", "j2", "j3"] {
+            fleet
+                .set_power_profile(id, PowerProfile::max_performance())
+                .unwrap();
+        }
+
+        let results = fleet.apply_power_profiles_verified().await;
+        assert_eq!(results.len(), 3);
+        for id in ["j1", "j2"] {
+            let applied = results[id].as_ref().unwrap();
+            assert_eq!(applied.mode, PowerMode::Maxn);
+            assert!(applied.clocks_locked);
+            assert_eq!(applied.fan_pwm, 255);
+        }
+        match &results["j3"] {
+            Err(Error::PowerMode(message)) => {
+                assert!(message.contains("mode 15W (expected MAXN)"), "{message}");
+            }
+            other => panic!("unexpected result: {other:?}"),
+        }
+        assert_eq!(execs[2].1.count("sudo nvpmodel -m 0"), 1);
+        assert_eq!(execs[2].1.count("sudo jetson_clocks"), 1);
+        assert!(execs[3].1.calls().is_empty());
+
+        let results = fleet.apply_power_profiles().await;
+        assert!(results.values().all(Result::is_ok));
+        assert!(matches!(
+            fleet.set_power_profile("j9", PowerProfile::balanced()),
+            Err(Error::DeviceNotFound(_))
+        ));
     }
 
-    #[test]
-    fn test_deployment_config() {
-        let config = DeploymentConfig::default();
-        assert_eq!(config.quantization, Some("q4_0".to_string()));
-        assert_eq!(config.memory_budget_mb, 6000);
-        assert!(config.target_devices.is_empty());
+    #[tokio::test]
+    async fn test_deploy_model_auto_quant_selects_per_device() {
+        let mut fleet = Fleet::new();
+        let mut execs = Vec::new();
+        for (id, budget_mb) in [("small", 3000), ("large", 6000), ("tiny", 1000)] {
+            let exec = Arc::new(disk_mock());
+            fleet
+                .add_device(
+                    mock::device(id, crate::JetsonModel::OrinNano8GB, &exec),
+                    ThermalPolicy::default(),
+                )
+                .unwrap();
+            fleet.devices.get_mut(id).unwrap().memory_budget_mb = budget_mb;
+            execs.push(exec);
+        }
+        let dir = std::env::temp_dir().join(format!("cohete-auto-quant-{}", std::process::id()));
+        std::fs::create_dir_all(&dir).unwrap();
+        let mut quantized = HashMap::new();
+        for level in [QuantLevel::Q4_0, QuantLevel::Q8_0] {
+            let path = dir.join(format!("llama.{level}.gguf"));
+            let mut data = b"GGUF".to_vec();
+            data.extend_from_slice(&3u32.to_le_bytes());
+            data.extend_from_slice(level.as_str().as_bytes());
+            std::fs::write(&path, data).unwrap();
+            quantized.insert(level, path);
+        }
+
+        let source = Path::new("/models/llama.f16.gguf");
+        let report = fleet
+            .deploy_model_auto_quant(source, &quantized, 8000)
+            .await;
+        std::fs::remove_dir_all(&dir).unwrap();
+
+        let uploaded = |exec: &MockExecutor| {
+            exec.inputs()
+                .into_iter()
+                .find(|(command, _)| command.contains("llama."))
+                .map(|(command, data)| (command, String::from_utf8(data).unwrap()))
+        };
+        let (command, data) = uploaded(&execs[0]).unwrap();
+        assert!(command.contains("/mnt/nvme/models/llama.q4_0.gguf"));
+        assert!(data.ends_with("q4_0"));
+        assert!(uploaded(&execs[1]).unwrap().1.ends_with("q8_0"));
+        assert!(execs.iter().all(|e| e.count("llama-quantize") == 0));
+
+        let (failed, err) = report.failed.unwrap();
+        assert_eq!(failed, "tiny");
+        assert!(matches!(err, Error::InsufficientMemory { .. }));
+        let state = fleet.to_state();
+        assert_eq!(
+            state.devices["large"].models["llama"].quant,
+            QuantLevel::Q8_0
+        );
     }
 
-    #[test]
-    fn test_deployment_config_clone() {
-        let config = DeploymentConfig {
-            target_devices: vec!["j1".to_string(), "j2".to_string()],
-            quantization: Some("q8_0".to_string()),
-            memory_budget_mb: 4000,
-            thermal_policy: ThermalPolicy::aggressive(),
+    #[tokio::test]
+    async fn test_deploy_model_auto_quant_halts_on_unreadable_level() {
+        let mut fleet = Fleet::new();
+        let mut execs = Vec::new();
+        for (id, budget_mb) in [("large", 6000), ("small", 3000)] {
+            let exec = Arc::new(disk_mock());
+            fleet
+                .add_device(
+                    mock::device(id, crate::JetsonModel::OrinNano8GB, &exec),
+                    ThermalPolicy::default(),
+                )
+                .unwrap();
+            fleet.devices.get_mut(id).unwrap().memory_budget_mb = budget_mb;
+            execs.push(exec);
+        }
+        let dir =
+            std::env::temp_dir().join(format!("cohete-auto-quant-bad-{}", std::process::id()));
+        std::fs::create_dir_all(&dir).unwrap();
+        let mut quantized = HashMap::new();
+        for (level, magic) in [(QuantLevel::Q4_0, b"GGUF"), (QuantLevel::Q8_0, b"NOPE")] {
+            let path = dir.join(format!("llama.{level}.gguf"));
+            let mut data = magic.to_vec();
+            data.extend_from_slice(&3u32.to_le_bytes());
+            std::fs::write(&path, data).unwrap();
+            quantized.insert(level, path);
+        }
+
+        let report = fleet
+            .deploy_model_auto_quant(Path::new("/models/llama.f16.gguf"), &quantized, 8000)
+            .await;
+        std::fs::remove_dir_all(&dir).unwrap();
+
+        assert!(report.completed.is_empty());
+        let (failed, err) = report.failed.unwrap();
+        assert_eq!(failed, "large");
+        assert!(matches!(err, Error::Quantization(_)));
+        assert_eq!(report.skipped, vec!["small".to_string()]);
+        assert!(execs.iter().all(|e| e.inputs().is_empty()));
+    }
+
+    #[tokio::test]
+    async fn test_power_governor_steps_down_and_restores() {
+        let draw = |mw: &[u32]| -> Vec<String> {
+            mw.iter()
+                .map(|mw| format!("RAM 2048/7620MB CPU [50%@1510] GPU@50C VDD_IN {mw}mW/{mw}mW"))
+                .collect()
         };
-        let cloned = config.clone();
-        assert_eq!(cloned.target_devices.len(), 2);
-        assert_eq!(cloned.quantization, Some("q8_0".to_string()));
+        let mut fleet = Fleet::new();
+        let mut execs = Vec::new();
+        for (id, mw) in [
+            ("j1", [14000, 11000, 9000, 6000, 6000, 6000]),
+            ("j2", [12000, 12000, 9000, 5000, 8000, 8000]),
+        ] {
+            let lines = draw(&mw);
+            let lines: Vec<&str> = lines.iter().map(String::as_str).collect();
+            let exec = Arc::new(
+                MockExecutor::new()
+                    .on_seq("tegrastats", &lines)
+                    .on("nvpmodel -q", "NV Power Mode: MAXN\n0\n"),
+            );
+            fleet
+                .add_device(
+                    mock::device(id, crate::JetsonModel::AgxOrin32GB, &exec),
+                    ThermalPolicy::default(),
+                )
+                .unwrap();
+            execs.push(exec);
+        }
+
+        let mut actions = Vec::new();
+        for _ in 0..6 {
+            actions.push(fleet.govern_power(20.0).await.unwrap().action);
+        }
+        let step = |down: bool, id: &str, from, to| {
+            let device_id = id.to_string();
+            if down {
+                GovernorAction::StepDown {
+                    device_id,
+                    from,
+                    to,
+                }
+            } else {
+                GovernorAction::StepUp {
+                    device_id,
+                    from,
+                    to,
+                }
+            }
+        };
+        assert_eq!(
+            actions,
+            [
+                // 26W: highest draw first
+                step(true, "j1", PowerMode::Maxn, PowerMode::Power15W),
+                // 23W: j2 now draws most
+                step(true, "j2", PowerMode::Maxn, PowerMode::Power15W),
+                // 18W: under the cap but above the restore point
+                GovernorAction::Hold,
+                // 11W: lowest draw restored first
+                step(false, "j2", PowerMode::Power15W, PowerMode::Maxn),
+                step(false, "j1", PowerMode::Power15W, PowerMode::Maxn),
+                GovernorAction::Hold,
+            ]
+        );
+        assert_eq!(execs[0].count("sudo nvpmodel -m 1"), 1);
+        assert_eq!(execs[0].count("sudo nvpmodel -m 0"), 1);
     }
 
-    #[test]
-    fn test_fleet_member_fields() {
+    #[tokio::test]
+    async fn test_power_governor_holds_at_lowest_mode() {
+        let exec = Arc::new(
+            MockExecutor::new()
+                .on("tegrastats", HOT)
+                .on("nvpmodel -q", "NV Power Mode: 7W\n2\n"),
+        );
         let mut fleet = Fleet::new();
-        let device = make_test_device("j1", crate::JetsonModel::AgxOrin64GB);
-        fleet.add_device(device, ThermalPolicy::aggressive()).unwrap();
+        fleet
+            .add_device(
+                mock::device("j1", crate::JetsonModel::OrinNano8GB, &exec),
+                ThermalPolicy::default(),
+            )
+            .unwrap();
+        let step = fleet.govern_power(5.0).await.unwrap();
+        assert!((step.total_watts - 14.0).abs() < 0.001);
+        assert_eq!(step.action, GovernorAction::Hold);
+        assert_eq!(exec.count("sudo nvpmodel"), 0);
+    }
 
-        let member = fleet.get("j1").unwrap();
-        assert!(member.enabled);
-        assert_eq!(member.policy.threshold_c, 75.0);
-        assert_eq!(member.device.id(), "j1");
+    #[tokio::test]
+    async fn test_support_bundle_all() {
+        let mut fleet = Fleet::new();
+        for id in ["j1", "j2", "j3"] {
+            let exec = Arc::new(MockExecutor::new());
+            fleet
+                .add_device(
+                    mock::device(id, crate::JetsonModel::OrinNano8GB, &exec),
+                    ThermalPolicy::default(),
+                )
+                .unwrap();
+        }
+        fleet.devices.get_mut("j3").unwrap().online = false;
+        let out_dir =
+            std::env::temp_dir().join(format!("cohete-fleet-bundle-{}", std::process::id()));
+
+        let bundles = fleet.support_bundle_all(&out_dir).await;
+        assert_eq!(bundles.len(), 2);
+        assert_eq!(
+            bundles["j2"].as_ref().unwrap(),
+            &out_dir.join("j2-support.tar")
+        );
+        assert!(out_dir.join("j1-support.tar").exists());
+        std::fs::remove_dir_all(&out_dir).unwrap();
     }
 
     #[tokio::test]
-    async fn test_fleet_deploy_model() {
-        let fleet = Fleet::new();
-        let result = fleet.deploy_model(&[1, 2, 3]).await;
-        assert!(result.is_ok());
+    async fn test_retry_budget_bounds_fleet_retries() {
+        let mut fleet = Fleet::new().with_retry_budget(
+            RetryBudget::new(2, Duration::from_secs(3600)).with_backoff(Duration::ZERO),
+        );
+        let mut execs = Vec::new();
+        for id in ["j1", "j2", "j3"] {
+            let exec = Arc::new(MockExecutor::new().fail("uptime", 255, "Connection reset"));
+            fleet
+                .add_device(
+                    mock::device(id, crate::JetsonModel::OrinNano8GB, &exec),
+                    ThermalPolicy::default(),
+                )
+                .unwrap();
+            execs.push(exec);
+        }
+
+        let results = fleet.exec_all("uptime").await;
+        assert!(results.values().all(Result::is_err));
+        // One attempt per device plus the two budgeted retries
+        let attempts: usize = execs.iter().map(|e| e.count("uptime")).sum();
+        assert_eq!(attempts, 5);
+        assert_eq!(fleet.retry_budget().unwrap().remaining(), 0);
+
+        // Spent budget: every failure surfaces after a single attempt
+        fleet.exec_all("uptime").await;
+        let attempts: usize = execs.iter().map(|e| e.count("uptime")).sum();
+        assert_eq!(attempts, 8);
     }
 
     #[tokio::test]
-    async fn test_fleet_start_inference_servers() {
-        let fleet = Fleet::new();
-        let result = fleet.start_inference_servers().await;
-        assert!(result.is_ok());
+    async fn test_retry_budget_skips_permanent_failures() {
+        let exec = Arc::new(disk_mock().fail("cat >", 1, "No space left on device"));
+        let mut fleet = Fleet::new().with_retry_budget(
+            RetryBudget::new(5, Duration::from_secs(3600)).with_backoff(Duration::ZERO),
+        );
+        fleet
+            .add_device(
+                mock::device("j1", crate::JetsonModel::OrinNano8GB, &exec),
+                ThermalPolicy::default(),
+            )
+            .unwrap();
+
+        assert!(fleet.deploy_model(b"gguf").await.is_err());
+        assert_eq!(exec.count("cat >"), 1);
+        assert_eq!(fleet.retry_budget().unwrap().remaining(), 5);
+    }
+
+    #[tokio::test]
+    async fn test_sysfs_connection_failure_stays_transient() {
+        let path = "/sys/class/thermal/thermal_zone0/temp";
+        let dropped = Arc::new(MockExecutor::new().fail(
+            "thermal_zone0",
+            255,
+            "ssh: connect to host 192.168.55.1 port 22: Permission denied",
+        ));
+        let device = mock::device("j1", crate::JetsonModel::OrinNano8GB, &dropped);
+        for err in [
+            device.read_sysfs(path).await.unwrap_err(),
+            device.read_sysfs_many(&[path]).await.unwrap_err(),
+            device.write_sysfs(path, "1").await.unwrap_err(),
+        ] {
+            assert!(
+                matches!(err, Error::CommandFailed { exit_code: 255, .. }),
+                "{err}"
+            );
+            assert!(is_transient(&err));
+        }
+
+        let missing = Arc::new(MockExecutor::new().fail("thermal_zone0", 1, "No such file"));
+        let device = mock::device("j1", crate::JetsonModel::OrinNano8GB, &missing);
+        let err = device.read_sysfs(path).await.unwrap_err();
+        assert!(matches!(err, Error::SubsystemUnavailable { .. }));
+        assert!(!is_transient(&err));
     }
 
-    #[cfg(feature = "batuta")]
     #[test]
-    fn test_jetson_executor() {
-        let executor = JetsonExecutor::new("192.168.1.100")
-            .with_thermal_policy(ThermalPolicy::aggressive())
-            .with_memory_budget_mb(4000);
-        assert_eq!(executor.ip, "192.168.1.100");
-        assert_eq!(executor.memory_budget_mb, 4000);
-        assert_eq!(executor.policy.threshold_c, 75.0);
+    fn test_retry_budget_refills_over_window() {
+        let budget = RetryBudget::new(2, Duration::from_millis(20));
+        assert!(budget.try_acquire());
+        assert!(budget.try_acquire());
+        assert!(!budget.try_acquire());
+        std::thread::sleep(Duration::from_millis(30));
+        assert_eq!(budget.remaining(), 2);
+        assert!(!RetryBudget::new(0, Duration::ZERO).try_acquire());
+    }
+
+    const APPLY_CONFIG: &str = "
+fleet:
+  devices:
+    - id: j1
+      power_profile:
+        preset: balanced
+    - id: j2
+models:
+  - name: llama
+    source: /mnt/nvme/models/llama.f16.gguf
+    quantization: q8_0
+  - name: phi
+    source: /mnt/nvme/models/phi.f16.gguf
+    quantization: q8_0
+    devices: j2
+";
+
+    /// Serves each source as `GGUF <source>`, recording what was fetched.
+    #[derive(Debug, Default)]
+    struct RecordingFetcher {
+        fetched: std::sync::Mutex<Vec<String>>,
+    }
+
+    impl ModelFetcher for RecordingFetcher {
+        fn fetch(&self, source: &str) -> Result<Vec<u8>> {
+            self.fetched.lock().unwrap().push(source.to_string());
+            Ok(format!("GGUF {source}").into_bytes())
+        }
+    }
+
+    fn apply_fleet() -> (Fleet, Arc<MockExecutor>, Arc<RecordingFetcher>) {
+        let exec = Arc::new(disk_mock().on("nvpmodel -q", "NV Power Mode: 15W\n2\n"));
+        let fetcher = Arc::new(RecordingFetcher::default());
+        let mut fleet = Fleet::new().with_model_fetcher(fetcher.clone());
+        for id in ["j1", "j2", "old"] {
+            fleet
+                .add_device(
+                    mock::device(id, crate::JetsonModel::OrinNano8GB, &exec),
+                    ThermalPolicy::conservative(),
+                )
+                .unwrap();
+        }
+        (fleet, exec, fetcher)
+    }
+
+    #[tokio::test]
+    async fn test_apply_diff_converges_then_noops() {
+        let config = CoheteConfig::from_yaml(APPLY_CONFIG).unwrap();
+        let (mut fleet, exec, fetcher) = apply_fleet();
+
+        let current = fleet.to_state();
+        let report = fleet.apply_diff(&config, &current).await.unwrap();
+        assert_eq!(report.removed_devices, vec!["old"]);
+        assert_eq!(report.power_profiles, vec!["j1"]);
+        assert_eq!(
+            report.deployed,
+            vec![
+                ("j1".to_string(), "llama".to_string()),
+                ("j2".to_string(), "llama".to_string()),
+                ("j2".to_string(), "phi".to_string()),
+            ]
+        );
+        assert!(report.thermal_policies.is_empty());
+
+        // Each source is fetched once, then uploaded before quantizing
+        let llama = "/mnt/nvme/models/llama.f16.gguf";
+        assert_eq!(
+            *fetcher.fetched.lock().unwrap(),
+            [llama, "/mnt/nvme/models/phi.f16.gguf"]
+        );
+        let uploads: Vec<(String, Vec<u8>)> = exec
+            .inputs()
+            .into_iter()
+            .filter(|(command, _)| command.contains("llama.f16.gguf"))
+            .collect();
+        assert_eq!(uploads.len(), 2);
+        assert!(uploads
+            .iter()
+            .all(|(_, data)| *data == format!("GGUF {llama}").into_bytes()));
+        let calls = exec.calls();
+        let step = |pattern: &str| calls.iter().position(|c| c.contains(pattern)).unwrap();
+        assert!(step("llama.f16.gguf.tmp") < step("llama-quantize"));
+
+        // Nothing changed: a second apply touches no device
+        let calls = exec.calls().len();
+        let current = fleet.to_state();
+        let report = fleet.apply_diff(&config, &current).await.unwrap();
+        assert!(report.is_noop(), "{report:?}");
+        assert_eq!(exec.calls().len(), calls);
+        assert_eq!(fetcher.fetched.lock().unwrap().len(), 2);
+    }
+
+    #[tokio::test]
+    async fn test_apply_diff_deploys_only_changed_model() {
+        let mut config = CoheteConfig::from_yaml(APPLY_CONFIG).unwrap();
+        let (mut fleet, exec, _) = apply_fleet();
+        let current = fleet.to_state();
+        fleet.apply_diff(&config, &current).await.unwrap();
+
+        config.models[1].quantization = Some("q4_0".to_string());
+        let quantized = exec.count("llama-quantize");
+        let uploads = exec.inputs().len();
+        let current = fleet.to_state();
+        let report = fleet.apply_diff(&config, &current).await.unwrap();
+
+        assert_eq!(report.deployed, vec![("j2".to_string(), "phi".to_string())]);
+        assert!(report.power_profiles.is_empty());
+        assert_eq!(exec.count("llama-quantize"), quantized + 1);
+        assert_eq!(exec.count("phi.q4_0.gguf"), 2);
+        // The source and the manifest are uploaded to j2 only
+        let uploaded: Vec<String> = exec.inputs()[uploads..]
+            .iter()
+            .map(|(command, _)| command.clone())
+            .collect();
+        assert_eq!(uploaded.len(), 2);
+        assert!(uploaded[0].contains("phi.f16.gguf"));
+        assert_eq!(
+            fleet.to_state().devices["j2"].models["phi"].quant,
+            QuantLevel::Q4_0
+        );
+    }
+
+    #[tokio::test]
+    async fn test_fleet_state_diff() {
+        let exec = Arc::new(disk_mock());
+        let device = |id| mock::device(id, crate::JetsonModel::OrinNano8GB, &exec);
+        let mut fleet = Fleet::new();
+        for id in ["j1", "j2"] {
+            fleet
+                .add_device(device(id), ThermalPolicy::conservative())
+                .unwrap();
+        }
+        fleet
+            .deploy_with_oom_fallback("/mnt/nvme/models/llama.f16.gguf", &oom_config())
+            .await
+            .unwrap();
+        let yesterday = fleet.to_state();
+        assert!(yesterday.diff(&fleet.to_state()).is_empty());
+
+        fleet
+            .add_device(device("j3"), ThermalPolicy::conservative())
+            .unwrap();
+        let upgrade = DeploymentConfig {
+            target_devices: vec!["j1".to_string()],
+            ..oom_config()
+        };
+        fleet
+            .deploy_with_oom_fallback("/mnt/nvme/models/v2/llama.f16.gguf", &upgrade)
+            .await
+            .unwrap();
+        fleet.cordon("j2").unwrap();
+        fleet.devices.get_mut("j2").unwrap().policy = ThermalPolicy::aggressive();
+
+        // Snapshots survive a round trip through storage
+        let json = serde_json::to_string(&fleet.to_state()).unwrap();
+        let today: FleetState = serde_json::from_str(&json).unwrap();
+        let diff = yesterday.diff(&today);
+
+        assert_eq!(diff.added, vec!["j3"]);
+        assert!(diff.removed.is_empty());
+        assert_eq!(
+            diff.for_device("j1").collect::<Vec<_>>(),
+            vec![&DeviceChange::Model {
+                device_id: "j1".to_string(),
+                model: "llama".to_string(),
+                from: Some(ModelState {
+                    source: "/mnt/nvme/models/llama.f16.gguf".to_string(),
+                    quant: QuantLevel::Q8_0,
+                }),
+                to: Some(ModelState {
+                    source: "/mnt/nvme/models/v2/llama.f16.gguf".to_string(),
+                    quant: QuantLevel::Q8_0,
+                }),
+            }]
+        );
+        let j2: Vec<_> = diff.for_device("j2").collect();
+        assert_eq!(j2.len(), 2);
+        assert_eq!(
+            j2[0],
+            &DeviceChange::Cordoned {
+                device_id: "j2".to_string(),
+                cordoned: true,
+            }
+        );
+        assert!(matches!(
+            j2[1],
+            DeviceChange::ThermalPolicy { from, to, .. }
+                if from.threshold_c == 65.0 && to.threshold_c > from.threshold_c
+        ));
+
+        let reverse = today.diff(&yesterday);
+        assert_eq!(reverse.removed, vec!["j3"]);
     }
 }
@@ -24,7 +24,7 @@
 //!
 //!     for device in devices {
 //!         let mut monitor = TegraMonitor::connect(&device)?;
-//!         let stats = monitor.sample()?;
+//!         let stats = monitor.sample_async().await?;
 //!         println!("{}: GPU {}°C, Memory {}MB free",
 //!             device.id(),
 //!             stats.gpu_temp,
@@ -74,6 +74,7 @@ pub mod fleet;
 pub mod quantize;
 pub mod provision;
 pub mod config;
+pub mod audit;
 mod error;
 
 // Re-exports
@@ -103,6 +104,22 @@ pub enum JetsonModel {
 }
 
 impl JetsonModel {
+    /// Every known model, in declaration order.
+    ///
+    /// Excludes [`Self::Unknown`], which is a detection fallback rather than
+    /// real hardware.
+    #[must_use]
+    pub const fn variants() -> &'static [Self] {
+        &[
+            Self::OrinNano4GB,
+            Self::OrinNano8GB,
+            Self::OrinNX8GB,
+            Self::OrinNX16GB,
+            Self::AgxOrin32GB,
+            Self::AgxOrin64GB,
+        ]
+    }
+
     /// Total memory in MB
     #[must_use]
     pub const fn memory_mb(&self) -> u64 {
@@ -131,6 +148,23 @@ impl JetsonModel {
         }
     }
 
+    /// Whether CPU and iGPU share one physical memory pool.
+    ///
+    /// True for every current Jetson; listed per model so a future module
+    /// with dedicated GPU memory must decide explicitly.
+    #[must_use]
+    pub const fn is_unified_memory(&self) -> bool {
+        match self {
+            Self::OrinNano4GB
+            | Self::OrinNano8GB
+            | Self::OrinNX8GB
+            | Self::OrinNX16GB
+            | Self::AgxOrin32GB
+            | Self::AgxOrin64GB
+            | Self::Unknown => true,
+        }
+    }
+
     /// AI performance in TOPS
     #[must_use]
     pub const fn tops(&self) -> u32 {
@@ -186,6 +220,24 @@ mod tests {
         assert_eq!(JetsonModel::Unknown.cuda_cores(), 0);
     }
 
+    #[test]
+    fn test_jetson_model_unified_memory() {
+        assert!(JetsonModel::OrinNano8GB.is_unified_memory());
+        assert!(JetsonModel::AgxOrin64GB.is_unified_memory());
+        assert!(JetsonModel::Unknown.is_unified_memory());
+    }
+
+    #[test]
+    fn test_jetson_model_variants() {
+        let variants = JetsonModel::variants();
+        assert_eq!(variants.len(), 6);
+        assert!(!variants.contains(&JetsonModel::Unknown));
+        assert!(variants.iter().all(|m| m.memory_mb() > 0));
+
+        let unique: std::collections::HashSet<_> = variants.iter().collect();
+        assert_eq!(unique.len(), variants.len());
+    }
+
     #[test]
     fn test_jetson_model_display() {
         assert_eq!(JetsonModel::OrinNano4GB.to_string(), "Jetson Orin Nano 4GB");
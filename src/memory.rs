@@ -2,8 +2,21 @@
 //!
 //! Provides budget-aware allocation, memory tracking, and OOM prevention.
 
-use crate::{Error, Result};
+use crate::device::JetsonDevice;
+use crate::{Error, JetsonModel, Result};
+use std::fmt::Write;
 use std::sync::atomic::{AtomicU64, Ordering};
+use std::sync::{Mutex, PoisonError};
+
+/// Memory the desktop session pins, reclaimed on headless devices (MB).
+pub const DESKTOP_RESERVE_MB: u64 = 1024;
+
+/// Smallest system reservation, even when headless (MB).
+const MIN_RESERVED_MB: u64 = 512;
+
+/// Gap between tracked and OS-reported free memory beyond which
+/// [`MemoryBudget::reconcile`] warns of untracked consumers.
+pub const RECONCILE_DRIFT_MB: u64 = 512;
 
 /// Memory budget enforcer - Poka-Yoke pattern.
 ///
@@ -16,6 +29,8 @@ pub struct MemoryBudget {
     reserved_mb: u64,
     /// Currently allocated in MB
     allocated: AtomicU64,
+    /// Allocated MB per label, in order of first allocation
+    labels: Mutex<Vec<(String, u64)>>,
 }
 
 impl MemoryBudget {
@@ -31,6 +46,7 @@ impl MemoryBudget {
             total_mb,
             reserved_mb,
             allocated: AtomicU64::new(0),
+            labels: Mutex::new(Vec::new()),
         }
     }
 
@@ -64,6 +80,60 @@ impl MemoryBudget {
         Self::new(65536, 8192)
     }
 
+    /// Create a budget for a device's model, sized for its display state.
+    ///
+    /// The total is the memory CUDA can allocate on the device (see
+    /// [`JetsonDevice::memory_zones`]), so carveouts reserved at boot are
+    /// not counted. The reservation matches the model presets (such as
+    /// [`Self::orin_nano_8gb`]), less [`DESKTOP_RESERVE_MB`] when the
+    /// device is headless.
+    ///
+    /// # Errors
+    ///
+    /// Returns `Error::Config` if the model's memory size is unknown, or an
+    /// error if the display state or `/proc/meminfo` cannot be read.
+    pub async fn from_device(device: &JetsonDevice) -> Result<Self> {
+        if device.model().memory_mb() == 0 {
+            return Err(Error::Config(format!(
+                "{}: unknown model, memory size not known",
+                device.id()
+            )));
+        }
+        let mut reserved_mb = Self::for_model(device.model())?.reserved_mb;
+        if device.is_headless().await? {
+            reserved_mb = reserved_mb
+                .saturating_sub(DESKTOP_RESERVE_MB)
+                .max(MIN_RESERVED_MB);
+        }
+        Ok(device.memory_zones().await?.cuda_budget(reserved_mb))
+    }
+
+    /// Budget for a model, reserving as the model presets do.
+    ///
+    /// # Errors
+    ///
+    /// Returns `Error::Config` for [`JetsonModel::Unknown`], whose memory
+    /// size is not known; use [`Self::new`] with an explicit size instead.
+    pub fn for_model(model: JetsonModel) -> Result<Self> {
+        let reserved_mb = match model.memory_mb() {
+            0 => {
+                return Err(Error::Config(
+                    "unknown model, memory size not known".to_string(),
+                ))
+            }
+            1..=4096 => 1024,
+            4097..=16384 => 2048,
+            total => total / 8,
+        };
+        Ok(Self::new(model.memory_mb(), reserved_mb))
+    }
+
+    /// Reserved for the system in MB.
+    #[must_use]
+    pub const fn reserved_mb(&self) -> u64 {
+        self.reserved_mb
+    }
+
     /// Get total memory in MB.
     #[must_use]
     pub fn total_mb(&self) -> u64 {
@@ -75,8 +145,70 @@ impl MemoryBudget {
     /// # Errors
     ///
     /// Returns `Error::InsufficientMemory` if allocation would exceed budget.
-    pub fn allocate(&self, size_mb: u64, _label: &str) -> Result<MemoryGuard<'_>> {
-        self.try_allocate(size_mb)
+    pub fn allocate(&self, size_mb: u64, label: &str) -> Result<MemoryGuard<'_>> {
+        let mut guard = self.try_allocate(size_mb)?;
+        self.adjust_label(label, size_mb, true);
+        guard.label = Some(label.to_string());
+        Ok(guard)
+    }
+
+    /// Allocated MB per label, in order of first allocation.
+    ///
+    /// Allocations made without a label are not included.
+    #[must_use]
+    pub fn allocations_by_label(&self) -> Vec<(String, u64)> {
+        self.labels
+            .lock()
+            .unwrap_or_else(PoisonError::into_inner)
+            .clone()
+    }
+
+    /// Human-readable one-line breakdown of the budget.
+    ///
+    /// For example: `Total: 8192MB | Reserved: 2048MB | Allocated: 3000MB
+    /// (by: weights 2000, kv_cache 1000) | Available: 3144MB | 49% used`.
+    #[must_use]
+    pub fn report(&self) -> String {
+        let allocated = self.allocated_mb();
+        let mut report = format!(
+            "Total: {}MB | Reserved: {}MB | Allocated: {allocated}MB",
+            self.total_mb, self.reserved_mb
+        );
+        let labels = self.allocations_by_label();
+        let labeled: u64 = labels.iter().map(|(_, mb)| mb).sum();
+        let mut parts: Vec<String> = labels
+            .iter()
+            .map(|(label, mb)| format!("{label} {mb}"))
+            .collect();
+        if allocated > labeled && !parts.is_empty() {
+            parts.push(format!("unlabeled {}", allocated - labeled));
+        }
+        if !parts.is_empty() {
+            let _ = write!(report, " (by: {})", parts.join(", "));
+        }
+        let _ = write!(
+            report,
+            " | Available: {}MB | {:.0}% used",
+            self.available_mb(),
+            self.utilization_percent()
+        );
+        report
+    }
+
+    fn adjust_label(&self, label: &str, size_mb: u64, add: bool) {
+        let mut labels = self.labels.lock().unwrap_or_else(PoisonError::into_inner);
+        let index = labels.iter().position(|(l, _)| l == label);
+        match (index, add) {
+            (Some(i), true) => labels[i].1 += size_mb,
+            (None, true) => labels.push((label.to_string(), size_mb)),
+            (Some(i), false) => {
+                labels[i].1 = labels[i].1.saturating_sub(size_mb);
+                if labels[i].1 == 0 {
+                    labels.remove(i);
+                }
+            }
+            (None, false) => {}
+        }
     }
 
     /// Get available memory in MB.
@@ -86,6 +218,37 @@ impl MemoryBudget {
         self.total_mb.saturating_sub(self.reserved_mb + allocated)
     }
 
+    /// Compare tracked free memory against the device's live
+    /// `MemAvailable`.
+    ///
+    /// Logs a warning when they differ by more than
+    /// [`RECONCILE_DRIFT_MB`].
+    ///
+    /// # Errors
+    ///
+    /// Returns an error if `/proc/meminfo` cannot be read or has no
+    /// `MemAvailable` line.
+    pub async fn reconcile(&self, device: &JetsonDevice) -> Result<MemoryReconciliation> {
+        let meminfo = device.exec("cat /proc/meminfo").await?;
+        let os_available_mb = parse_meminfo_available(&meminfo).ok_or_else(|| Error::Parse {
+            context: "/proc/meminfo".to_string(),
+            message: "no MemAvailable line".to_string(),
+        })?;
+        let reconciliation = MemoryReconciliation {
+            tracked_available_mb: self.available_mb(),
+            os_available_mb,
+        };
+        if reconciliation.diverged() {
+            tracing::warn!(
+                device = %device.id(),
+                tracked_available_mb = reconciliation.tracked_available_mb,
+                os_available_mb,
+                "Memory budget diverges from OS, untracked consumers likely"
+            );
+        }
+        Ok(reconciliation)
+    }
+
     /// Get allocated memory in MB.
     #[must_use]
     pub fn allocated_mb(&self) -> u64 {
@@ -115,6 +278,7 @@ impl MemoryBudget {
         Ok(MemoryGuard {
             budget: self,
             size_mb,
+            label: None,
         })
     }
 
@@ -142,6 +306,7 @@ impl MemoryBudget {
 pub struct MemoryGuard<'a> {
     budget: &'a MemoryBudget,
     size_mb: u64,
+    label: Option<String>,
 }
 
 impl Drop for MemoryGuard<'_> {
@@ -149,6 +314,9 @@ impl Drop for MemoryGuard<'_> {
         self.budget
             .allocated
             .fetch_sub(self.size_mb, Ordering::Release);
+        if let Some(label) = &self.label {
+            self.budget.adjust_label(label, self.size_mb, false);
+        }
     }
 }
 
@@ -158,6 +326,111 @@ impl MemoryGuard<'_> {
     pub fn size_mb(&self) -> u64 {
         self.size_mb
     }
+
+    /// Label given to [`MemoryBudget::allocate`], if any.
+    #[must_use]
+    pub fn label(&self) -> Option<&str> {
+        self.label.as_deref()
+    }
+}
+
+/// Split of device memory between the iGPU carveout and the system.
+///
+/// Carveouts are reserved at boot for the GPU and other engines. Protected
+/// ones such as VPR are off-limits to CUDA, which on unified-memory modules
+/// allocates from system memory instead.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct MemoryZones {
+    /// Memory managed by the kernel in MB (`MemTotal`)
+    pub system_mb: u64,
+    /// Carveout memory reserved for the iGPU in MB
+    pub gpu_carveout_mb: u64,
+    /// CPU and iGPU share system memory
+    pub unified: bool,
+}
+
+impl MemoryZones {
+    /// Memory CUDA can allocate in MB: all of system memory on unified
+    /// modules, as `MemTotal` already excludes the carveouts, the carveout
+    /// otherwise.
+    #[must_use]
+    pub const fn cuda_mb(&self) -> u64 {
+        if self.unified {
+            self.system_mb
+        } else {
+            self.gpu_carveout_mb
+        }
+    }
+
+    /// Budget for CPU allocations: system memory only.
+    #[must_use]
+    pub fn cpu_budget(&self, reserved_mb: u64) -> MemoryBudget {
+        MemoryBudget::new(self.system_mb, reserved_mb)
+    }
+
+    /// Budget for CUDA allocations.
+    #[must_use]
+    pub fn cuda_budget(&self, reserved_mb: u64) -> MemoryBudget {
+        MemoryBudget::new(self.cuda_mb(), reserved_mb)
+    }
+}
+
+/// Tracked vs OS-reported free memory, from [`MemoryBudget::reconcile`].
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct MemoryReconciliation {
+    /// Free memory according to the budget's allocations, in MB
+    pub tracked_available_mb: u64,
+    /// `MemAvailable` reported by the kernel, in MB
+    pub os_available_mb: u64,
+}
+
+impl MemoryReconciliation {
+    /// Tracked minus OS-available memory in MB; positive when the OS has
+    /// less free than the budget believes.
+    #[must_use]
+    #[allow(clippy::cast_possible_wrap)]
+    pub const fn delta_mb(&self) -> i64 {
+        self.tracked_available_mb as i64 - self.os_available_mb as i64
+    }
+
+    /// Whether the two differ by more than [`RECONCILE_DRIFT_MB`].
+    #[must_use]
+    pub const fn diverged(&self) -> bool {
+        self.delta_mb().unsigned_abs() > RECONCILE_DRIFT_MB
+    }
+}
+
+/// Parse `MemAvailable` from `/proc/meminfo` output, in MB.
+#[must_use]
+pub fn parse_meminfo_available(meminfo: &str) -> Option<u64> {
+    meminfo.lines().find_map(|line| {
+        let kb = line
+            .strip_prefix("MemAvailable:")?
+            .trim()
+            .strip_suffix("kB")?;
+        kb.trim().parse::<u64>().ok().map(|kb| kb / 1024)
+    })
+}
+
+/// Sum GPU carveout sizes from `path:bytes` lines of nvmap heap `size`
+/// files, in MB.
+///
+/// The `iovmm` heap is backed by system memory and is skipped.
+#[must_use]
+pub fn parse_carveouts(output: &str) -> u64 {
+    let bytes: u64 = output
+        .lines()
+        .filter_map(|line| line.rsplit_once(':'))
+        .filter(|(path, _)| !path.contains("/iovmm/"))
+        .filter_map(|(_, value)| {
+            let value = value.trim();
+            value.strip_prefix("0x").map_or_else(
+                || value.parse().ok(),
+                |hex| u64::from_str_radix(hex, 16).ok(),
+            )
+        })
+        .sum();
+    bytes / (1024 * 1024)
 }
 
 /// Estimate model memory requirements.
@@ -225,6 +498,107 @@ impl ModelMemoryEstimate {
 #[cfg(test)]
 mod tests {
     use super::*;
+    use crate::device::mock::{self, MockExecutor};
+    use crate::JetsonModel;
+    use std::sync::Arc;
+
+    const MEMINFO: &str = "MemTotal:        7620440 kB
+MemFree:          512000 kB
+MemAvailable:    2097152 kB
+Cached:          1572864 kB
+";
+
+    #[tokio::test]
+    async fn test_reconcile_flags_untracked_consumers() {
+        let exec = Arc::new(MockExecutor::new().on("/proc/meminfo", MEMINFO));
+        let device = mock::device("j1", JetsonModel::OrinNano8GB, &exec);
+        let budget = MemoryBudget::orin_nano_8gb();
+        let _model = budget.allocate(1024, "model").unwrap();
+
+        // Tracked: 8192 - 2048 reserved - 1024 = 5120 MB; OS has 2048 MB
+        let drift = budget.reconcile(&device).await.unwrap();
+        assert_eq!(drift.tracked_available_mb, 5120);
+        assert_eq!(drift.os_available_mb, 2048);
+        assert_eq!(drift.delta_mb(), 3072);
+        assert!(drift.diverged());
+
+        let _cache = budget.allocate(2816, "kv-cache").unwrap();
+        let close = budget.reconcile(&device).await.unwrap();
+        assert_eq!(close.delta_mb(), 256);
+        assert!(!close.diverged());
+    }
+
+    #[tokio::test]
+    async fn test_reconcile_requires_mem_available() {
+        let exec = Arc::new(MockExecutor::new().on("/proc/meminfo", "MemTotal: 7620440 kB\n"));
+        let device = mock::device("j1", JetsonModel::OrinNano8GB, &exec);
+        assert!(matches!(
+            MemoryBudget::orin_nano_8gb().reconcile(&device).await,
+            Err(Error::Parse { .. })
+        ));
+        assert_eq!(parse_meminfo_available(MEMINFO), Some(2048));
+    }
+
+    #[tokio::test]
+    async fn test_from_device_reclaims_desktop_reservation() {
+        let meminfo = "MemTotal:        7802880 kB\n";
+        let desktop = Arc::new(
+            MockExecutor::new()
+                .on("is-active", "active\n")
+                .on("MemTotal", meminfo),
+        );
+        let headless = Arc::new(
+            MockExecutor::new()
+                .on("is-active", "inactive\n")
+                .on("MemTotal", meminfo),
+        );
+        let budget = |model, exec| async move {
+            MemoryBudget::from_device(&mock::device("j1", model, &exec))
+                .await
+                .unwrap()
+        };
+
+        let nano = budget(JetsonModel::OrinNano8GB, desktop.clone()).await;
+        // Sized from MemTotal, not the nominal 8192 MB
+        assert_eq!((nano.total_mb(), nano.reserved_mb()), (7620, 2048));
+        let nano = budget(JetsonModel::OrinNano8GB, headless.clone()).await;
+        assert_eq!(nano.reserved_mb(), 1024);
+        let small = budget(JetsonModel::OrinNano4GB, headless.clone()).await;
+        assert_eq!(small.reserved_mb(), MIN_RESERVED_MB);
+        let agx = budget(JetsonModel::AgxOrin64GB, desktop).await;
+        assert_eq!(
+            agx.reserved_mb(),
+            MemoryBudget::agx_orin_64gb().reserved_mb()
+        );
+
+        let unknown = mock::device("j2", JetsonModel::Unknown, &headless);
+        assert!(matches!(
+            MemoryBudget::from_device(&unknown).await,
+            Err(Error::Config(_))
+        ));
+    }
+
+    #[test]
+    fn test_for_model_rejects_unknown() {
+        for (model, preset) in [
+            (JetsonModel::OrinNano4GB, MemoryBudget::orin_nano_4gb()),
+            (JetsonModel::OrinNano8GB, MemoryBudget::orin_nano_8gb()),
+            (JetsonModel::OrinNX16GB, MemoryBudget::orin_nx_16gb()),
+            (JetsonModel::AgxOrin32GB, MemoryBudget::agx_orin_32gb()),
+            (JetsonModel::AgxOrin64GB, MemoryBudget::agx_orin_64gb()),
+        ] {
+            let budget = MemoryBudget::for_model(model).unwrap();
+            assert_eq!(
+                (budget.total_mb(), budget.reserved_mb()),
+                (preset.total_mb(), preset.reserved_mb()),
+                "{model}"
+            );
+        }
+
+        let err = MemoryBudget::for_model(JetsonModel::Unknown).unwrap_err();
+        assert!(matches!(err, Error::Config(_)));
+        assert!(err.to_string().contains("unknown model"));
+    }
 
     #[test]
     fn test_memory_budget_available() {
@@ -264,4 +638,64 @@ mod tests {
         let _guard = budget.try_allocate(3072).unwrap();
         assert!((budget.utilization_percent() - 50.0).abs() < 0.1);
     }
+
+    #[test]
+    fn test_budget_report_breakdown() {
+        let budget = MemoryBudget::orin_nano_8gb();
+        let weights = budget.allocate(2000, "weights").unwrap();
+        let kv_cache = budget.allocate(1000, "kv_cache").unwrap();
+        assert_eq!(kv_cache.label(), Some("kv_cache"));
+
+        assert_eq!(
+            budget.report(),
+            "Total: 8192MB | Reserved: 2048MB | Allocated: 3000MB \
+             (by: weights 2000, kv_cache 1000) | Available: 3144MB | 49% used"
+        );
+
+        let scratch = budget.try_allocate(144).unwrap();
+        assert!(budget
+            .report()
+            .contains("(by: weights 2000, kv_cache 1000, unlabeled 144)"));
+        drop(scratch);
+
+        drop(weights);
+        assert_eq!(
+            budget.allocations_by_label(),
+            vec![("kv_cache".to_string(), 1000)]
+        );
+        drop(kv_cache);
+        assert_eq!(
+            budget.report(),
+            "Total: 8192MB | Reserved: 2048MB | Allocated: 0MB | Available: 6144MB | 0% used"
+        );
+    }
+
+    #[test]
+    fn test_parse_carveouts_agx() {
+        let output = "/sys/kernel/debug/nvmap/iovmm/size:0x800000000\n\
+                      /sys/kernel/debug/nvmap/vpr/size:0x2a000000\n\
+                      /sys/kernel/debug/nvmap/generic-0/size:33554432\n";
+        // 672 MB VPR + 32 MB generic; iovmm is system memory
+        assert_eq!(parse_carveouts(output), 704);
+        assert_eq!(parse_carveouts(""), 0);
+    }
+
+    #[test]
+    fn test_memory_zones_budgets() {
+        let zones = MemoryZones {
+            system_mb: 30_000,
+            gpu_carveout_mb: 704,
+            unified: true,
+        };
+        // MemTotal already excludes the carveouts; they are not taken twice
+        assert_eq!(zones.cuda_mb(), 30_000);
+        assert_eq!(zones.cpu_budget(4096).available_mb(), 25_904);
+        assert_eq!(zones.cuda_budget(4096).available_mb(), 25_904);
+
+        let discrete = MemoryZones {
+            unified: false,
+            ..zones
+        };
+        assert_eq!(discrete.cuda_mb(), 704);
+    }
 }
@@ -2,7 +2,30 @@
 //!
 //! Provides nvpmodel and jetson_clocks integration.
 
-use crate::Result;
+use crate::{
+    config::{CoheteConfig, PowerControlConfig},
+    device::{
+        blocking, shell_quote, sysfs_error, CarrierBoard, CommandExecutor, JetsonDevice,
+        LocalExecutor, SshExecutor,
+    },
+    Error, Result, Subsystem,
+};
+use serde::{Deserialize, Deserializer, Serialize, Serializer};
+use std::collections::HashMap;
+use std::future::Future;
+use std::pin::Pin;
+use std::sync::Arc;
+use std::time::Duration;
+
+/// GPU devfreq node. The GPU sits at `17000000` on Orin and Xavier, named
+/// `ga10b`, `gv11b` or `gpu` depending on module and L4T release.
+const GPU_DEVFREQ: &str = "/sys/class/devfreq/17000000.*";
+
+/// PWM duty cycle node of the carrier board fan.
+pub(crate) const FAN_PWM: &str = "/sys/devices/platform/pwm-fan/hwmon/hwmon*/pwm1";
+
+/// BPMP debugfs directory for the EMC (memory) clock.
+const EMC_CLK: &str = "/sys/kernel/debug/bpmp/debug/clk/emc";
 
 /// Power mode settings for nvpmodel.
 #[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
@@ -29,6 +52,17 @@ impl PowerMode {
         }
     }
 
+    /// Get power mode from nvpmodel mode ID.
+    #[must_use]
+    pub const fn from_mode_id(id: u8) -> Self {
+        match id {
+            0 => Self::Maxn,
+            1 => Self::Power15W,
+            2 => Self::Power7W,
+            id => Self::Custom(id),
+        }
+    }
+
     /// Get human-readable name.
     #[must_use]
     pub const fn name(&self) -> &'static str {
@@ -40,6 +74,26 @@ impl PowerMode {
         }
     }
 
+    /// Next mode down in power draw, or `None` at 7W and for custom modes.
+    #[must_use]
+    pub const fn step_down(&self) -> Option<Self> {
+        match self {
+            Self::Maxn => Some(Self::Power15W),
+            Self::Power15W => Some(Self::Power7W),
+            Self::Power7W | Self::Custom(_) => None,
+        }
+    }
+
+    /// Next mode up in power draw, or `None` at MAXN and for custom modes.
+    #[must_use]
+    pub const fn step_up(&self) -> Option<Self> {
+        match self {
+            Self::Power7W => Some(Self::Power15W),
+            Self::Power15W => Some(Self::Maxn),
+            Self::Maxn | Self::Custom(_) => None,
+        }
+    }
+
     /// Get power budget in watts.
     #[must_use]
     pub const fn power_budget_watts(&self) -> Option<u32> {
@@ -113,8 +167,8 @@ pub struct PowerProfile {
     pub mode: PowerMode,
     /// Enable jetson_clocks
     pub enable_clocks: bool,
-    /// Fan speed (0-255)
-    pub fan_speed: u8,
+    /// Fan speed
+    pub fan_speed: FanSpeed,
 }
 
 impl PowerProfile {
@@ -124,7 +178,7 @@ impl PowerProfile {
         Self {
             mode: PowerMode::Maxn,
             enable_clocks: true,
-            fan_speed: 255,
+            fan_speed: FanSpeed::FULL,
         }
     }
 
@@ -134,7 +188,7 @@ impl PowerProfile {
         Self {
             mode: PowerMode::Power15W,
             enable_clocks: false,
-            fan_speed: 128,
+            fan_speed: FanSpeed::from_pwm(128),
         }
     }
 
@@ -144,7 +198,7 @@ impl PowerProfile {
         Self {
             mode: PowerMode::Power7W,
             enable_clocks: false,
-            fan_speed: 64,
+            fan_speed: FanSpeed::from_pwm(64),
         }
     }
 }
@@ -155,9 +209,619 @@ impl Default for PowerProfile {
     }
 }
 
+/// Fan duty, stored as the raw PWM value (0-255) the fan node takes.
+///
+/// Configured either as a PWM integer (`fan_speed: 128`) or a percentage
+/// (`fan_speed: 50%`).
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq, PartialOrd, Ord, Hash)]
+pub struct FanSpeed(u8);
+
+impl FanSpeed {
+    /// Fan stopped.
+    pub const OFF: Self = Self(0);
+    /// Fan at full duty.
+    pub const FULL: Self = Self(u8::MAX);
+
+    /// Speed from a raw PWM duty.
+    #[must_use]
+    pub const fn from_pwm(pwm: u8) -> Self {
+        Self(pwm)
+    }
+
+    /// Speed from a percentage, rounded to the nearest PWM step.
+    ///
+    /// Values above 100 are clamped.
+    #[must_use]
+    pub fn from_percent(percent: u8) -> Self {
+        let percent = u16::from(percent.min(100));
+        let pwm = (percent * 255 + 50) / 100;
+        Self(u8::try_from(pwm).unwrap_or(u8::MAX))
+    }
+
+    /// Raw PWM duty (0-255).
+    #[must_use]
+    pub const fn to_pwm(self) -> u8 {
+        self.0
+    }
+
+    /// Duty as a percentage, rounded to the nearest whole percent.
+    #[must_use]
+    pub fn to_percent(self) -> u8 {
+        let percent = (u16::from(self.0) * 100 + 127) / 255;
+        u8::try_from(percent).unwrap_or(100)
+    }
+}
+
+impl std::fmt::Display for FanSpeed {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        write!(f, "{}%", self.to_percent())
+    }
+}
+
+impl std::str::FromStr for FanSpeed {
+    type Err = Error;
+
+    /// Parse `"50%"` as a percentage or `"128"` as a PWM duty.
+    fn from_str(s: &str) -> Result<Self> {
+        let s = s.trim();
+        let invalid = || Error::Parse {
+            context: "fan speed".to_string(),
+            message: format!("expected 0-255 or 0-100%, got '{s}'"),
+        };
+        let (digits, is_percent) = s.strip_suffix('%').map_or((s, false), |p| (p.trim(), true));
+        let value: u8 = digits.parse().map_err(|_| invalid())?;
+        match (is_percent, value) {
+            (false, pwm) => Ok(Self(pwm)),
+            (true, percent) if percent <= 100 => Ok(Self::from_percent(percent)),
+            (true, _) => Err(invalid()),
+        }
+    }
+}
+
+impl Serialize for FanSpeed {
+    /// Written as a percentage when that round-trips exactly, else as PWM.
+    fn serialize<S: Serializer>(&self, serializer: S) -> std::result::Result<S::Ok, S::Error> {
+        if Self::from_percent(self.to_percent()) == *self {
+            serializer.collect_str(self)
+        } else {
+            serializer.serialize_u8(self.0)
+        }
+    }
+}
+
+impl<'de> Deserialize<'de> for FanSpeed {
+    fn deserialize<D: Deserializer<'de>>(deserializer: D) -> std::result::Result<Self, D::Error> {
+        #[derive(Deserialize)]
+        #[serde(untagged)]
+        enum Raw {
+            Pwm(u8),
+            Text(String),
+        }
+        match Raw::deserialize(deserializer)? {
+            Raw::Pwm(pwm) => Ok(Self(pwm)),
+            Raw::Text(text) => text.parse().map_err(serde::de::Error::custom),
+        }
+    }
+}
+
+/// Power state read back from a device, see [`PowerController::verify_profile`].
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct AppliedProfile {
+    /// Active nvpmodel mode
+    pub mode: PowerMode,
+    /// GPU clock floor pinned to its cap, as `jetson_clocks` leaves it
+    pub clocks_locked: bool,
+    /// Fan PWM duty (0-255)
+    pub fan_pwm: u8,
+}
+
+/// nvpmodel controller for a device.
+#[derive(Debug, Clone)]
+pub struct PowerController {
+    stability_samples: u32,
+}
+
+impl PowerController {
+    /// Create a new power controller.
+    #[must_use]
+    pub const fn new() -> Self {
+        Self {
+            stability_samples: 5,
+        }
+    }
+
+    /// Set number of samples taken by [`Self::verify_stable`], at least
+    /// two so both ends of the window are observed.
+    #[must_use]
+    pub fn with_stability_samples(mut self, samples: u32) -> Self {
+        self.stability_samples = samples.max(2);
+        self
+    }
+
+    /// Query the active nvpmodel power mode.
+    ///
+    /// # Errors
+    ///
+    /// Returns an error if nvpmodel cannot be queried or its output is not understood.
+    pub async fn current_mode(&self, device: &JetsonDevice) -> Result<PowerMode> {
+        let output = device
+            .exec("nvpmodel -q")
+            .await
+            .map_err(|e| e.with_subsystem(Subsystem::NvpModel))?;
+        parse_nvpmodel_query(&output)
+    }
+
+    /// Set the nvpmodel power mode.
+    ///
+    /// # Errors
+    ///
+    /// Returns an error if nvpmodel rejects the mode.
+    pub async fn set_mode(&self, device: &JetsonDevice, mode: PowerMode) -> Result<()> {
+        device
+            .exec(&format!("sudo nvpmodel -m {}", mode.mode_id()))
+            .await
+            .map(|_| ())
+            .map_err(|e| e.with_subsystem(Subsystem::NvpModel))
+    }
+
+    /// Current GPU frequency cap in Hz.
+    ///
+    /// # Errors
+    ///
+    /// Returns an error if the devfreq node cannot be read.
+    pub async fn gpu_max_freq(&self, device: &JetsonDevice) -> Result<u64> {
+        let output = device
+            .read_sysfs(&format!("{GPU_DEVFREQ}/max_freq"))
+            .await
+            .map_err(|e| e.with_subsystem(Subsystem::Power))?;
+        output.parse().map_err(|_| Error::Parse {
+            context: "gpu max_freq".to_string(),
+            message: format!("invalid frequency: {}", output.trim()),
+        })
+    }
+
+    /// Lock the EMC (memory) clock at its maximum rate.
+    ///
+    /// Equivalent to the EMC part of `jetson_clocks`, leaving GPU and CPU
+    /// clocks untouched.
+    ///
+    /// # Errors
+    ///
+    /// Returns `Error::SubsystemUnavailable` if BPMP debugfs is not mounted
+    /// or not writable (requires root).
+    pub async fn set_emc_max(&self, device: &JetsonDevice) -> Result<()> {
+        device
+            .exec(&format!(
+                "sudo sh -c 'cat {EMC_CLK}/max_rate > {EMC_CLK}/rate && echo 1 > {EMC_CLK}/mrq_rate_locked'"
+            ))
+            .await
+            .map(|_| ())
+            .map_err(|e| sysfs_error(EMC_CLK, e).with_subsystem(Subsystem::Power))
+    }
+
+    /// Current EMC clock frequency in MHz.
+    ///
+    /// # Errors
+    ///
+    /// Returns `Error::SubsystemUnavailable` if BPMP debugfs is not readable,
+    /// or `Error::Parse` if the rate is not a number.
+    pub async fn emc_freq(&self, device: &JetsonDevice) -> Result<u32> {
+        let output = device
+            .read_sysfs(&format!("{EMC_CLK}/rate"))
+            .await
+            .map_err(|e| e.with_subsystem(Subsystem::Power))?;
+        parse_emc_rate(&output)
+    }
+
+    /// Apply a power profile: nvpmodel mode, `jetson_clocks` if enabled,
+    /// then fan duty.
+    ///
+    /// # Errors
+    ///
+    /// Returns an error if any step is rejected.
+    pub async fn apply_profile(&self, device: &JetsonDevice, profile: &PowerProfile) -> Result<()> {
+        self.set_mode(device, profile.mode).await?;
+        if profile.enable_clocks {
+            device
+                .exec("sudo jetson_clocks")
+                .await
+                .map_err(|e| e.with_subsystem(Subsystem::JetsonClocks))?;
+        }
+        FanController::new()
+            .set_speed(device, profile.fan_speed)
+            .await
+    }
+
+    /// Read back the mode, GPU clock lock and fan duty.
+    ///
+    /// # Errors
+    ///
+    /// Returns an error if any of them cannot be read.
+    pub async fn read_profile(&self, device: &JetsonDevice) -> Result<AppliedProfile> {
+        let mode = self.current_mode(device).await?;
+        let freqs = device
+            .read_sysfs_many(&[
+                &format!("{GPU_DEVFREQ}/min_freq"),
+                &format!("{GPU_DEVFREQ}/max_freq"),
+            ])
+            .await?;
+        let fan_pwm = FanController::new().pwm(device).await?;
+        Ok(AppliedProfile {
+            mode,
+            clocks_locked: freqs[0] == freqs[1],
+            fan_pwm,
+        })
+    }
+
+    /// Check a device took `profile`, returning what was read back.
+    ///
+    /// Catches nvpmodel silently refusing a mode (e.g. one the module does
+    /// not support). Clocks are only checked when the profile enables them.
+    ///
+    /// # Errors
+    ///
+    /// Returns `Error::PowerMode` listing every mismatch, or an error if the
+    /// state cannot be read.
+    pub async fn verify_profile(
+        &self,
+        device: &JetsonDevice,
+        profile: &PowerProfile,
+    ) -> Result<AppliedProfile> {
+        let applied = self.read_profile(device).await?;
+        let mut mismatches = Vec::new();
+        if applied.mode != profile.mode {
+            mismatches.push(format!("mode {} (expected {})", applied.mode, profile.mode));
+        }
+        if profile.enable_clocks && !applied.clocks_locked {
+            mismatches.push("GPU clocks not locked".to_string());
+        }
+        if applied.fan_pwm != profile.fan_speed.to_pwm() {
+            mismatches.push(format!(
+                "fan pwm {} (expected {})",
+                applied.fan_pwm,
+                profile.fan_speed.to_pwm()
+            ));
+        }
+        if mismatches.is_empty() {
+            Ok(applied)
+        } else {
+            Err(Error::PowerMode(format!(
+                "{}: profile not applied: {}",
+                device.id(),
+                mismatches.join(", ")
+            )))
+        }
+    }
+
+    /// Verify the power mode holds over a window.
+    ///
+    /// Samples the nvpmodel mode and GPU frequency cap several times across
+    /// `duration`, the first at its start and the last at its end. Returns
+    /// `false` if the mode leaves `expected` or the cap moves, which
+    /// indicates a service (e.g. nvpmodel-boot) reverting it.
+    ///
+    /// # Errors
+    ///
+    /// Returns an error if the mode or clocks cannot be read.
+    pub async fn verify_stable(
+        &self,
+        device: &JetsonDevice,
+        expected: PowerMode,
+        duration: Duration,
+    ) -> Result<bool> {
+        let interval = duration / (self.stability_samples - 1);
+        let mut baseline_freq = None;
+
+        for sample in 0..self.stability_samples {
+            if sample > 0 {
+                tokio::time::sleep(interval).await;
+            }
+            let mode = self.current_mode(device).await?;
+            let freq = self.gpu_max_freq(device).await?;
+            let freq_changed = baseline_freq.is_some_and(|f| f != freq);
+            if mode != expected || freq_changed {
+                tracing::warn!(
+                    device = %device.id(),
+                    expected = %expected,
+                    actual = %mode,
+                    gpu_max_freq = freq,
+                    "Power mode changed underneath us"
+                );
+                return Ok(false);
+            }
+            baseline_freq = Some(freq);
+        }
+
+        Ok(true)
+    }
+}
+
+impl Default for PowerController {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+/// Parse `nvpmodel -q` output into a power mode.
+///
+/// The mode ID is printed on the line after `NV Power Mode: <name>`.
+fn parse_nvpmodel_query(output: &str) -> Result<PowerMode> {
+    output
+        .lines()
+        .rev()
+        .find_map(|line| line.trim().parse::<u8>().ok())
+        .map(PowerMode::from_mode_id)
+        .ok_or_else(|| Error::Parse {
+            context: "nvpmodel -q".to_string(),
+            message: format!("no mode ID in output: {}", output.trim()),
+        })
+}
+
+/// PWM fan controller for a device.
+#[derive(Debug, Clone, Copy)]
+pub struct FanController {
+    pwm_path: &'static str,
+}
+
+impl FanController {
+    /// Create a fan controller for the NVIDIA devkit fan node.
+    #[must_use]
+    pub const fn new() -> Self {
+        Self { pwm_path: FAN_PWM }
+    }
+
+    /// Create a fan controller for a carrier board's fan header.
+    ///
+    /// Returns `None` if the carrier has no PWM-controlled fan.
+    #[must_use]
+    pub const fn for_carrier(carrier: CarrierBoard) -> Option<Self> {
+        match carrier.fan_pwm_path() {
+            Some(pwm_path) => Some(Self { pwm_path }),
+            None => None,
+        }
+    }
+
+    /// PWM node this controller drives.
+    #[must_use]
+    pub const fn pwm_path(&self) -> &'static str {
+        self.pwm_path
+    }
+
+    /// Current fan PWM duty (0-255).
+    ///
+    /// # Errors
+    ///
+    /// Returns an error if the PWM node cannot be read.
+    pub async fn pwm(&self, device: &JetsonDevice) -> Result<u8> {
+        let output = device
+            .read_sysfs(self.pwm_path)
+            .await
+            .map_err(|e| e.with_subsystem(Subsystem::Power))?;
+        output.parse().map_err(|_| Error::Parse {
+            context: "fan pwm".to_string(),
+            message: format!("invalid duty: {}", output.trim()),
+        })
+    }
+
+    /// Set fan PWM duty (0-255).
+    ///
+    /// # Errors
+    ///
+    /// Returns an error if the PWM node cannot be written.
+    pub async fn set_pwm(&self, device: &JetsonDevice, duty: u8) -> Result<()> {
+        device
+            .write_sysfs(self.pwm_path, &duty.to_string())
+            .await
+            .map_err(|e| e.with_subsystem(Subsystem::Power))
+    }
+
+    /// Current fan speed.
+    ///
+    /// # Errors
+    ///
+    /// Returns an error if the PWM node cannot be read.
+    pub async fn speed(&self, device: &JetsonDevice) -> Result<FanSpeed> {
+        self.pwm(device).await.map(FanSpeed::from_pwm)
+    }
+
+    /// Set fan speed.
+    ///
+    /// # Errors
+    ///
+    /// Returns an error if the PWM node cannot be written.
+    pub async fn set_speed(&self, device: &JetsonDevice, speed: FanSpeed) -> Result<()> {
+        self.set_pwm(device, speed.to_pwm()).await
+    }
+}
+
+impl Default for FanController {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+/// Future returned by [`PowerSwitch::power_cycle`].
+pub type PowerCycleFuture<'a> = Pin<Box<dyn Future<Output = Result<()>> + Send + 'a>>;
+
+/// Remote power control used to hard-reboot hung devices.
+pub trait PowerSwitch: std::fmt::Debug + Send + Sync {
+    /// Cut power to a device, then restore it.
+    ///
+    /// # Errors
+    ///
+    /// Returns an error if the device has no power control or switching fails.
+    fn power_cycle<'a>(&'a self, device_id: &'a str) -> PowerCycleFuture<'a>;
+
+    /// Configuration of a device's power control, if it has one.
+    fn power_control(&self, device_id: &str) -> Option<PowerControlConfig> {
+        let _ = device_id;
+        None
+    }
+}
+
+/// Default time a device is left unpowered during a power cycle.
+pub const DEFAULT_POWER_OFF_TIME: Duration = Duration::from_secs(5);
+
+/// Power switch driven by per-device PDU outlets or GPIO relays.
+///
+/// PDU requests are sent with `curl` from the local host; GPIO relays are
+/// switched with `gpioset` on the host that drives them.
+#[derive(Debug, Clone)]
+pub struct ConfiguredPowerSwitch {
+    controls: HashMap<String, PowerControlConfig>,
+    local: Arc<dyn CommandExecutor>,
+    hosts: HashMap<String, Arc<dyn CommandExecutor>>,
+    off_time: Duration,
+}
+
+impl ConfiguredPowerSwitch {
+    /// Create a switch with no devices.
+    #[must_use]
+    pub fn new() -> Self {
+        Self {
+            controls: HashMap::new(),
+            local: Arc::new(LocalExecutor),
+            hosts: HashMap::new(),
+            off_time: DEFAULT_POWER_OFF_TIME,
+        }
+    }
+
+    /// Build from the `power` entries of the fleet configuration.
+    #[must_use]
+    pub fn from_config(config: &CoheteConfig) -> Self {
+        config
+            .fleet
+            .devices
+            .iter()
+            .filter_map(|dev| Some((dev.id.clone(), dev.power.clone()?)))
+            .fold(Self::new(), |switch, (id, control)| {
+                switch.with_control(id, control)
+            })
+    }
+
+    /// Set the power control for a device.
+    #[must_use]
+    pub fn with_control(
+        mut self,
+        device_id: impl Into<String>,
+        control: PowerControlConfig,
+    ) -> Self {
+        self.controls.insert(device_id.into(), control);
+        self
+    }
+
+    /// Set the transport for PDU requests (defaults to the local shell).
+    #[must_use]
+    pub fn with_local_executor(mut self, executor: Arc<dyn CommandExecutor>) -> Self {
+        self.local = executor;
+        self
+    }
+
+    /// Set the transport for a GPIO host (defaults to SSH).
+    #[must_use]
+    pub fn with_host_executor(
+        mut self,
+        host: impl Into<String>,
+        executor: Arc<dyn CommandExecutor>,
+    ) -> Self {
+        self.hosts.insert(host.into(), executor);
+        self
+    }
+
+    /// Set how long devices stay unpowered.
+    #[must_use]
+    pub const fn with_off_time(mut self, off_time: Duration) -> Self {
+        self.off_time = off_time;
+        self
+    }
+
+    /// Whether a device has power control configured.
+    #[must_use]
+    pub fn controls(&self, device_id: &str) -> bool {
+        self.controls.contains_key(device_id)
+    }
+
+    /// Transport and off/on commands for a device.
+    fn commands(&self, device_id: &str) -> Result<(Arc<dyn CommandExecutor>, [String; 2])> {
+        match self.controls.get(device_id) {
+            Some(PowerControlConfig::Pdu { url, outlet }) => {
+                let url = url.trim_end_matches('/');
+                let command = |state| {
+                    format!(
+                        "curl -sf -X POST {}",
+                        shell_quote(&format!("{url}/outlets/{outlet}/{state}"))
+                    )
+                };
+                Ok((self.local.clone(), [command("off"), command("on")]))
+            }
+            Some(PowerControlConfig::Gpio { host, chip, line }) => {
+                let executor = self.hosts.get(host).cloned().unwrap_or_else(|| {
+                    Arc::new(SshExecutor::new(host.clone())) as Arc<dyn CommandExecutor>
+                });
+                let chip = shell_quote(chip);
+                Ok((
+                    executor,
+                    [
+                        format!("gpioset {chip} {line}=0"),
+                        format!("gpioset {chip} {line}=1"),
+                    ],
+                ))
+            }
+            None => Err(Error::Config(format!(
+                "device {device_id}: no power control configured"
+            ))),
+        }
+    }
+}
+
+impl Default for ConfiguredPowerSwitch {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+impl PowerSwitch for ConfiguredPowerSwitch {
+    fn power_cycle<'a>(&'a self, device_id: &'a str) -> PowerCycleFuture<'a> {
+        Box::pin(async move {
+            let (executor, [off, on]) = self.commands(device_id)?;
+            // curl and gpioset block until the switch answers
+            let switch = |command: String| {
+                let executor = Arc::clone(&executor);
+                blocking(move || executor.run(&command)?.into_stdout(&command).map(|_| ()))
+            };
+            tracing::warn!(device = device_id, "Power cycling device");
+            switch(off).await?;
+            tokio::time::sleep(self.off_time).await;
+            switch(on).await
+        })
+    }
+
+    fn power_control(&self, device_id: &str) -> Option<PowerControlConfig> {
+        self.controls.get(device_id).cloned()
+    }
+}
+
+/// Parse an EMC rate in Hz into MHz.
+fn parse_emc_rate(output: &str) -> Result<u32> {
+    output
+        .trim()
+        .parse::<u64>()
+        .ok()
+        .and_then(|hz| u32::try_from(hz / 1_000_000).ok())
+        .ok_or_else(|| Error::Parse {
+            context: "emc rate".to_string(),
+            message: format!("invalid frequency: {}", output.trim()),
+        })
+}
+
 #[cfg(test)]
 mod tests {
     use super::*;
+    use crate::device::mock::{self, MockExecutor};
+    use std::sync::Arc;
+
+    const MAXN: &str = "NV Power Mode: MAXN\n0\n";
+    const MODE_15W: &str = "NV Power Mode: 15W\n1\n";
 
     #[test]
     fn test_power_mode_id() {
@@ -225,7 +889,7 @@ mod tests {
         let profile = PowerProfile::max_performance();
         assert_eq!(profile.mode, PowerMode::Maxn);
         assert!(profile.enable_clocks);
-        assert_eq!(profile.fan_speed, 255);
+        assert_eq!(profile.fan_speed.to_pwm(), 255);
     }
 
     #[test]
@@ -233,7 +897,7 @@ mod tests {
         let profile = PowerProfile::balanced();
         assert_eq!(profile.mode, PowerMode::Power15W);
         assert!(!profile.enable_clocks);
-        assert_eq!(profile.fan_speed, 128);
+        assert_eq!(profile.fan_speed.to_pwm(), 128);
     }
 
     #[test]
@@ -241,7 +905,7 @@ mod tests {
         let profile = PowerProfile::power_saver();
         assert_eq!(profile.mode, PowerMode::Power7W);
         assert!(!profile.enable_clocks);
-        assert_eq!(profile.fan_speed, 64);
+        assert_eq!(profile.fan_speed.to_pwm(), 64);
     }
 
     #[test]
@@ -249,7 +913,7 @@ mod tests {
         let profile = PowerProfile::default();
         assert_eq!(profile.mode, PowerMode::Power15W);
         assert!(!profile.enable_clocks);
-        assert_eq!(profile.fan_speed, 128);
+        assert_eq!(profile.fan_speed.to_pwm(), 128);
     }
 
     #[test]
@@ -261,6 +925,66 @@ mod tests {
         assert_eq!(cloned.fan_speed, profile.fan_speed);
     }
 
+    #[test]
+    fn test_fan_speed_percent_boundaries() {
+        assert_eq!(FanSpeed::from_percent(0).to_pwm(), 0);
+        assert_eq!(FanSpeed::from_percent(50).to_pwm(), 128);
+        assert_eq!(FanSpeed::from_percent(100).to_pwm(), 255);
+        assert_eq!(FanSpeed::from_percent(150), FanSpeed::FULL);
+
+        assert_eq!(FanSpeed::from_pwm(0).to_percent(), 0);
+        assert_eq!(FanSpeed::from_pwm(128).to_percent(), 50);
+        assert_eq!(FanSpeed::from_pwm(255).to_percent(), 100);
+        assert_eq!(FanSpeed::from_pwm(128).to_string(), "50%");
+    }
+
+    #[test]
+    fn test_fan_speed_parse() {
+        assert_eq!("50%".parse::<FanSpeed>().unwrap().to_pwm(), 128);
+        assert_eq!("0%".parse::<FanSpeed>().unwrap(), FanSpeed::OFF);
+        assert_eq!("100 %".parse::<FanSpeed>().unwrap(), FanSpeed::FULL);
+        assert_eq!("200".parse::<FanSpeed>().unwrap().to_pwm(), 200);
+        assert!("101%".parse::<FanSpeed>().is_err());
+        assert!("256".parse::<FanSpeed>().is_err());
+        assert!("fast".parse::<FanSpeed>().is_err());
+    }
+
+    #[test]
+    fn test_fan_speed_yaml() {
+        let speeds: Vec<FanSpeed> = serde_yaml::from_str("[50%, 200, \"100%\"]").unwrap();
+        assert_eq!(
+            speeds.iter().map(|s| s.to_pwm()).collect::<Vec<_>>(),
+            [128, 200, 255]
+        );
+        assert!(serde_yaml::from_str::<FanSpeed>("120%").is_err());
+
+        let yaml = serde_yaml::to_string(&[FanSpeed::from_percent(50), FanSpeed::from_pwm(200)]);
+        assert_eq!(yaml.unwrap().trim(), "- 50%\n- 200");
+    }
+
+    #[tokio::test]
+    async fn test_fan_controller_set_speed() {
+        let exec = Arc::new(MockExecutor::new().on("cat /sys/devices/platform/pwm-fan", "191\n"));
+        let device = mock::device("j1", crate::JetsonModel::OrinNano8GB, &exec);
+        let fan = FanController::new();
+
+        fan.set_speed(&device, FanSpeed::from_percent(75))
+            .await
+            .unwrap();
+        assert!(exec.calls().iter().any(|c| c.starts_with("echo 191 |")));
+        assert_eq!(fan.speed(&device).await.unwrap().to_percent(), 75);
+    }
+
+    #[test]
+    fn test_power_mode_steps() {
+        assert_eq!(PowerMode::Maxn.step_down(), Some(PowerMode::Power15W));
+        assert_eq!(PowerMode::Power15W.step_down(), Some(PowerMode::Power7W));
+        assert_eq!(PowerMode::Power7W.step_down(), None);
+        assert_eq!(PowerMode::Power7W.step_up(), Some(PowerMode::Power15W));
+        assert_eq!(PowerMode::Maxn.step_up(), None);
+        assert_eq!(PowerMode::Custom(4).step_down(), None);
+    }
+
     #[test]
     fn test_power_mode_hash() {
         use std::collections::HashSet;
@@ -271,4 +995,331 @@ mod tests {
         set.insert(PowerMode::Power7W);
         assert_eq!(set.len(), 2);
     }
+
+    #[test]
+    fn test_power_mode_from_mode_id() {
+        assert_eq!(PowerMode::from_mode_id(0), PowerMode::Maxn);
+        assert_eq!(PowerMode::from_mode_id(1), PowerMode::Power15W);
+        assert_eq!(PowerMode::from_mode_id(2), PowerMode::Power7W);
+        assert_eq!(PowerMode::from_mode_id(7), PowerMode::Custom(7));
+    }
+
+    #[test]
+    fn test_parse_nvpmodel_query() {
+        assert_eq!(parse_nvpmodel_query(MAXN).unwrap(), PowerMode::Maxn);
+        assert_eq!(parse_nvpmodel_query(MODE_15W).unwrap(), PowerMode::Power15W);
+        assert!(parse_nvpmodel_query("NV Power Mode: MAXN").is_err());
+    }
+
+    #[tokio::test]
+    async fn test_verify_stable_holds() {
+        let exec = Arc::new(
+            MockExecutor::new()
+                .on("nvpmodel -q", MAXN)
+                .on("max_freq", "918000000\n"),
+        );
+        let device = mock::device("j1", crate::JetsonModel::OrinNano8GB, &exec);
+        let controller = PowerController::new();
+
+        let stable = controller
+            .verify_stable(&device, PowerMode::Maxn, Duration::from_millis(20))
+            .await
+            .unwrap();
+        assert!(stable);
+        assert_eq!(exec.count("nvpmodel -q"), 5);
+    }
+
+    #[tokio::test]
+    async fn test_verify_stable_covers_window() {
+        let exec = Arc::new(
+            MockExecutor::new()
+                .on("nvpmodel -q", MAXN)
+                .on("max_freq", "918000000\n"),
+        );
+        let device = mock::device("j1", crate::JetsonModel::OrinNano8GB, &exec);
+        let window = Duration::from_millis(200);
+
+        let started = std::time::Instant::now();
+        let stable = PowerController::new()
+            .verify_stable(&device, PowerMode::Maxn, window)
+            .await
+            .unwrap();
+        assert!(stable);
+        assert!(started.elapsed() >= window);
+    }
+
+    #[tokio::test]
+    async fn test_verify_stable_mode_reverted() {
+        let exec = Arc::new(
+            MockExecutor::new()
+                .on_seq("nvpmodel -q", &[MAXN, MAXN, MODE_15W])
+                .on("max_freq", "918000000\n"),
+        );
+        let device = mock::device("j1", crate::JetsonModel::OrinNano8GB, &exec);
+        let controller = PowerController::new();
+
+        let stable = controller
+            .verify_stable(&device, PowerMode::Maxn, Duration::from_millis(20))
+            .await
+            .unwrap();
+        assert!(!stable);
+        assert_eq!(exec.count("nvpmodel -q"), 3);
+    }
+
+    #[tokio::test]
+    async fn test_verify_stable_clock_cap_changed() {
+        let exec = Arc::new(
+            MockExecutor::new()
+                .on("nvpmodel -q", MAXN)
+                .on_seq("max_freq", &["918000000\n", "612000000\n"]),
+        );
+        let device = mock::device("j1", crate::JetsonModel::OrinNano8GB, &exec);
+
+        let stable = PowerController::new()
+            .verify_stable(&device, PowerMode::Maxn, Duration::from_millis(10))
+            .await
+            .unwrap();
+        assert!(!stable);
+    }
+
+    #[tokio::test]
+    async fn test_set_mode_command() {
+        let exec = Arc::new(MockExecutor::new());
+        let device = mock::device("j1", crate::JetsonModel::OrinNano8GB, &exec);
+        PowerController::new()
+            .set_mode(&device, PowerMode::Power7W)
+            .await
+            .unwrap();
+        assert_eq!(exec.calls(), vec!["sudo nvpmodel -m 2".to_string()]);
+    }
+
+    #[tokio::test]
+    async fn test_set_mode_failure_tagged_nvpmodel() {
+        let exec = Arc::new(MockExecutor::new().fail(
+            "nvpmodel -m",
+            255,
+            "ssh: connect to host j1: Connection reset",
+        ));
+        let device = mock::device("j1", crate::JetsonModel::OrinNano8GB, &exec);
+        let err = PowerController::new()
+            .set_mode(&device, PowerMode::Maxn)
+            .await
+            .unwrap_err();
+        assert_eq!(err.subsystem(), Some(Subsystem::NvpModel));
+        // The transport failure underneath stays visible to retry logic
+        assert!(matches!(
+            err.untagged(),
+            Error::CommandFailed { exit_code: 255, .. }
+        ));
+    }
+
+    #[tokio::test]
+    async fn test_jetson_clocks_failure_tagged() {
+        let exec = Arc::new(MockExecutor::new().fail(
+            "jetson_clocks",
+            1,
+            "sudo: jetson_clocks: command not found",
+        ));
+        let device = mock::device("j1", crate::JetsonModel::OrinNano8GB, &exec);
+        let err = PowerController::new()
+            .apply_profile(&device, &PowerProfile::max_performance())
+            .await
+            .unwrap_err();
+        assert_eq!(err.subsystem(), Some(Subsystem::JetsonClocks));
+        assert!(matches!(err.untagged(), Error::CommandFailed { .. }));
+    }
+
+    #[tokio::test]
+    async fn test_clock_and_fan_failures_tagged_power() {
+        let exec = Arc::new(
+            MockExecutor::new()
+                .fail("emc", 1, "sh: write error: Input/output error")
+                .fail("devfreq", 1, "cat: read error: Input/output error")
+                .fail("pwm-fan", 1, "tee: write error: Input/output error"),
+        );
+        let device = mock::device("j1", crate::JetsonModel::OrinNano8GB, &exec);
+        let controller = PowerController::new();
+        let fan = FanController::new();
+
+        let errors = [
+            controller.set_emc_max(&device).await.unwrap_err(),
+            controller.emc_freq(&device).await.unwrap_err(),
+            controller.gpu_max_freq(&device).await.unwrap_err(),
+            fan.pwm(&device).await.unwrap_err(),
+            fan.set_pwm(&device, 255).await.unwrap_err(),
+        ];
+        for err in errors {
+            assert_eq!(err.subsystem(), Some(Subsystem::Power), "{err}");
+            assert!(
+                matches!(err.untagged(), Error::CommandFailed { .. }),
+                "{err}"
+            );
+        }
+    }
+
+    #[test]
+    fn test_parse_emc_rate() {
+        assert_eq!(parse_emc_rate("3199000000\n").unwrap(), 3199);
+        assert_eq!(parse_emc_rate("2133000000").unwrap(), 2133);
+        assert!(parse_emc_rate("").is_err());
+        assert!(parse_emc_rate("fast").is_err());
+    }
+
+    #[tokio::test]
+    async fn test_set_emc_max_command() {
+        let exec = Arc::new(MockExecutor::new());
+        let device = mock::device("j1", crate::JetsonModel::OrinNano8GB, &exec);
+        PowerController::new().set_emc_max(&device).await.unwrap();
+
+        let calls = exec.calls();
+        assert_eq!(calls.len(), 1);
+        assert!(calls[0].starts_with("sudo sh -c"));
+        assert!(calls[0].contains("cat /sys/kernel/debug/bpmp/debug/clk/emc/max_rate > /sys/kernel/debug/bpmp/debug/clk/emc/rate"));
+        assert!(calls[0].contains("echo 1 > /sys/kernel/debug/bpmp/debug/clk/emc/mrq_rate_locked"));
+    }
+
+    #[tokio::test]
+    async fn test_emc_freq() {
+        let exec = Arc::new(MockExecutor::new().on("emc/rate", "3199000000\n"));
+        let device = mock::device("j1", crate::JetsonModel::OrinNano8GB, &exec);
+        let mhz = PowerController::new().emc_freq(&device).await.unwrap();
+        assert_eq!(mhz, 3199);
+        assert_eq!(
+            exec.calls(),
+            vec!["sudo cat /sys/kernel/debug/bpmp/debug/clk/emc/rate".to_string()]
+        );
+    }
+
+    #[tokio::test]
+    async fn test_emc_permission_denied() {
+        let exec = Arc::new(MockExecutor::new().fail(
+            "emc",
+            1,
+            "cat: /sys/kernel/debug/bpmp/debug/clk/emc/rate: Permission denied",
+        ));
+        let device = mock::device("j1", crate::JetsonModel::OrinNano8GB, &exec);
+        let err = PowerController::new().emc_freq(&device).await.unwrap_err();
+        assert!(matches!(
+            err,
+            Error::SubsystemUnavailable {
+                subsystem: Subsystem::Power,
+                ..
+            }
+        ));
+        assert!(err.to_string().contains("debugfs"));
+    }
+
+    #[tokio::test]
+    async fn test_fan_controller() {
+        let exec = Arc::new(MockExecutor::new().on("cat /sys/devices/platform/pwm-fan", "120\n"));
+        let device = mock::device("j1", crate::JetsonModel::OrinNano8GB, &exec);
+        let fan = FanController::new();
+        assert_eq!(fan.pwm(&device).await.unwrap(), 120);
+        fan.set_pwm(&device, 255).await.unwrap();
+        assert_eq!(
+            exec.calls()[1],
+            "echo 255 | sudo tee /sys/devices/platform/pwm-fan/hwmon/hwmon*/pwm1 > /dev/null"
+        );
+    }
+
+    #[tokio::test]
+    async fn test_verify_profile_lists_mismatches() {
+        let exec = Arc::new(
+            MockExecutor::new()
+                .on("nvpmodel -q", MAXN)
+                .on("devfreq", "306000000\n\x1e1020000000\n\x1e")
+                .on("cat /sys/devices/platform/pwm-fan", "120\n"),
+        );
+        let device = mock::device("j1", crate::JetsonModel::OrinNano8GB, &exec);
+        let controller = PowerController::new();
+
+        let applied = controller.read_profile(&device).await.unwrap();
+        assert!(!applied.clocks_locked);
+        let err = controller
+            .verify_profile(&device, &PowerProfile::max_performance())
+            .await
+            .unwrap_err();
+        let message = err.to_string();
+        assert!(message.contains("GPU clocks not locked"), "{message}");
+        assert!(message.contains("fan pwm 120 (expected 255)"), "{message}");
+        assert!(!message.contains("expected MAXN"), "{message}");
+    }
+
+    fn pdu(url: &str, outlet: u32) -> PowerControlConfig {
+        PowerControlConfig::Pdu {
+            url: url.to_string(),
+            outlet,
+        }
+    }
+
+    #[tokio::test]
+    async fn test_power_cycle_toggles_mapped_outlet() {
+        let local = Arc::new(MockExecutor::new());
+        let switch = ConfiguredPowerSwitch::new()
+            .with_control("j1", pdu("http://pdu.lab/", 3))
+            .with_control("j2", pdu("http://pdu.lab", 7))
+            .with_local_executor(local.clone())
+            .with_off_time(Duration::ZERO);
+
+        switch.power_cycle("j2").await.unwrap();
+        assert_eq!(
+            local.calls(),
+            vec![
+                "curl -sf -X POST 'http://pdu.lab/outlets/7/off'",
+                "curl -sf -X POST 'http://pdu.lab/outlets/7/on'",
+            ]
+        );
+
+        let err = switch.power_cycle("j9").await.unwrap_err();
+        assert!(matches!(err, Error::Config(_)));
+    }
+
+    #[tokio::test]
+    async fn test_power_cycle_gpio_and_failure() {
+        let relay_host = Arc::new(MockExecutor::new());
+        let local = Arc::new(MockExecutor::new().fail("/off", 22, "outlet busy"));
+        let switch = ConfiguredPowerSwitch::new()
+            .with_control(
+                "j1",
+                PowerControlConfig::Gpio {
+                    host: "pi-relay".to_string(),
+                    chip: "gpiochip0".to_string(),
+                    line: 17,
+                },
+            )
+            .with_control("j2", pdu("http://pdu.lab", 1))
+            .with_host_executor("pi-relay", relay_host.clone())
+            .with_local_executor(local.clone())
+            .with_off_time(Duration::ZERO);
+
+        switch.power_cycle("j1").await.unwrap();
+        assert_eq!(
+            relay_host.calls(),
+            vec!["gpioset 'gpiochip0' 17=0", "gpioset 'gpiochip0' 17=1"]
+        );
+
+        // Never power back on after a failed power-off
+        assert!(switch.power_cycle("j2").await.is_err());
+        assert_eq!(local.count("/on"), 0);
+    }
+
+    #[test]
+    fn test_power_switch_from_config() {
+        let config = CoheteConfig::from_yaml(
+            r"
+fleet:
+  devices:
+    - id: j1
+      power:
+        type: pdu
+        url: http://pdu.lab
+        outlet: 4
+    - id: j2
+",
+        )
+        .unwrap();
+        let switch = ConfiguredPowerSwitch::from_config(&config);
+        assert!(switch.controls("j1"));
+        assert!(!switch.controls("j2"));
+    }
 }
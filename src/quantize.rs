@@ -2,10 +2,32 @@
 //!
 //! Provides memory-aware quantization for edge deployment.
 
-use crate::{memory::MemoryBudget, Result};
+use crate::{
+    device::{shell_quote, CommandExecutor, LocalExecutor},
+    memory::MemoryBudget,
+    Error, JetsonModel, Result,
+};
+use serde::{Deserialize, Serialize};
+use std::collections::HashMap;
+use std::io::Read;
+use std::path::{Path, PathBuf};
+
+/// F16 decode latency per billion parameters on a 40 TOPS device (ms/token).
+const F16_MS_PER_BILLION_AT_40_TOPS: f64 = 35.0;
+
+/// Levels in order of decreasing quality, as considered for selection.
+const SELECTION_ORDER: [QuantLevel; 6] = [
+    QuantLevel::F16,
+    QuantLevel::Q8_0,
+    QuantLevel::Q5_1,
+    QuantLevel::Q5_0,
+    QuantLevel::Q4_1,
+    QuantLevel::Q4_0,
+];
 
 /// Quantization levels (compatible with llama.cpp).
-#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash, Serialize, Deserialize)]
+#[serde(rename_all = "lowercase")]
 pub enum QuantLevel {
     /// 4-bit quantization (type 0)
     Q4_0,
@@ -24,6 +46,21 @@ pub enum QuantLevel {
 }
 
 impl QuantLevel {
+    /// Level of a llama.cpp file type (GGUF `general.file_type`).
+    #[must_use]
+    pub const fn from_ftype(ftype: u32) -> Option<Self> {
+        match ftype {
+            0 => Some(Self::F32),
+            1 => Some(Self::F16),
+            2 => Some(Self::Q4_0),
+            3 => Some(Self::Q4_1),
+            7 => Some(Self::Q8_0),
+            8 => Some(Self::Q5_0),
+            9 => Some(Self::Q5_1),
+            _ => None,
+        }
+    }
+
     /// Bits per parameter.
     #[must_use]
     pub const fn bits_per_param(&self) -> u8 {
@@ -62,6 +99,23 @@ impl QuantLevel {
         }
     }
 
+    /// Approximate decode throughput relative to F16.
+    ///
+    /// Token generation is memory-bandwidth bound, so smaller weights
+    /// decode faster; dequantization overhead keeps the gain sublinear.
+    #[must_use]
+    pub const fn relative_speed(&self) -> f32 {
+        match self {
+            Self::Q4_0 => 3.0,
+            Self::Q4_1 => 2.8,
+            Self::Q5_0 => 2.5,
+            Self::Q5_1 => 2.4,
+            Self::Q8_0 => 1.8,
+            Self::F16 => 1.0,
+            Self::F32 => 0.5,
+        }
+    }
+
     /// String representation.
     #[must_use]
     pub const fn as_str(&self) -> &'static str {
@@ -75,6 +129,37 @@ impl QuantLevel {
             Self::F32 => "f32",
         }
     }
+
+    /// Next level down in memory footprint, or `None` at `Q4_0`.
+    #[must_use]
+    pub const fn next_lower(&self) -> Option<Self> {
+        match self {
+            Self::F32 => Some(Self::F16),
+            Self::F16 => Some(Self::Q8_0),
+            Self::Q8_0 => Some(Self::Q5_1),
+            Self::Q5_1 => Some(Self::Q5_0),
+            Self::Q5_0 => Some(Self::Q4_1),
+            Self::Q4_1 => Some(Self::Q4_0),
+            Self::Q4_0 => None,
+        }
+    }
+}
+
+impl std::str::FromStr for QuantLevel {
+    type Err = Error;
+
+    fn from_str(s: &str) -> Result<Self> {
+        match s.to_ascii_lowercase().as_str() {
+            "q4_0" => Ok(Self::Q4_0),
+            "q4_1" => Ok(Self::Q4_1),
+            "q5_0" => Ok(Self::Q5_0),
+            "q5_1" => Ok(Self::Q5_1),
+            "q8_0" => Ok(Self::Q8_0),
+            "f16" => Ok(Self::F16),
+            "f32" => Ok(Self::F32),
+            other => Err(Error::Quantization(format!("unknown level: {other}"))),
+        }
+    }
 }
 
 impl std::fmt::Display for QuantLevel {
@@ -114,27 +199,121 @@ impl JetsonQuantizer {
     }
 
     /// Select optimal quantization level for memory budget.
-    #[must_use]
-    pub fn select_for_budget(model_f16_size_mb: u64, budget: &MemoryBudget) -> QuantLevel {
+    ///
+    /// Best effort: returns `Q4_0` even if it does not fit. Use
+    /// [`Self::try_select_for_budget`] to reject impossible deployments.
+    ///
+    /// # Errors
+    ///
+    /// Returns `Error::Config` if the budget has no usable memory, as for an
+    /// unidentified model.
+    pub fn select_for_budget(model_f16_size_mb: u64, budget: &MemoryBudget) -> Result<QuantLevel> {
+        match Self::try_select_for_budget(model_f16_size_mb, budget) {
+            Err(Error::InsufficientMemory { .. }) => Ok(QuantLevel::Q4_0),
+            result => result,
+        }
+    }
+
+    /// Select the highest-quality level that fits the memory budget.
+    ///
+    /// # Errors
+    ///
+    /// Returns `Error::InsufficientMemory` with the `Q4_0` size if even the
+    /// most aggressive level does not fit, or `Error::Config` if the budget
+    /// has no usable memory.
+    #[allow(
+        clippy::cast_precision_loss,
+        clippy::cast_possible_truncation,
+        clippy::cast_sign_loss
+    )]
+    pub fn try_select_for_budget(
+        model_f16_size_mb: u64,
+        budget: &MemoryBudget,
+    ) -> Result<QuantLevel> {
+        Self::try_select_from(model_f16_size_mb, budget, &SELECTION_ORDER)
+    }
+
+    /// Select the highest-quality level among `levels` that fits the budget.
+    ///
+    /// # Errors
+    ///
+    /// Returns `Error::InsufficientMemory` with the smallest offered size if
+    /// none fits, `Error::Config` if the budget has no usable memory, or
+    /// `Error::Quantization` if `levels` is empty.
+    #[allow(
+        clippy::cast_precision_loss,
+        clippy::cast_possible_truncation,
+        clippy::cast_sign_loss
+    )]
+    pub fn try_select_from(
+        model_f16_size_mb: u64,
+        budget: &MemoryBudget,
+        levels: &[QuantLevel],
+    ) -> Result<QuantLevel> {
+        if budget.usable_mb() == 0 {
+            return Err(Error::Config(
+                "memory budget is zero; model memory unknown, set it explicitly".to_string(),
+            ));
+        }
         let available = budget.available_mb();
+        let estimate =
+            |level: QuantLevel| (model_f16_size_mb as f32 * level.memory_factor()) as u64;
 
         // Try each level from highest quality to lowest
-        for level in [
-            QuantLevel::F16,
-            QuantLevel::Q8_0,
-            QuantLevel::Q5_1,
-            QuantLevel::Q5_0,
-            QuantLevel::Q4_1,
-            QuantLevel::Q4_0,
-        ] {
-            let estimated_size = (model_f16_size_mb as f32 * level.memory_factor()) as u64;
-            if estimated_size <= available {
-                return level;
-            }
+        let mut offered: Vec<QuantLevel> = levels.to_vec();
+        offered.sort_by(|a, b| b.memory_factor().total_cmp(&a.memory_factor()));
+        let smallest = offered
+            .last()
+            .copied()
+            .ok_or_else(|| Error::Quantization("no quantization levels offered".to_string()))?;
+        offered
+            .into_iter()
+            .find(|&level| estimate(level) <= available)
+            .ok_or_else(|| Error::InsufficientMemory {
+                requested_mb: estimate(smallest),
+                available_mb: available,
+            })
+    }
+
+    /// Select the highest-quality level that fits memory and meets a latency SLA.
+    ///
+    /// Per-token latency is estimated from the model's TOPS and
+    /// [`QuantLevel::relative_speed`].
+    ///
+    /// # Errors
+    ///
+    /// Returns `Error::Quantization` if no level both fits `budget` and
+    /// decodes within `max_ms_per_token`, or the model's TOPS are unknown.
+    pub fn select_for_latency(
+        model: JetsonModel,
+        params_billions: f64,
+        budget: &MemoryBudget,
+        max_ms_per_token: f32,
+    ) -> Result<QuantLevel> {
+        if model.tops() == 0 {
+            return Err(Error::Quantization(format!(
+                "cannot estimate latency for {model}: unknown TOPS"
+            )));
         }
+        let available = budget.available_mb();
+        let f16_size_mb = params_billions * 2000.0;
+        let f16_ms =
+            params_billions * F16_MS_PER_BILLION_AT_40_TOPS * 40.0 / f64::from(model.tops());
 
-        // Default to most aggressive
-        QuantLevel::Q4_0
+        SELECTION_ORDER
+            .into_iter()
+            .find(|level| {
+                let size_mb = f16_size_mb * f64::from(level.memory_factor());
+                let ms = f16_ms / f64::from(level.relative_speed());
+                #[allow(clippy::cast_precision_loss)]
+                let fits = size_mb <= available as f64;
+                fits && ms <= f64::from(max_ms_per_token)
+            })
+            .ok_or_else(|| {
+                Error::Quantization(format!(
+                    "no level meets {max_ms_per_token}ms/token for a {params_billions}B model on {model}"
+                ))
+            })
     }
 
     /// Quantize model bytes.
@@ -153,6 +332,236 @@ impl JetsonQuantizer {
     }
 }
 
+/// Quantize a GGUF on this host into `out_dir`, one file per level.
+///
+/// Quantizing on the orchestrator spares the devices the time and memory;
+/// deploy the results with `Fleet::deploy_model_auto_quant`. Outputs are
+/// named `{stem}.{sha}.{level}.gguf` after the source's SHA-256, so a level
+/// already produced from the same source is reused, not requantized.
+///
+/// # Errors
+///
+/// Returns an error if the source cannot be hashed, `out_dir` cannot be
+/// created, or `llama-quantize` fails.
+pub fn quantize_for_fleet(
+    source_gguf: &Path,
+    levels: &[QuantLevel],
+    out_dir: &Path,
+) -> Result<HashMap<QuantLevel, PathBuf>> {
+    quantize_with(&LocalExecutor, source_gguf, levels, out_dir)
+}
+
+fn quantize_with(
+    executor: &dyn CommandExecutor,
+    source_gguf: &Path,
+    levels: &[QuantLevel],
+    out_dir: &Path,
+) -> Result<HashMap<QuantLevel, PathBuf>> {
+    let source = shell_quote(&source_gguf.to_string_lossy());
+    let command = format!("sha256sum {source}");
+    let digest = String::from_utf8_lossy(&executor.run(&command)?.into_stdout(&command)?)
+        .split_whitespace()
+        .next()
+        .filter(|d| d.len() >= 16)
+        .map(|d| d[..16].to_string())
+        .ok_or_else(|| Error::Quantization(format!("cannot hash {}", source_gguf.display())))?;
+    let stem = source_gguf
+        .file_stem()
+        .map_or_else(|| "model".into(), |s| s.to_string_lossy());
+    let stem = stem.trim_end_matches(".f16");
+
+    std::fs::create_dir_all(out_dir)?;
+    let mut outputs = HashMap::new();
+    for &level in levels {
+        let output = out_dir.join(format!("{stem}.{digest}.{level}.gguf"));
+        if output.exists() {
+            tracing::debug!(output = %output.display(), "Reusing cached quantization");
+        } else {
+            let partial = shell_quote(&format!("{}.partial", output.display()));
+            let command = format!(
+                "llama-quantize {source} {partial} {} && mv {partial} {}",
+                level.as_str().to_ascii_uppercase(),
+                shell_quote(&output.to_string_lossy())
+            );
+            executor.run(&command)?.into_stdout(&command)?;
+        }
+        outputs.insert(level, output);
+    }
+    Ok(outputs)
+}
+
+/// Magic bytes opening every GGUF file.
+const GGUF_MAGIC: &[u8; 4] = b"GGUF";
+
+/// Longest metadata string read into memory; longer ones are skipped.
+const GGUF_MAX_STRING: u64 = 1 << 16;
+
+/// Deepest nesting of metadata arrays accepted; real models use one level.
+const GGUF_MAX_ARRAY_DEPTH: usize = 8;
+
+/// Check that `path` is a GGUF file, optionally of a given architecture.
+///
+/// Reads the header only: magic, version (2 or 3) and, when
+/// `required_arch` is set, the `general.architecture` metadata key.
+///
+/// # Errors
+///
+/// Returns `Error::Quantization` if the file is not GGUF, has an unsupported
+/// version, or is for another architecture, or `Error::Io` if it cannot be
+/// read.
+pub fn validate_gguf(path: &Path, required_arch: Option<&str>) -> Result<()> {
+    let file = std::fs::File::open(path)?;
+    let mut reader = std::io::BufReader::new(file);
+    let invalid = |reason: String| Error::Quantization(format!("{}: {reason}", path.display()));
+
+    check_gguf_header(&mut reader).map_err(invalid)?;
+    let Some(required) = required_arch else {
+        return Ok(());
+    };
+    match find_gguf_key(&mut reader, "general.architecture")? {
+        Some(GgufValue::Str(arch)) if arch == required => Ok(()),
+        Some(GgufValue::Str(arch)) => Err(invalid(format!(
+            "architecture is '{arch}', expected '{required}'"
+        ))),
+        _ => Err(invalid(format!(
+            "no general.architecture, expected '{required}'"
+        ))),
+    }
+}
+
+/// Quant level of a GGUF model from its header's `general.file_type`.
+///
+/// `header` needs only the start of the file, up to that key.
+///
+/// # Errors
+///
+/// Returns `Error::Quantization` if `header` is not GGUF or has no file
+/// type matching a [`QuantLevel`], or an error if it is truncated first.
+pub fn gguf_quant_level(header: &[u8]) -> Result<QuantLevel> {
+    let mut reader = header;
+    let invalid = |reason: String| Error::Quantization(format!("GGUF header: {reason}"));
+    check_gguf_header(&mut reader).map_err(invalid)?;
+    match find_gguf_key(&mut reader, "general.file_type")? {
+        Some(GgufValue::U32(ftype)) => QuantLevel::from_ftype(ftype)
+            .ok_or_else(|| invalid(format!("file type {ftype} is not a supported level"))),
+        _ => Err(invalid("no general.file_type".to_string())),
+    }
+}
+
+/// Metadata value of a GGUF key.
+enum GgufValue {
+    Str(String),
+    U32(u32),
+    Other,
+}
+
+/// Check the magic and version, returning the reason a header is rejected.
+fn check_gguf_header(reader: &mut impl Read) -> std::result::Result<(), String> {
+    let mut magic = [0u8; 4];
+    reader
+        .read_exact(&mut magic)
+        .map_err(|_| "too short to be GGUF".to_string())?;
+    if &magic != GGUF_MAGIC {
+        return Err(format!("not a GGUF file (magic {magic:02x?})"));
+    }
+    let version = read_u32(reader).map_err(|e| e.to_string())?;
+    if !(2..=3).contains(&version) {
+        return Err(format!("unsupported GGUF version {version}"));
+    }
+    Ok(())
+}
+
+/// Walk the metadata after the header to the value of `key`.
+fn find_gguf_key(reader: &mut impl Read, key: &str) -> Result<Option<GgufValue>> {
+    let _tensor_count = read_u64(reader)?;
+    let kv_count = read_u64(reader)?;
+    for _ in 0..kv_count {
+        let name = read_gguf_string(reader)?;
+        let value_type = read_u32(reader)?;
+        if name.as_deref() == Some(key) {
+            return Ok(Some(match value_type {
+                GGUF_TYPE_STRING => GgufValue::Str(read_gguf_string(reader)?.unwrap_or_default()),
+                GGUF_TYPE_U32 => GgufValue::U32(read_u32(reader)?),
+                _ => GgufValue::Other,
+            }));
+        }
+        skip_gguf_value(reader, value_type, 0)?;
+    }
+    Ok(None)
+}
+
+/// GGUF metadata value type of 32-bit unsigned integers.
+const GGUF_TYPE_U32: u32 = 4;
+/// GGUF metadata value type of strings.
+const GGUF_TYPE_STRING: u32 = 8;
+/// GGUF metadata value type of arrays.
+const GGUF_TYPE_ARRAY: u32 = 9;
+
+fn read_u32(reader: &mut impl Read) -> Result<u32> {
+    let mut buf = [0u8; 4];
+    reader.read_exact(&mut buf)?;
+    Ok(u32::from_le_bytes(buf))
+}
+
+fn read_u64(reader: &mut impl Read) -> Result<u64> {
+    let mut buf = [0u8; 8];
+    reader.read_exact(&mut buf)?;
+    Ok(u64::from_le_bytes(buf))
+}
+
+/// Read a GGUF string; `None` if it is too long to be a key or name.
+fn read_gguf_string(reader: &mut impl Read) -> Result<Option<String>> {
+    let len = read_u64(reader)?;
+    if len > GGUF_MAX_STRING {
+        skip_bytes(reader, len)?;
+        return Ok(None);
+    }
+    let mut buf = vec![0u8; usize::try_from(len).unwrap_or_default()];
+    reader.read_exact(&mut buf)?;
+    Ok(Some(String::from_utf8_lossy(&buf).into_owned()))
+}
+
+fn skip_bytes(reader: &mut impl Read, len: u64) -> Result<()> {
+    let skipped = std::io::copy(&mut reader.take(len), &mut std::io::sink())?;
+    if skipped < len {
+        return Err(std::io::Error::from(std::io::ErrorKind::UnexpectedEof).into());
+    }
+    Ok(())
+}
+
+/// Skip a metadata value; `depth` counts the arrays it is nested in.
+fn skip_gguf_value(reader: &mut impl Read, value_type: u32, depth: usize) -> Result<()> {
+    let size = match value_type {
+        0 | 1 | 7 => 1,
+        2 | 3 => 2,
+        4..=6 => 4,
+        10..=12 => 8,
+        GGUF_TYPE_STRING => {
+            let len = read_u64(reader)?;
+            return skip_bytes(reader, len);
+        }
+        GGUF_TYPE_ARRAY => {
+            if depth >= GGUF_MAX_ARRAY_DEPTH {
+                return Err(Error::Quantization(format!(
+                    "GGUF metadata arrays nested deeper than {GGUF_MAX_ARRAY_DEPTH}"
+                )));
+            }
+            let item_type = read_u32(reader)?;
+            let len = read_u64(reader)?;
+            for _ in 0..len {
+                skip_gguf_value(reader, item_type, depth + 1)?;
+            }
+            return Ok(());
+        }
+        other => {
+            return Err(Error::Quantization(format!(
+                "unknown GGUF metadata type {other}"
+            )))
+        }
+    };
+    skip_bytes(reader, size)
+}
+
 /// Result of quantization operation.
 #[derive(Debug, Clone)]
 pub struct QuantResult {
@@ -195,6 +604,171 @@ mod tests {
         assert_eq!(QuantLevel::F16.memory_factor(), 1.0);
     }
 
+    #[test]
+    fn test_try_select_from_offered_levels() {
+        let budget = MemoryBudget::new(5000, 0);
+        let offered = [QuantLevel::Q4_0, QuantLevel::Q8_0];
+        let level = JetsonQuantizer::try_select_from(8000, &budget, &offered).unwrap();
+        assert_eq!(level, QuantLevel::Q8_0);
+
+        let budget = MemoryBudget::new(3000, 0);
+        let level = JetsonQuantizer::try_select_from(8000, &budget, &offered).unwrap();
+        assert_eq!(level, QuantLevel::Q4_0);
+
+        let budget = MemoryBudget::new(1000, 0);
+        assert!(matches!(
+            JetsonQuantizer::try_select_from(8000, &budget, &offered),
+            Err(Error::InsufficientMemory {
+                requested_mb: 2000,
+                ..
+            })
+        ));
+        assert!(matches!(
+            JetsonQuantizer::try_select_from(8000, &budget, &[]),
+            Err(Error::Quantization(_))
+        ));
+    }
+
+    #[test]
+    fn test_quantize_for_fleet_caches_by_source_and_level() {
+        use crate::device::mock::MockExecutor;
+
+        let exec = MockExecutor::new().on(
+            "sha256sum",
+            "9f86d081884c7d659a2feaa0c55ad015a3bf4f1b2b0b822cd15d6c15b0f00a08  llama.f16.gguf\n",
+        );
+        let out_dir = std::env::temp_dir().join(format!("cohete-quant-{}", std::process::id()));
+        let source = Path::new("/models/llama.f16.gguf");
+
+        let levels = [QuantLevel::Q4_0, QuantLevel::Q8_0];
+        let outputs = quantize_with(&exec, source, &levels, &out_dir).unwrap();
+        assert_eq!(exec.count("llama-quantize"), 2);
+        assert_eq!(
+            outputs[&QuantLevel::Q4_0],
+            out_dir.join("llama.9f86d081884c7d65.q4_0.gguf")
+        );
+        assert!(exec
+            .calls()
+            .iter()
+            .any(|c| c.contains("Q8_0") && c.contains("mv ")));
+
+        // Stand in for llama-quantize having written its outputs
+        for path in outputs.values() {
+            std::fs::write(path, b"GGUF").unwrap();
+        }
+        let levels = [QuantLevel::Q4_0, QuantLevel::Q8_0, QuantLevel::Q5_0];
+        let outputs = quantize_with(&exec, source, &levels, &out_dir).unwrap();
+        std::fs::remove_dir_all(&out_dir).unwrap();
+        assert_eq!(outputs.len(), 3);
+        assert_eq!(exec.count("llama-quantize"), 3);
+        assert!(exec.calls().last().unwrap().contains("Q5_0"));
+    }
+
+    /// Minimal GGUF v3 header with `metadata` as string key/value pairs.
+    fn gguf_bytes(metadata: &[(&str, &str)]) -> Vec<u8> {
+        let string = |out: &mut Vec<u8>, s: &str| {
+            out.extend_from_slice(&(s.len() as u64).to_le_bytes());
+            out.extend_from_slice(s.as_bytes());
+        };
+        let mut out = b"GGUF".to_vec();
+        out.extend_from_slice(&3u32.to_le_bytes());
+        out.extend_from_slice(&0u64.to_le_bytes());
+        out.extend_from_slice(&(metadata.len() as u64 + 1).to_le_bytes());
+        // A non-string value ahead of the architecture must be skipped
+        string(&mut out, "general.file_type");
+        out.extend_from_slice(&4u32.to_le_bytes());
+        out.extend_from_slice(&2u32.to_le_bytes());
+        for (key, value) in metadata {
+            string(&mut out, key);
+            out.extend_from_slice(&GGUF_TYPE_STRING.to_le_bytes());
+            string(&mut out, value);
+        }
+        out
+    }
+
+    #[test]
+    fn test_validate_gguf() {
+        let dir = std::env::temp_dir().join(format!("cohete-gguf-{}", std::process::id()));
+        std::fs::create_dir_all(&dir).unwrap();
+        let write = |name: &str, data: &[u8]| {
+            let path = dir.join(name);
+            std::fs::write(&path, data).unwrap();
+            path
+        };
+        let valid = write(
+            "llama.gguf",
+            &gguf_bytes(&[("general.name", "tiny"), ("general.architecture", "llama")]),
+        );
+        let not_gguf = write("model.safetensors", b"{\"__metadata__\": {}}");
+        let no_arch = write("bare.gguf", &gguf_bytes(&[]));
+
+        let results = (
+            validate_gguf(&valid, None),
+            validate_gguf(&valid, Some("llama")),
+            validate_gguf(&valid, Some("phi3")),
+            validate_gguf(&not_gguf, None),
+            validate_gguf(&no_arch, Some("llama")),
+        );
+        std::fs::remove_dir_all(&dir).unwrap();
+
+        assert!(results.0.is_ok());
+        assert!(results.1.is_ok());
+        assert!(
+            matches!(&results.2, Err(Error::Quantization(m)) if m.contains("'llama', expected 'phi3'"))
+        );
+        assert!(matches!(&results.3, Err(Error::Quantization(m)) if m.contains("not a GGUF")));
+        assert!(
+            matches!(&results.4, Err(Error::Quantization(m)) if m.contains("no general.architecture"))
+        );
+    }
+
+    #[test]
+    fn test_gguf_quant_level() {
+        // gguf_bytes writes general.file_type 2 (Q4_0)
+        let header = gguf_bytes(&[("general.architecture", "llama")]);
+        assert_eq!(gguf_quant_level(&header).unwrap(), QuantLevel::Q4_0);
+        assert_eq!(QuantLevel::from_ftype(8), Some(QuantLevel::Q5_0));
+        assert_eq!(QuantLevel::from_ftype(15), None);
+
+        assert!(matches!(
+            gguf_quant_level(b"PK\x03\x04"),
+            Err(Error::Quantization(m)) if m.contains("not a GGUF")
+        ));
+        // Cut off before the file type is reached
+        assert!(gguf_quant_level(&header[..30]).is_err());
+    }
+
+    #[test]
+    fn test_gguf_nested_arrays_are_capped() {
+        // An array of arrays nested `depth` deep, ahead of general.file_type
+        let header = |depth: usize| {
+            let mut out = b"GGUF".to_vec();
+            out.extend_from_slice(&3u32.to_le_bytes());
+            out.extend_from_slice(&0u64.to_le_bytes());
+            out.extend_from_slice(&2u64.to_le_bytes());
+            out.extend_from_slice(&4u64.to_le_bytes());
+            out.extend_from_slice(b"nest");
+            out.extend_from_slice(&GGUF_TYPE_ARRAY.to_le_bytes());
+            for _ in 1..depth {
+                out.extend_from_slice(&GGUF_TYPE_ARRAY.to_le_bytes());
+                out.extend_from_slice(&1u64.to_le_bytes());
+            }
+            out.extend_from_slice(&GGUF_TYPE_U32.to_le_bytes());
+            out.extend_from_slice(&0u64.to_le_bytes());
+            out.extend_from_slice(&17u64.to_le_bytes());
+            out.extend_from_slice(b"general.file_type");
+            out.extend_from_slice(&GGUF_TYPE_U32.to_le_bytes());
+            out.extend_from_slice(&2u32.to_le_bytes());
+            out
+        };
+
+        assert_eq!(gguf_quant_level(&header(8)).unwrap(), QuantLevel::Q4_0);
+        assert!(matches!(
+            gguf_quant_level(&header(9)),
+            Err(Error::Quantization(m)) if m.contains("nested deeper than 8")
+        ));
+    }
+
     #[test]
     fn test_quant_level_display() {
         assert_eq!(QuantLevel::Q4_0.to_string(), "q4_0");
@@ -205,14 +779,53 @@ mod tests {
     fn test_select_for_budget() {
         let budget = MemoryBudget::orin_nano_8gb(); // 6144 MB available
         // 14GB F16 model: at Q5_1 = 14000 * 0.3125 = 4375 MB (fits)
-        let level = JetsonQuantizer::select_for_budget(14000, &budget);
+        let level = JetsonQuantizer::select_for_budget(14000, &budget).unwrap();
         assert_eq!(level, QuantLevel::Q5_1);
 
         // 20GB F16 model: Q4_1 = 20000 * 0.25 = 5000 MB (fits, higher quality than Q4_0)
-        let level = JetsonQuantizer::select_for_budget(20000, &budget);
+        let level = JetsonQuantizer::select_for_budget(20000, &budget).unwrap();
         assert_eq!(level, QuantLevel::Q4_1);
     }
 
+    #[test]
+    fn test_select_for_budget_rejects_zero_budget() {
+        // What an unidentified model would yield without the for_model guard
+        let budget = MemoryBudget::new(crate::JetsonModel::Unknown.memory_mb(), 0);
+        assert!(matches!(
+            JetsonQuantizer::select_for_budget(100, &budget),
+            Err(Error::Config(_))
+        ));
+        assert!(matches!(
+            JetsonQuantizer::try_select_for_budget(100, &budget),
+            Err(Error::Config(_))
+        ));
+
+        let reserved_all = MemoryBudget::new(4096, 4096);
+        assert!(JetsonQuantizer::select_for_budget(100, &reserved_all).is_err());
+    }
+
+    #[test]
+    fn test_try_select_for_budget_model_too_large() {
+        let budget = MemoryBudget::orin_nano_8gb();
+        // 100GB F16 model: even Q4_0 needs 25GB
+        let err = JetsonQuantizer::try_select_for_budget(100_000, &budget).unwrap_err();
+        assert!(matches!(
+            err,
+            Error::InsufficientMemory {
+                requested_mb: 25_000,
+                available_mb: 6144,
+            }
+        ));
+        assert_eq!(
+            JetsonQuantizer::select_for_budget(100_000, &budget).unwrap(),
+            QuantLevel::Q4_0
+        );
+        assert_eq!(
+            JetsonQuantizer::try_select_for_budget(14000, &budget).unwrap(),
+            QuantLevel::Q5_1
+        );
+    }
+
     #[test]
     fn test_quantizer() {
         let quantizer = JetsonQuantizer::new(QuantLevel::Q4_0);
@@ -220,4 +833,85 @@ mod tests {
         assert_eq!(result.level, QuantLevel::Q4_0);
         assert!(result.compression_ratio() > 1.0);
     }
+
+    #[test]
+    fn test_quant_level_next_lower() {
+        assert_eq!(QuantLevel::Q8_0.next_lower(), Some(QuantLevel::Q5_1));
+        assert_eq!(QuantLevel::Q4_1.next_lower(), Some(QuantLevel::Q4_0));
+        assert_eq!(QuantLevel::Q4_0.next_lower(), None);
+
+        let mut level = QuantLevel::F32;
+        let mut steps = 0;
+        while let Some(next) = level.next_lower() {
+            assert!(next.memory_factor() <= level.memory_factor());
+            level = next;
+            steps += 1;
+        }
+        assert_eq!(steps, 6);
+    }
+
+    #[test]
+    fn test_quant_level_from_str() {
+        assert_eq!("q4_0".parse::<QuantLevel>().unwrap(), QuantLevel::Q4_0);
+        assert_eq!("Q8_0".parse::<QuantLevel>().unwrap(), QuantLevel::Q8_0);
+        assert_eq!("f16".parse::<QuantLevel>().unwrap(), QuantLevel::F16);
+        assert!("q3_k".parse::<QuantLevel>().is_err());
+    }
+
+    #[test]
+    fn test_relative_speed_ordering() {
+        for pair in SELECTION_ORDER.windows(2) {
+            assert!(pair[1].relative_speed() >= pair[0].relative_speed());
+        }
+    }
+
+    #[test]
+    fn test_select_for_latency_tight_sla() {
+        // 3B on Orin Nano 8GB: F16 ~105ms, Q5_0 ~42ms, Q4_1 ~37.5ms
+        let budget = MemoryBudget::orin_nano_8gb();
+        let level =
+            JetsonQuantizer::select_for_latency(JetsonModel::OrinNano8GB, 3.0, &budget, 40.0)
+                .unwrap();
+        assert_eq!(level, QuantLevel::Q4_1);
+    }
+
+    #[test]
+    fn test_select_for_latency_relaxed_sla() {
+        // Q8_0 ~58ms meets 60ms; F16 fits memory but is too slow
+        let budget = MemoryBudget::orin_nano_8gb();
+        let level =
+            JetsonQuantizer::select_for_latency(JetsonModel::OrinNano8GB, 3.0, &budget, 60.0)
+                .unwrap();
+        assert_eq!(level, QuantLevel::Q8_0);
+    }
+
+    #[test]
+    fn test_select_for_latency_memory_bound() {
+        // 7B: latency allows anything, but only Q5_1 and below fit 6144 MB
+        let budget = MemoryBudget::orin_nano_8gb();
+        let level =
+            JetsonQuantizer::select_for_latency(JetsonModel::OrinNano8GB, 7.0, &budget, 1000.0)
+                .unwrap();
+        assert_eq!(level, QuantLevel::Q5_1);
+    }
+
+    #[test]
+    fn test_select_for_latency_unmet() {
+        let budget = MemoryBudget::orin_nano_8gb();
+        let err = JetsonQuantizer::select_for_latency(JetsonModel::OrinNano8GB, 3.0, &budget, 20.0)
+            .unwrap_err();
+        assert!(matches!(err, Error::Quantization(_)));
+
+        assert!(
+            JetsonQuantizer::select_for_latency(JetsonModel::Unknown, 1.0, &budget, 100.0).is_err()
+        );
+    }
+
+    #[test]
+    fn test_quant_level_serde() {
+        let json = serde_json::to_string(&QuantLevel::Q4_0).unwrap();
+        assert_eq!(json, "\"q4_0\"");
+        let level: QuantLevel = serde_json::from_str("\"q5_1\"").unwrap();
+        assert_eq!(level, QuantLevel::Q5_1);
+    }
 }
@@ -2,8 +2,29 @@
 //!
 //! Provides NVMe SSD management, swap configuration, and model storage.
 
-use crate::Result;
-use std::path::PathBuf;
+use crate::{
+    device::{shell_quote, JetsonDevice},
+    memory::MemoryBudget,
+    quantize::QuantLevel,
+    Error, Result, Subsystem,
+};
+use serde::{Deserialize, Serialize};
+use std::path::{Path, PathBuf};
+use std::time::Duration;
+
+/// Manifest of deployed models, kept in the models directory.
+pub const MANIFEST_FILE: &str = "manifest.json";
+
+const GB: u64 = 1024 * 1024 * 1024;
+
+/// Free inodes below which a filesystem counts as exhausted.
+pub const MIN_FREE_INODES: u64 = 1000;
+
+/// Default tmpfs scratch mount point, kept off the SSD to spare it writes.
+pub const DEFAULT_SCRATCH_DIR: &str = "/run/cohete/scratch";
+
+/// Default minimum age before a file may be garbage collected.
+pub const DEFAULT_GC_MIN_AGE: Duration = Duration::from_secs(3600);
 
 /// NVMe device handle.
 #[derive(Debug)]
@@ -50,6 +71,149 @@ impl NvmeDevice {
         let used = self.capacity_bytes - self.available_bytes;
         (used as f32 / self.capacity_bytes as f32) * 100.0
     }
+
+    /// Link this SSD's controller trained at on the PCI Express bus.
+    ///
+    /// The controller is found through `/sys/block/<name>/device`, so each
+    /// drive on a multi-slot carrier reports its own link. When it lacks the
+    /// sysfs link attributes, the link status `lspci -vv` reports for that
+    /// PCI function is used instead (see [`parse_lspci_link`]).
+    ///
+    /// # Errors
+    ///
+    /// Returns `Error::SubsystemUnavailable` if neither sysfs nor lspci
+    /// reports the controller's link, or `Error::Parse` if it is not
+    /// understood.
+    pub async fn pcie_link(&self, device: &JetsonDevice) -> Result<PcieLink> {
+        let name = self
+            .device_path
+            .file_name()
+            .map(|n| n.to_string_lossy())
+            .ok_or_else(|| Error::SubsystemUnavailable {
+                subsystem: Subsystem::Nvme,
+                reason: format!("{} is not a block device", self.device_path.display()),
+            })?;
+        // `device` is the NVMe controller; its own `device` is the PCI function
+        let pci = format!("/sys/block/{name}/device/device");
+        let link = device
+            .read_sysfs_many(&[
+                &format!("{pci}/current_link_speed"),
+                &format!("{pci}/current_link_width"),
+            ])
+            .await;
+        match link {
+            Ok(link) => parse_sysfs_link(&link[0], &link[1]),
+            Err(Error::SubsystemUnavailable { .. }) => {
+                let lspci = exec(
+                    device,
+                    &format!("sudo lspci -vv -s \"$(basename \"$(readlink -f {pci})\")\""),
+                )
+                .await?;
+                parse_lspci_link(&lspci)
+            }
+            Err(e) => Err(e.with_subsystem(Subsystem::Nvme)),
+        }
+    }
+}
+
+/// Negotiated PCI Express link of a device.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+pub struct PcieLink {
+    /// Link generation (1-5)
+    pub gen: u8,
+    /// Lane count
+    pub width: u8,
+}
+
+impl PcieLink {
+    /// Whether the link runs slower or narrower than `expected`.
+    #[must_use]
+    pub const fn is_degraded(&self, expected: Self) -> bool {
+        self.gen < expected.gen || self.width < expected.width
+    }
+}
+
+impl std::fmt::Display for PcieLink {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        write!(f, "Gen{} x{}", self.gen, self.width)
+    }
+}
+
+/// Parse the `LnkSta` of the first SSD controller in `lspci -vv` output.
+///
+/// # Errors
+///
+/// Returns `Error::SubsystemUnavailable` if no SSD controller is listed,
+/// or `Error::Parse` if its link status is missing or not understood.
+pub fn parse_lspci_link(output: &str) -> Result<PcieLink> {
+    let section = output
+        .split("\n\n")
+        .find(|s| s.contains("Non-Volatile memory controller"))
+        .ok_or_else(|| Error::SubsystemUnavailable {
+            subsystem: Subsystem::Nvme,
+            reason: "no NVMe controller in lspci output".to_string(),
+        })?;
+    let invalid = |message: String| Error::Parse {
+        context: "lspci LnkSta".to_string(),
+        message,
+    };
+    let status = section
+        .lines()
+        .find_map(|line| line.trim().strip_prefix("LnkSta:"))
+        .ok_or_else(|| invalid("no LnkSta line (run lspci as root)".to_string()))?;
+
+    let mut speed = None;
+    let mut width = None;
+    for part in status.split(',') {
+        let mut words = part.split_whitespace();
+        match (words.next(), words.next()) {
+            (Some("Speed"), Some(value)) => speed = value.strip_suffix("GT/s"),
+            (Some("Width"), Some(value)) => width = value.strip_prefix('x'),
+            _ => {}
+        }
+    }
+    let gen = speed
+        .and_then(pcie_gen)
+        .ok_or_else(|| invalid(format!("unknown speed in: {}", status.trim())))?;
+    let width = width
+        .and_then(|w| w.parse().ok())
+        .ok_or_else(|| invalid(format!("unknown width in: {}", status.trim())))?;
+    Ok(PcieLink { gen, width })
+}
+
+/// Parse a PCI function's sysfs `current_link_speed` (e.g. `8.0 GT/s PCIe`)
+/// and `current_link_width` (e.g. `4`).
+///
+/// # Errors
+///
+/// Returns `Error::Parse` if either value is not understood.
+pub fn parse_sysfs_link(speed: &str, width: &str) -> Result<PcieLink> {
+    let invalid = |message: String| Error::Parse {
+        context: "PCIe link".to_string(),
+        message,
+    };
+    let gen = speed
+        .split_whitespace()
+        .next()
+        .and_then(pcie_gen)
+        .ok_or_else(|| invalid(format!("unknown speed: {speed}")))?;
+    let width = width
+        .trim()
+        .parse()
+        .map_err(|_| invalid(format!("unknown width: {width}")))?;
+    Ok(PcieLink { gen, width })
+}
+
+/// Link generation from a per-lane rate in GT/s (`8` or `8.0`).
+fn pcie_gen(gts: &str) -> Option<u8> {
+    match gts.strip_suffix(".0").unwrap_or(gts) {
+        "2.5" => Some(1),
+        "5" => Some(2),
+        "8" => Some(3),
+        "16" => Some(4),
+        "32" => Some(5),
+        _ => None,
+    }
 }
 
 /// Swap file configuration.
@@ -73,6 +237,100 @@ impl SwapConfig {
             swappiness: 10, // Low swappiness for ML
         }
     }
+
+    /// Resize the swap file at `path` to `new_size_gb`.
+    ///
+    /// The new file is allocated and formatted beside the old one, then
+    /// swapped in with a rename, so the device keeps its swap if allocation
+    /// fails. Does nothing if the file is active at the requested size already.
+    ///
+    /// # Errors
+    ///
+    /// Returns `Error::Storage` if the new size is below what is currently
+    /// swapped out or the filesystem lacks space for the new file, or an
+    /// error if any step fails.
+    pub async fn resize(&self, device: &JetsonDevice, new_size_gb: u64) -> Result<()> {
+        let path = self.path.to_string_lossy();
+        let quoted = shell_quote(&path);
+        let staged = shell_quote(&format!("{path}.new"));
+        let new_bytes = new_size_gb * GB;
+
+        let swaps = exec(device, "swapon --show=NAME,USED --bytes --noheadings").await?;
+        let used = parse_swapon_used(&swaps, &path);
+        let size = exec(
+            device,
+            &format!("stat -c %s {quoted} 2>/dev/null || echo 0"),
+        )
+        .await?;
+        let current_bytes: u64 = size.trim().parse().map_err(|_| Error::Parse {
+            context: "swap file size".to_string(),
+            message: format!("invalid size: {}", size.trim()),
+        })?;
+
+        if used.is_some() && current_bytes == new_bytes {
+            return Ok(());
+        }
+        if let Some(used) = used.filter(|used| *used > new_bytes) {
+            return Err(Error::Storage(format!(
+                "swap {path} on {}: {} MB in use, refusing to shrink to {} MB",
+                device.id(),
+                used / (1024 * 1024),
+                new_bytes / (1024 * 1024)
+            )));
+        }
+        let dir = self.path.parent().unwrap_or_else(|| Path::new("/"));
+        let df = exec(
+            device,
+            &format!("df -P -B1 {}", shell_quote(&dir.to_string_lossy())),
+        )
+        .await?;
+        let available = parse_df_available(&df)?;
+        if available < new_bytes {
+            return Err(Error::Storage(format!(
+                "swap {path} on {}: {} MB free, {} MB required",
+                device.id(),
+                available / (1024 * 1024),
+                new_bytes / (1024 * 1024)
+            )));
+        }
+
+        let staging = [
+            format!("sudo rm -f {staged}"),
+            format!("sudo fallocate -l {new_bytes} {staged}"),
+            format!("sudo chmod 600 {staged}"),
+            format!("sudo mkswap {staged}"),
+        ];
+        for step in &staging {
+            if let Err(e) = exec(device, step).await {
+                // The old swap is untouched; only the partial file needs removing
+                if let Err(cleanup) = exec(device, &format!("sudo rm -f {staged}")).await {
+                    tracing::warn!(
+                        device = %device.id(),
+                        error = %cleanup,
+                        "Failed to remove staged swap file"
+                    );
+                }
+                return Err(e);
+            }
+        }
+        if used.is_some() {
+            exec(device, &format!("sudo swapoff {quoted}")).await?;
+        }
+        if let Err(e) = exec(device, &format!("sudo mv -f {staged} {quoted}")).await {
+            if used.is_some() {
+                if let Err(restore) = exec(device, &format!("sudo swapon {quoted}")).await {
+                    tracing::warn!(
+                        device = %device.id(),
+                        error = %restore,
+                        "Failed to re-enable original swap"
+                    );
+                }
+            }
+            return Err(e);
+        }
+        exec(device, &format!("sudo swapon {quoted}")).await?;
+        Ok(())
+    }
 }
 
 impl Default for SwapConfig {
@@ -94,6 +352,14 @@ pub struct StorageLayout {
     pub cache_dir: PathBuf,
     /// Docker directory (optional)
     pub docker_dir: Option<PathBuf>,
+    /// Models directory quota in GB (None = unlimited)
+    pub models_quota_gb: Option<u64>,
+    /// Cache directory quota in GB (None = unlimited)
+    pub cache_quota_gb: Option<u64>,
+    /// Files used or modified more recently than this are never collected
+    pub gc_min_age: Duration,
+    /// RAM-backed scratch mount for model loading and quantization
+    pub scratch_dir: PathBuf,
 }
 
 impl StorageLayout {
@@ -107,6 +373,636 @@ impl StorageLayout {
             cache_dir: nvme_mount.join("cache"),
             docker_dir: Some(nvme_mount.join("docker")),
             nvme_mount,
+            models_quota_gb: None,
+            cache_quota_gb: None,
+            gc_min_age: DEFAULT_GC_MIN_AGE,
+            scratch_dir: PathBuf::from(DEFAULT_SCRATCH_DIR),
+        }
+    }
+
+    /// Set models directory quota.
+    #[must_use]
+    pub const fn with_models_quota_gb(mut self, quota_gb: u64) -> Self {
+        self.models_quota_gb = Some(quota_gb);
+        self
+    }
+
+    /// Set cache directory quota.
+    #[must_use]
+    pub const fn with_cache_quota_gb(mut self, quota_gb: u64) -> Self {
+        self.cache_quota_gb = Some(quota_gb);
+        self
+    }
+
+    /// Set the minimum age before a file may be garbage collected.
+    #[must_use]
+    pub const fn with_gc_min_age(mut self, min_age: Duration) -> Self {
+        self.gc_min_age = min_age;
+        self
+    }
+
+    /// Set the tmpfs scratch mount point.
+    #[must_use]
+    pub fn with_scratch_dir(mut self, dir: impl Into<PathBuf>) -> Self {
+        self.scratch_dir = dir.into();
+        self
+    }
+
+    /// Mount a `size_mb` tmpfs at [`Self::scratch_dir`], unmounted on release.
+    ///
+    /// An existing tmpfs there is resized instead, and restored to its old
+    /// size on release rather than unmounted. The size must fit the
+    /// device's available memory budget (see [`MemoryBudget::from_device`]),
+    /// as tmpfs pages count against RAM and would otherwise cause OOMs.
+    ///
+    /// # Errors
+    ///
+    /// Returns `Error::InsufficientMemory` if the scratch does not fit the
+    /// budget, or an error if the mount fails.
+    pub async fn ensure_tmpfs_scratch(
+        &self,
+        device: &JetsonDevice,
+        size_mb: u64,
+    ) -> Result<TmpfsScratch> {
+        let budget = MemoryBudget::from_device(device).await?;
+        if size_mb > budget.available_mb() {
+            return Err(Error::InsufficientMemory {
+                requested_mb: size_mb,
+                available_mb: budget.available_mb(),
+            });
+        }
+        let dir = shell_quote(&self.scratch_dir.to_string_lossy());
+        let existing = exec(
+            device,
+            &format!("findmnt -n -b -o FSTYPE,SIZE {dir} || true"),
+        )
+        .await?;
+        let mut fields = existing.split_whitespace();
+        let cleanup = if fields.next() == Some("tmpfs") {
+            exec(
+                device,
+                &format!("sudo mount -o remount,size={size_mb}m {dir}"),
+            )
+            .await?;
+            fields
+                .next()
+                .and_then(|bytes| bytes.parse::<u64>().ok())
+                .map_or(ScratchCleanup::None, |bytes| {
+                    ScratchCleanup::RestoreSize(bytes.div_ceil(1024 * 1024))
+                })
+        } else {
+            exec(
+                device,
+                &format!(
+                    "sudo mkdir -p {dir} && sudo mount -t tmpfs -o size={size_mb}m,mode=1777 tmpfs {dir}"
+                ),
+            )
+            .await?;
+            ScratchCleanup::Unmount
+        };
+        Ok(TmpfsScratch {
+            device: device.clone(),
+            path: self.scratch_dir.clone(),
+            size_mb,
+            cleanup,
+        })
+    }
+
+    /// Path of the deployed-model manifest.
+    #[must_use]
+    pub fn manifest_path(&self) -> PathBuf {
+        self.models_dir.join(MANIFEST_FILE)
+    }
+
+    /// Read the device's model manifest; a missing manifest is empty.
+    ///
+    /// # Errors
+    ///
+    /// Returns an error if the device cannot be reached or the manifest is
+    /// not valid JSON.
+    pub async fn read_manifest(&self, device: &JetsonDevice) -> Result<ModelManifest> {
+        let path = shell_quote(&self.manifest_path().to_string_lossy());
+        let content = exec(device, &format!("cat {path} 2>/dev/null || true")).await?;
+        if content.trim().is_empty() {
+            return Ok(ModelManifest::default());
+        }
+        serde_json::from_str(&content).map_err(|e| Error::Parse {
+            context: format!("{} manifest", device.id()),
+            message: e.to_string(),
+        })
+    }
+
+    /// Add or replace a model in the device's manifest.
+    ///
+    /// # Errors
+    ///
+    /// Returns an error if the manifest cannot be read or written.
+    pub async fn record_model(&self, device: &JetsonDevice, entry: ManifestEntry) -> Result<()> {
+        let mut manifest = self.read_manifest(device).await?;
+        manifest.upsert(entry);
+        self.write_manifest(device, &manifest).await
+    }
+
+    /// Upload `manifest` to the device, replacing the current one.
+    async fn write_manifest(&self, device: &JetsonDevice, manifest: &ModelManifest) -> Result<()> {
+        let json =
+            serde_json::to_vec_pretty(manifest).map_err(|e| Error::Internal(e.to_string()))?;
+        device.upload(&json, self.manifest_path()).await
+    }
+
+    /// Measure directory usage and garbage-collect categories over quota.
+    ///
+    /// Over-quota directories have their least recently accessed files
+    /// deleted until usage is back under quota. Timestamps are judged
+    /// against the device's clock; future timestamps count as now, and
+    /// files younger than `gc_min_age` are kept even if that leaves the
+    /// directory over quota.
+    ///
+    /// # Errors
+    ///
+    /// Returns an error if usage cannot be measured or files cannot be removed.
+    pub async fn enforce_quotas(&self, device: &JetsonDevice) -> Result<QuotaReport> {
+        let categories = [
+            (
+                StorageCategory::Models,
+                &self.models_dir,
+                self.models_quota_gb,
+            ),
+            (StorageCategory::Data, &self.data_dir, None),
+            (StorageCategory::Cache, &self.cache_dir, self.cache_quota_gb),
+        ];
+        let dirs: Vec<String> = categories
+            .iter()
+            .map(|(_, dir, _)| shell_quote(&dir.to_string_lossy()))
+            .collect();
+        // du exits non-zero when a directory is missing; report what it could measure
+        let output = exec(
+            device,
+            &format!("du -sb {} 2>/dev/null; true", dirs.join(" ")),
+        )
+        .await?;
+        let measured = parse_du(&output);
+
+        let mut usage = Vec::with_capacity(categories.len());
+        for (category, dir, quota_gb) in categories {
+            let used_bytes = measured
+                .iter()
+                .find(|(path, _)| path == dir)
+                .map_or(0, |(_, bytes)| *bytes);
+            let mut entry = CategoryUsage {
+                category,
+                path: dir.clone(),
+                used_bytes,
+                quota_bytes: quota_gb.map(|gb| gb * GB),
+                freed_bytes: 0,
+            };
+            if let Some(excess) = entry.excess_bytes() {
+                tracing::warn!(
+                    device = %device.id(),
+                    category = ?category,
+                    used_bytes,
+                    excess,
+                    "Storage over quota, collecting garbage"
+                );
+                entry.freed_bytes = self.gc_oldest(device, dir, excess).await?;
+            }
+            usage.push(entry);
+        }
+
+        Ok(QuotaReport { categories: usage })
+    }
+
+    /// Delete least recently accessed files under `dir` until `bytes` are freed.
+    ///
+    /// The model manifest is never a candidate: deploys and drift checks rely
+    /// on it. Models collected from the models directory are dropped from the
+    /// manifest before their files are removed, so it never lists a missing file.
+    async fn gc_oldest(&self, device: &JetsonDevice, dir: &Path, bytes: u64) -> Result<u64> {
+        let clock = exec(device, "date +%s").await?;
+        let now: f64 = clock.trim().parse().map_err(|_| Error::Parse {
+            context: "device clock".to_string(),
+            message: format!("invalid timestamp: {}", clock.trim()),
+        })?;
+        let listing = exec(
+            device,
+            &format!(
+                "find {} -type f -printf '%A@ %T@ %s %p\\n'",
+                shell_quote(&dir.to_string_lossy())
+            ),
+        )
+        .await?;
+        let mut files: Vec<GcCandidate> = parse_find_listing(&listing)
+            .into_iter()
+            .filter(|f| f.age(now) >= self.gc_min_age.as_secs_f64())
+            .filter(|f| Path::new(&f.path) != dir.join(MANIFEST_FILE))
+            .collect();
+        files.sort_by(|a, b| a.last_used(now).total_cmp(&b.last_used(now)));
+
+        let mut freed = 0;
+        let mut victims = Vec::new();
+        for file in files {
+            if freed >= bytes {
+                break;
+            }
+            freed += file.size;
+            victims.push(file.path);
+        }
+        if freed < bytes {
+            tracing::warn!(
+                device = %device.id(),
+                dir = %dir.display(),
+                short_bytes = bytes - freed,
+                "Not enough old files to get under quota"
+            );
+        }
+        if victims.is_empty() {
+            return Ok(freed);
+        }
+        if dir == self.models_dir {
+            let mut manifest = self.read_manifest(device).await?;
+            let removed: Vec<&str> = victims
+                .iter()
+                .filter_map(|path| Path::new(path).strip_prefix(dir).ok())
+                .filter_map(Path::to_str)
+                .collect();
+            if manifest.remove_files(&removed) {
+                self.write_manifest(device, &manifest).await?;
+            }
+        }
+        let victims: Vec<String> = victims.iter().map(|path| shell_quote(path)).collect();
+        exec(device, &format!("rm -f -- {}", victims.join(" "))).await?;
+        Ok(freed)
+    }
+
+    /// Check the filesystem at `nvme_mount` can take `required_bytes` more data.
+    ///
+    /// Inodes are checked alongside bytes: caches of many small files can
+    /// exhaust them while space remains, making writes fail with `ENOSPC`.
+    ///
+    /// # Errors
+    ///
+    /// Returns `Error::Storage` if free bytes or inodes are insufficient,
+    /// or an error if `df` cannot be run or parsed.
+    pub async fn preflight(&self, device: &JetsonDevice, required_bytes: u64) -> Result<DiskSpace> {
+        let mount = shell_quote(&self.nvme_mount.to_string_lossy());
+        let bytes = exec(device, &format!("df -P -B1 {mount}")).await?;
+        let inodes = exec(device, &format!("df -P -i {mount}")).await?;
+        let space = DiskSpace {
+            available_bytes: parse_df_available(&bytes)?,
+            inodes: parse_df_inodes(&inodes)?,
+        };
+
+        if space.available_bytes < required_bytes {
+            return Err(Error::Storage(format!(
+                "{} on {}: {} MB free, {} MB required",
+                self.nvme_mount.display(),
+                device.id(),
+                space.available_bytes / (1024 * 1024),
+                required_bytes / (1024 * 1024)
+            )));
+        }
+        if let Some((total, free)) = space.inodes {
+            if free < MIN_FREE_INODES {
+                return Err(Error::Storage(format!(
+                    "{} on {} is out of inodes ({free} of {total} free) despite {} MB free; \
+                     remove small cache files",
+                    self.nvme_mount.display(),
+                    device.id(),
+                    space.available_bytes / (1024 * 1024)
+                )));
+            }
+        }
+        Ok(space)
+    }
+}
+
+/// Run a storage command, tagging failures with [`Subsystem::Nvme`].
+async fn exec(device: &JetsonDevice, command: &str) -> Result<String> {
+    device
+        .exec(command)
+        .await
+        .map_err(|e| e.with_subsystem(Subsystem::Nvme))
+}
+
+/// Parse `du -sb` output into `(path, bytes)` pairs.
+fn parse_du(output: &str) -> Vec<(PathBuf, u64)> {
+    output
+        .lines()
+        .filter_map(|line| {
+            let (bytes, path) = line.split_once(char::is_whitespace)?;
+            Some((PathBuf::from(path.trim()), bytes.parse().ok()?))
+        })
+        .collect()
+}
+
+/// Bytes in use by the swap area at `path` from `swapon --show=NAME,USED
+/// --bytes --noheadings` output, or `None` if it is not active.
+fn parse_swapon_used(output: &str, path: &str) -> Option<u64> {
+    output.lines().find_map(|line| {
+        let mut fields = line.split_whitespace();
+        let (name, used) = (fields.next()?, fields.next()?);
+        if name == path {
+            used.parse().ok()
+        } else {
+            None
+        }
+    })
+}
+
+/// Last data row of `df -P` output, split into columns.
+fn df_row(output: &str) -> Result<Vec<&str>> {
+    output
+        .lines()
+        .skip(1)
+        .last()
+        .map(|line| line.split_whitespace().collect::<Vec<_>>())
+        .filter(|fields| fields.len() >= 6)
+        .ok_or_else(|| Error::Parse {
+            context: "df".to_string(),
+            message: format!("unexpected output: {}", output.trim()),
+        })
+}
+
+/// Parse available bytes from `df -P -B1` output.
+fn parse_df_available(output: &str) -> Result<u64> {
+    let fields = df_row(output)?;
+    fields[3].parse().map_err(|_| Error::Parse {
+        context: "df".to_string(),
+        message: format!("invalid available bytes: {}", fields[3]),
+    })
+}
+
+/// Parse `(total, free)` inodes from `df -P -i` output.
+///
+/// Filesystems without fixed inode tables (e.g. btrfs) report none.
+fn parse_df_inodes(output: &str) -> Result<Option<(u64, u64)>> {
+    let fields = df_row(output)?;
+    let parse = |field: &str| {
+        field.parse::<u64>().map_err(|_| Error::Parse {
+            context: "df -i".to_string(),
+            message: format!("invalid inode count: {field}"),
+        })
+    };
+    if fields[1] == "-" {
+        return Ok(None);
+    }
+    let total = parse(fields[1])?;
+    if total == 0 {
+        return Ok(None);
+    }
+    Ok(Some((total, parse(fields[3])?)))
+}
+
+/// File considered for garbage collection.
+#[derive(Debug, Clone, PartialEq)]
+struct GcCandidate {
+    /// Access time (seconds since the epoch, 0 if unknown)
+    atime: f64,
+    /// Modification time (seconds since the epoch)
+    mtime: f64,
+    size: u64,
+    path: String,
+}
+
+impl GcCandidate {
+    /// LRU sort key: atime, or mtime when atime is missing, capped at `now`.
+    fn last_used(&self, now: f64) -> f64 {
+        let used = if self.atime > 0.0 {
+            self.atime
+        } else {
+            self.mtime
+        };
+        used.min(now)
+    }
+
+    /// Seconds since the file was last accessed or modified, 0 if dated in the future.
+    fn age(&self, now: f64) -> f64 {
+        (now - self.atime.max(self.mtime)).max(0.0)
+    }
+}
+
+/// Parse `find -printf '%A@ %T@ %s %p\n'` output.
+fn parse_find_listing(output: &str) -> Vec<GcCandidate> {
+    output
+        .lines()
+        .filter_map(|line| {
+            let mut parts = line.splitn(4, ' ');
+            let atime = parts.next()?.parse().ok()?;
+            let mtime = parts.next()?.parse().ok()?;
+            let size = parts.next()?.parse().ok()?;
+            let path = parts.next()?.to_string();
+            Some(GcCandidate {
+                atime,
+                mtime,
+                size,
+                path,
+            })
+        })
+        .collect()
+}
+
+/// Model deployed on a device.
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+pub struct ManifestEntry {
+    /// Model name
+    pub name: String,
+    /// Quantization level deployed
+    pub quant: QuantLevel,
+    /// File name in the models directory
+    pub file: String,
+}
+
+/// Models deployed on a device, stored as JSON in the models directory.
+#[derive(Debug, Clone, Default, PartialEq, Eq, Serialize, Deserialize)]
+pub struct ModelManifest {
+    /// Deployed models
+    #[serde(default)]
+    pub models: Vec<ManifestEntry>,
+}
+
+impl ModelManifest {
+    /// Look up a model by name.
+    #[must_use]
+    pub fn get(&self, name: &str) -> Option<&ManifestEntry> {
+        self.models.iter().find(|m| m.name == name)
+    }
+
+    /// Add a model, replacing any entry with the same name.
+    pub fn upsert(&mut self, entry: ManifestEntry) {
+        match self.models.iter_mut().find(|m| m.name == entry.name) {
+            Some(existing) => *existing = entry,
+            None => self.models.push(entry),
+        }
+    }
+
+    /// Drop models whose file is in `files`, returning whether any were dropped.
+    pub fn remove_files(&mut self, files: &[&str]) -> bool {
+        let before = self.models.len();
+        self.models.retain(|m| !files.contains(&m.file.as_str()));
+        self.models.len() != before
+    }
+}
+
+/// Category of data kept on the storage mount.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+pub enum StorageCategory {
+    /// Model files
+    Models,
+    /// Datasets and outputs
+    Data,
+    /// Caches (downloads, compiled kernels)
+    Cache,
+}
+
+/// Usage of a single storage category.
+#[derive(Debug, Clone)]
+pub struct CategoryUsage {
+    /// Category
+    pub category: StorageCategory,
+    /// Directory measured
+    pub path: PathBuf,
+    /// Bytes used before garbage collection
+    pub used_bytes: u64,
+    /// Quota in bytes, if any
+    pub quota_bytes: Option<u64>,
+    /// Bytes freed by garbage collection
+    pub freed_bytes: u64,
+}
+
+impl CategoryUsage {
+    /// Bytes over quota before garbage collection, if any.
+    #[must_use]
+    pub fn excess_bytes(&self) -> Option<u64> {
+        self.quota_bytes
+            .filter(|quota| self.used_bytes > *quota)
+            .map(|quota| self.used_bytes - quota)
+    }
+
+    /// Whether the category exceeded its quota.
+    #[must_use]
+    pub fn over_quota(&self) -> bool {
+        self.excess_bytes().is_some()
+    }
+}
+
+/// Result of [`StorageLayout::enforce_quotas`].
+#[derive(Debug, Clone)]
+pub struct QuotaReport {
+    /// Per-category usage
+    pub categories: Vec<CategoryUsage>,
+}
+
+impl QuotaReport {
+    /// Categories that exceeded their quota.
+    pub fn over_quota(&self) -> impl Iterator<Item = &CategoryUsage> {
+        self.categories.iter().filter(|c| c.over_quota())
+    }
+
+    /// Usage for a category.
+    #[must_use]
+    pub fn get(&self, category: StorageCategory) -> Option<&CategoryUsage> {
+        self.categories.iter().find(|c| c.category == category)
+    }
+
+    /// Total bytes freed across categories.
+    #[must_use]
+    pub fn freed_bytes(&self) -> u64 {
+        self.categories.iter().map(|c| c.freed_bytes).sum()
+    }
+}
+
+/// Free space found by [`StorageLayout::preflight`].
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct DiskSpace {
+    /// Available bytes
+    pub available_bytes: u64,
+    /// Total and free inodes, if the filesystem has a fixed inode table
+    pub inodes: Option<(u64, u64)>,
+}
+
+/// Mounted tmpfs scratch from [`StorageLayout::ensure_tmpfs_scratch`].
+///
+/// Call [`Self::release`] to undo the mount; dropping the guard unreleased
+/// undoes it in the background, logging any failure.
+#[derive(Debug)]
+pub struct TmpfsScratch {
+    device: JetsonDevice,
+    path: PathBuf,
+    size_mb: u64,
+    cleanup: ScratchCleanup,
+}
+
+/// What releasing a [`TmpfsScratch`] must undo.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+enum ScratchCleanup {
+    /// Nothing: released, or a pre-existing mount of unknown size
+    None,
+    /// The guard mounted the tmpfs
+    Unmount,
+    /// The guard resized a pre-existing tmpfs from this many MB
+    RestoreSize(u64),
+}
+
+impl TmpfsScratch {
+    /// Mount point of the scratch.
+    #[must_use]
+    pub fn path(&self) -> &Path {
+        &self.path
+    }
+
+    /// Size of the scratch in MB.
+    #[must_use]
+    pub const fn size_mb(&self) -> u64 {
+        self.size_mb
+    }
+
+    /// Unmount the scratch, or restore a pre-existing tmpfs to its old size.
+    ///
+    /// # Errors
+    ///
+    /// Returns an error if the unmount fails, e.g. while files are open.
+    pub async fn release(mut self) -> Result<()> {
+        match self.cleanup_command() {
+            Some(command) => {
+                self.cleanup = ScratchCleanup::None;
+                exec(&self.device, &command).await.map(drop)
+            }
+            None => Ok(()),
+        }
+    }
+
+    fn cleanup_command(&self) -> Option<String> {
+        let dir = shell_quote(&self.path.to_string_lossy());
+        match self.cleanup {
+            ScratchCleanup::None => None,
+            ScratchCleanup::Unmount => Some(format!("sudo umount {dir}")),
+            ScratchCleanup::RestoreSize(mb) => {
+                Some(format!("sudo mount -o remount,size={mb}m {dir}"))
+            }
+        }
+    }
+}
+
+impl Drop for TmpfsScratch {
+    /// Best effort: off the async runtime's worker threads when inside one.
+    fn drop(&mut self) {
+        let Some(command) = self.cleanup_command() else {
+            return;
+        };
+        let (device, path) = (self.device.clone(), self.path.clone());
+        let cleanup = move || {
+            if let Err(e) = device.exec_blocking(&command) {
+                tracing::warn!(
+                    device = device.id(),
+                    path = %path.display(),
+                    error = %e,
+                    "Failed to release tmpfs scratch"
+                );
+            }
+        };
+        match tokio::runtime::Handle::try_current() {
+            Ok(runtime) => drop(runtime.spawn_blocking(cleanup)),
+            Err(_) => cleanup(),
         }
     }
 }
@@ -121,6 +1017,130 @@ impl Default for StorageLayout {
 mod tests {
     use super::*;
 
+    const LSPCI_VV: &str = "\
+0001:00:00.0 PCI bridge: NVIDIA Corporation Device 229e (rev a1) (prog-if 00 [Normal decode])
+\tLnkCap:\tPort #0, Speed 16GT/s, Width x8, ASPM not supported
+\tLnkSta:\tSpeed 8GT/s (downgraded), Width x4 (downgraded)
+
+0001:01:00.0 Non-Volatile memory controller: Samsung Electronics Co Ltd NVMe SSD Controller 980 (prog-if 02 [NVM Express])
+\tSubsystem: Samsung Electronics Co Ltd NVMe SSD Controller 980
+\tLnkCap:\tPort #0, Speed 8GT/s, Width x4, ASPM L1, Exit Latency L1 <64us
+\tLnkSta:\tSpeed 5GT/s (downgraded), Width x2 (downgraded)
+\t\tTrErr- Train- SlotClk+ DLActive- BWMgmt- ABWMgmt-
+";
+
+    #[test]
+    fn test_parse_lspci_link() {
+        let link = parse_lspci_link(LSPCI_VV).unwrap();
+        assert_eq!(link, PcieLink { gen: 2, width: 2 });
+        assert_eq!(link.to_string(), "Gen2 x2");
+        assert!(link.is_degraded(PcieLink { gen: 3, width: 4 }));
+        assert!(!link.is_degraded(PcieLink { gen: 2, width: 2 }));
+
+        let healthy = LSPCI_VV.replace(
+            "Speed 5GT/s (downgraded), Width x2",
+            "Speed 8GT/s, Width x4",
+        );
+        let link = parse_lspci_link(&healthy).unwrap();
+        assert!(!link.is_degraded(PcieLink { gen: 3, width: 4 }));
+
+        let bridge_only = LSPCI_VV.split("\n\n").next().unwrap();
+        assert!(matches!(
+            parse_lspci_link(bridge_only),
+            Err(Error::SubsystemUnavailable {
+                subsystem: Subsystem::Nvme,
+                ..
+            })
+        ));
+        let unprivileged = LSPCI_VV.replace("\tLnkSta:", "\tCapabilities: <access denied>");
+        assert!(matches!(
+            parse_lspci_link(&unprivileged),
+            Err(Error::Parse { .. })
+        ));
+    }
+
+    #[test]
+    fn test_parse_sysfs_link() {
+        assert_eq!(
+            parse_sysfs_link("8.0 GT/s PCIe", "4").unwrap(),
+            PcieLink { gen: 3, width: 4 }
+        );
+        assert_eq!(
+            parse_sysfs_link("2.5 GT/s", "1\n").unwrap(),
+            PcieLink { gen: 1, width: 1 }
+        );
+        assert!(matches!(
+            parse_sysfs_link("Unknown", "4"),
+            Err(Error::Parse { .. })
+        ));
+        assert!(matches!(
+            parse_sysfs_link("16.0 GT/s PCIe", "x4"),
+            Err(Error::Parse { .. })
+        ));
+    }
+
+    #[tokio::test]
+    async fn test_pcie_link_reads_each_drives_controller() {
+        use crate::device::mock::{self, MockExecutor};
+        use std::sync::Arc;
+
+        let exec = Arc::new(
+            MockExecutor::new()
+                .on("/sys/block/nvme0n1/", "16.0 GT/s PCIe\n\x1e4\n\x1e")
+                .on("/sys/block/nvme1n1/", "5.0 GT/s PCIe\n\x1e1\n\x1e"),
+        );
+        let device = mock::device("j1", crate::JetsonModel::AgxOrin64GB, &exec);
+        let slot = |path: &str| NvmeDevice {
+            device_path: PathBuf::from(path),
+            mount_point: PathBuf::from("/mnt/nvme"),
+            capacity_bytes: 0,
+            available_bytes: 0,
+        };
+
+        let first = slot("/dev/nvme0n1").pcie_link(&device).await.unwrap();
+        let second = slot("/dev/nvme1n1").pcie_link(&device).await.unwrap();
+        assert_eq!(first, PcieLink { gen: 4, width: 4 });
+        assert_eq!(second, PcieLink { gen: 2, width: 1 });
+        assert!(exec.calls()[1].contains("/sys/block/nvme1n1/device/device/current_link_width"));
+        assert_eq!(exec.count("lspci"), 0);
+    }
+
+    #[tokio::test]
+    async fn test_pcie_link_falls_back_to_lspci() {
+        use crate::device::mock::{self, MockExecutor};
+        use std::sync::Arc;
+
+        let controller = LSPCI_VV.split("\n\n").nth(1).unwrap();
+        let missing = "No such file or directory";
+        let exec = Arc::new(MockExecutor::new().on("lspci -vv -s", controller).fail(
+            "current_link_speed",
+            1,
+            missing,
+        ));
+        let device = mock::device("j1", crate::JetsonModel::OrinNano8GB, &exec);
+        let drive = NvmeDevice {
+            device_path: PathBuf::from("/dev/nvme0n1"),
+            mount_point: PathBuf::from("/mnt/nvme"),
+            capacity_bytes: 0,
+            available_bytes: 0,
+        };
+
+        let link = drive.pcie_link(&device).await.unwrap();
+        assert_eq!(link, PcieLink { gen: 2, width: 2 });
+        assert!(exec.calls()[1].contains("readlink -f /sys/block/nvme0n1/device/device"));
+
+        // Without lspci output for the function there is still no link
+        let exec = Arc::new(MockExecutor::new().fail("current_link_speed", 1, missing));
+        let device = mock::device("j1", crate::JetsonModel::OrinNano8GB, &exec);
+        assert!(matches!(
+            drive.pcie_link(&device).await,
+            Err(Error::SubsystemUnavailable {
+                subsystem: Subsystem::Nvme,
+                ..
+            })
+        ));
+    }
+
     #[test]
     fn test_swap_config_default_ml() {
         let config = SwapConfig::default_ml();
@@ -149,6 +1169,241 @@ mod tests {
         assert_eq!(cloned.path, PathBuf::from("/custom/swap"));
     }
 
+    fn swap_executor(used_gb: u64) -> std::sync::Arc<crate::device::mock::MockExecutor> {
+        let swaps = format!("/dev/zram0 0\n/mnt/nvme/swapfile {}\n", used_gb * GB);
+        std::sync::Arc::new(
+            crate::device::mock::MockExecutor::new()
+                .on("swapon --show", &swaps)
+                .on("stat -c %s", &format!("{}\n", 16 * GB))
+                .on("df -P -B1", DF_BYTES),
+        )
+    }
+
+    #[tokio::test]
+    async fn test_tmpfs_scratch_mounts_and_releases() {
+        use crate::device::mock::{self, MockExecutor};
+        use std::sync::Arc;
+
+        let exec = Arc::new(
+            MockExecutor::new()
+                .on("is-active", "active\n")
+                .on("MemTotal", MEMTOTAL_8GB),
+        );
+        let device = mock::device("j1", crate::JetsonModel::OrinNano8GB, &exec);
+        let layout = StorageLayout::default();
+        let scratch = layout.ensure_tmpfs_scratch(&device, 2048).await.unwrap();
+        assert_eq!(scratch.path(), Path::new(DEFAULT_SCRATCH_DIR));
+        assert_eq!(scratch.size_mb(), 2048);
+        scratch.release().await.unwrap();
+
+        let steps: Vec<String> = exec
+            .calls()
+            .into_iter()
+            .filter(|c| c.starts_with("sudo") && c.contains("mount"))
+            .collect();
+        assert_eq!(
+            steps,
+            [
+                "sudo mkdir -p '/run/cohete/scratch' && \
+                 sudo mount -t tmpfs -o size=2048m,mode=1777 tmpfs '/run/cohete/scratch'",
+                "sudo umount '/run/cohete/scratch'",
+            ]
+        );
+    }
+
+    #[tokio::test]
+    async fn test_tmpfs_scratch_resizes_existing_mount() {
+        use crate::device::mock::{self, MockExecutor};
+        use std::sync::Arc;
+
+        let exec = Arc::new(
+            MockExecutor::new()
+                .on("is-active", "active\n")
+                .on("MemTotal", MEMTOTAL_8GB)
+                .on("findmnt", "tmpfs  268435456\n"),
+        );
+        let device = mock::device("j1", crate::JetsonModel::OrinNano8GB, &exec);
+        let layout = StorageLayout::default().with_scratch_dir("/tmp/scratch");
+        let scratch = layout.ensure_tmpfs_scratch(&device, 512).await.unwrap();
+        scratch.release().await.unwrap();
+
+        let steps: Vec<String> = exec
+            .calls()
+            .into_iter()
+            .filter(|c| c.starts_with("sudo") && c.contains("mount"))
+            .collect();
+        assert_eq!(
+            steps,
+            [
+                "sudo mount -o remount,size=512m '/tmp/scratch'",
+                "sudo mount -o remount,size=256m '/tmp/scratch'",
+            ]
+        );
+    }
+
+    #[test]
+    fn test_tmpfs_scratch_drop_unmounts_only_own_mount() {
+        use crate::device::mock::{self, MockExecutor};
+        use std::sync::Arc;
+
+        let exec = Arc::new(MockExecutor::new());
+        let device = mock::device("j1", crate::JetsonModel::OrinNano8GB, &exec);
+        let scratch = |cleanup| TmpfsScratch {
+            device: device.clone(),
+            path: PathBuf::from("/tmp/scratch"),
+            size_mb: 512,
+            cleanup,
+        };
+        drop(scratch(ScratchCleanup::None));
+        assert!(exec.calls().is_empty());
+        drop(scratch(ScratchCleanup::Unmount));
+        assert_eq!(exec.calls(), ["sudo umount '/tmp/scratch'"]);
+    }
+
+    #[tokio::test]
+    async fn test_tmpfs_scratch_must_fit_memory_budget() {
+        use crate::device::mock::{self, MockExecutor};
+        use std::sync::Arc;
+
+        // 7620MB MemTotal with the desktop running reserves 2GB, leaving 5572MB
+        let exec = Arc::new(
+            MockExecutor::new()
+                .on("is-active", "active\n")
+                .on("MemTotal", MEMTOTAL_8GB),
+        );
+        let device = mock::device("j1", crate::JetsonModel::OrinNano8GB, &exec);
+        let err = StorageLayout::default()
+            .ensure_tmpfs_scratch(&device, 7000)
+            .await
+            .unwrap_err();
+        assert!(matches!(
+            err,
+            Error::InsufficientMemory {
+                requested_mb: 7000,
+                available_mb: 5572
+            }
+        ));
+        assert_eq!(exec.count("mount"), 0);
+    }
+
+    #[tokio::test]
+    async fn test_swap_resize_sequence() {
+        use crate::device::mock;
+
+        let exec = swap_executor(1);
+        let device = mock::device("j1", crate::JetsonModel::OrinNano8GB, &exec);
+        SwapConfig::default_ml().resize(&device, 32).await.unwrap();
+
+        let steps: Vec<String> = exec
+            .calls()
+            .into_iter()
+            .filter(|c| c.starts_with("sudo"))
+            .collect();
+        assert_eq!(
+            steps,
+            [
+                "sudo rm -f '/mnt/nvme/swapfile.new'",
+                "sudo fallocate -l 34359738368 '/mnt/nvme/swapfile.new'",
+                "sudo chmod 600 '/mnt/nvme/swapfile.new'",
+                "sudo mkswap '/mnt/nvme/swapfile.new'",
+                "sudo swapoff '/mnt/nvme/swapfile'",
+                "sudo mv -f '/mnt/nvme/swapfile.new' '/mnt/nvme/swapfile'",
+                "sudo swapon '/mnt/nvme/swapfile'",
+            ]
+        );
+
+        // Already active at the requested size
+        let exec = swap_executor(1);
+        let device = mock::device("j1", crate::JetsonModel::OrinNano8GB, &exec);
+        SwapConfig::default_ml().resize(&device, 16).await.unwrap();
+        assert!(exec.calls().iter().all(|c| !c.starts_with("sudo")));
+    }
+
+    #[tokio::test]
+    async fn test_swap_resize_keeps_original_when_allocation_fails() {
+        use crate::device::mock::{self, MockExecutor};
+        use std::sync::Arc;
+
+        let exec = Arc::new(
+            MockExecutor::new()
+                .fail(
+                    "fallocate",
+                    1,
+                    "fallocate: fallocate failed: No space left on device",
+                )
+                .on("swapon --show", &format!("/mnt/nvme/swapfile {}\n", GB))
+                .on("stat -c %s", &format!("{}\n", 16 * GB))
+                .on("df -P -B1", DF_BYTES),
+        );
+        let device = mock::device("j1", crate::JetsonModel::OrinNano8GB, &exec);
+        SwapConfig::default_ml()
+            .resize(&device, 32)
+            .await
+            .unwrap_err();
+
+        let steps: Vec<String> = exec
+            .calls()
+            .into_iter()
+            .filter(|c| c.starts_with("sudo"))
+            .collect();
+        // The active swap file is never switched off or touched
+        assert!(steps.iter().all(|c| !c.ends_with(" '/mnt/nvme/swapfile'")));
+        assert_eq!(steps.last().unwrap(), "sudo rm -f '/mnt/nvme/swapfile.new'");
+    }
+
+    #[tokio::test]
+    async fn test_swap_resize_restores_original_when_swap_in_fails() {
+        use crate::device::mock::{self, MockExecutor};
+        use std::sync::Arc;
+
+        let exec = Arc::new(
+            MockExecutor::new()
+                .fail("sudo mv", 1, "mv: cannot move: Input/output error")
+                .on("swapon --show", &format!("/mnt/nvme/swapfile {}\n", GB))
+                .on("stat -c %s", &format!("{}\n", 16 * GB))
+                .on("df -P -B1", DF_BYTES),
+        );
+        let device = mock::device("j1", crate::JetsonModel::OrinNano8GB, &exec);
+        SwapConfig::default_ml()
+            .resize(&device, 32)
+            .await
+            .unwrap_err();
+
+        assert_eq!(
+            exec.calls().last().unwrap(),
+            "sudo swapon '/mnt/nvme/swapfile'"
+        );
+        assert_eq!(exec.count("sudo swapoff"), 1);
+    }
+
+    #[tokio::test]
+    async fn test_swap_resize_refuses_shrink_below_usage() {
+        use crate::device::mock;
+
+        let exec = swap_executor(6);
+        let device = mock::device("j1", crate::JetsonModel::OrinNano8GB, &exec);
+        let err = SwapConfig::default_ml()
+            .resize(&device, 4)
+            .await
+            .unwrap_err();
+        assert!(
+            matches!(&err, Error::Storage(m) if m.contains("6144 MB in use")),
+            "{err}"
+        );
+        assert!(exec.calls().iter().all(|c| !c.starts_with("sudo")));
+
+        let err = SwapConfig::default_ml()
+            .resize(&device, 512)
+            .await
+            .unwrap_err();
+        assert!(
+            matches!(&err, Error::Storage(m) if m.contains("MB free")),
+            "{err}"
+        );
+
+        SwapConfig::default_ml().resize(&device, 8).await.unwrap();
+    }
+
     #[test]
     fn test_storage_layout_default() {
         let layout = StorageLayout::default();
@@ -236,4 +1491,407 @@ mod tests {
         };
         assert!((nvme.utilization_percent() - 100.0).abs() < 0.1);
     }
+
+    const DU_OUTPUT: &str = "\
+42949672960\t/mnt/nvme/models
+1048576\t/mnt/nvme/data
+16106127360\t/mnt/nvme/cache
+";
+
+    #[test]
+    fn test_parse_du() {
+        let usage = parse_du(DU_OUTPUT);
+        assert_eq!(usage.len(), 3);
+        assert_eq!(usage[0], (PathBuf::from("/mnt/nvme/models"), 40 * GB));
+        assert_eq!(usage[2], (PathBuf::from("/mnt/nvme/cache"), 15 * GB));
+        assert!(parse_du("du: cannot access '/x': No such file\n").is_empty());
+    }
+
+    #[test]
+    fn test_parse_df() {
+        assert_eq!(parse_df_available(DF_BYTES).unwrap(), 250 * GB);
+        assert_eq!(
+            parse_df_inodes(DF_INODES_EXHAUSTED).unwrap(),
+            Some((15_630_336, 12))
+        );
+        let btrfs = "Filesystem Inodes IUsed IFree IUse% Mounted on\n\
+                     /dev/nvme0n1p1 0 0 0 - /mnt/nvme\n";
+        assert_eq!(parse_df_inodes(btrfs).unwrap(), None);
+        assert!(parse_df_inodes("df: /mnt/nvme: No such file or directory\n").is_err());
+    }
+
+    #[test]
+    fn test_parse_find_listing() {
+        let files =
+            parse_find_listing("1700000000.5 1690000000.0 1024 /mnt/nvme/cache/a b.bin\nbad\n");
+        assert_eq!(
+            files,
+            vec![GcCandidate {
+                atime: 1_700_000_000.5,
+                mtime: 1_690_000_000.0,
+                size: 1024,
+                path: "/mnt/nvme/cache/a b.bin".to_string(),
+            }]
+        );
+    }
+
+    #[test]
+    fn test_gc_candidate_timestamps() {
+        let now = 1_700_000_000.0;
+        let missing_atime = GcCandidate {
+            atime: 0.0,
+            mtime: now - 7200.0,
+            size: 1,
+            path: "a".to_string(),
+        };
+        assert!((missing_atime.last_used(now) - (now - 7200.0)).abs() < 0.001);
+        assert!((missing_atime.age(now) - 7200.0).abs() < 0.001);
+
+        let future = GcCandidate {
+            atime: now + 86_400.0,
+            mtime: now + 86_400.0,
+            ..missing_atime
+        };
+        assert!((future.last_used(now) - now).abs() < 0.001);
+        assert!(future.age(now).abs() < 0.001);
+    }
+
+    #[test]
+    fn test_category_over_quota() {
+        let usage = CategoryUsage {
+            category: StorageCategory::Cache,
+            path: PathBuf::from("/mnt/nvme/cache"),
+            used_bytes: 15 * GB,
+            quota_bytes: Some(10 * GB),
+            freed_bytes: 0,
+        };
+        assert!(usage.over_quota());
+        assert_eq!(usage.excess_bytes(), Some(5 * GB));
+
+        let unlimited = CategoryUsage {
+            quota_bytes: None,
+            ..usage.clone()
+        };
+        assert!(!unlimited.over_quota());
+
+        let under = CategoryUsage {
+            quota_bytes: Some(20 * GB),
+            ..usage
+        };
+        assert_eq!(under.excess_bytes(), None);
+    }
+
+    #[tokio::test]
+    async fn test_enforce_quotas_collects_oldest() {
+        use crate::device::mock::{self, MockExecutor};
+        use std::sync::Arc;
+
+        let listing = format!(
+            "300.0 300.0 {} /mnt/nvme/cache/new.bin\n100.0 100.0 {} /mnt/nvme/cache/old.bin\n200.0 200.0 {} /mnt/nvme/cache/mid.bin\n",
+            5 * GB,
+            3 * GB,
+            7 * GB
+        );
+        let exec = Arc::new(
+            MockExecutor::new()
+                .on("du -sb", DU_OUTPUT)
+                .on("date +%s", "1700000000")
+                .on("find '/mnt/nvme/cache'", &listing),
+        );
+        let device = mock::device("j1", crate::JetsonModel::OrinNano8GB, &exec);
+        let layout = StorageLayout::default()
+            .with_models_quota_gb(64)
+            .with_cache_quota_gb(10);
+
+        let report = layout.enforce_quotas(&device).await.unwrap();
+
+        let over: Vec<_> = report.over_quota().map(|c| c.category).collect();
+        assert_eq!(over, vec![StorageCategory::Cache]);
+        let models = report.get(StorageCategory::Models).unwrap();
+        assert_eq!(models.used_bytes, 40 * GB);
+        assert_eq!(report.freed_bytes(), 10 * GB);
+        assert_eq!(
+            exec.calls().last().unwrap(),
+            "rm -f -- '/mnt/nvme/cache/old.bin' '/mnt/nvme/cache/mid.bin'"
+        );
+        assert_eq!(exec.count("find"), 1);
+    }
+
+    #[tokio::test]
+    async fn test_gc_protects_future_and_recent_files() {
+        use crate::device::mock::{self, MockExecutor};
+        use std::sync::Arc;
+
+        let now = 1_700_000_000_u64;
+        let listing = format!(
+            "{future} {future} {} /mnt/nvme/cache/future.bin\n\
+             {recent} {recent} {} /mnt/nvme/cache/recent.bin\n\
+             0.0 {old} {} /mnt/nvme/cache/no-atime.bin\n\
+             {stale} {stale} {} /mnt/nvme/cache/stale.bin\n",
+            8 * GB,
+            8 * GB,
+            2 * GB,
+            2 * GB,
+            future = now + 365 * 86_400,
+            recent = now - 60,
+            old = now - 30 * 86_400,
+            stale = now - 86_400,
+        );
+        let exec = Arc::new(
+            MockExecutor::new()
+                .on("du -sb", DU_OUTPUT)
+                .on("date +%s", &now.to_string())
+                .on("find '/mnt/nvme/cache'", &listing),
+        );
+        let device = mock::device("j1", crate::JetsonModel::OrinNano8GB, &exec);
+
+        let report = StorageLayout::default()
+            .with_cache_quota_gb(1)
+            .enforce_quotas(&device)
+            .await
+            .unwrap();
+
+        assert_eq!(report.freed_bytes(), 4 * GB);
+        assert_eq!(
+            exec.calls().last().unwrap(),
+            "rm -f -- '/mnt/nvme/cache/no-atime.bin' '/mnt/nvme/cache/stale.bin'"
+        );
+    }
+
+    #[tokio::test]
+    async fn test_gc_keeps_manifest_even_when_oldest() {
+        use crate::device::mock::{self, MockExecutor};
+        use std::sync::Arc;
+
+        let listing = format!(
+            "100.0 100.0 512 /mnt/nvme/models/manifest.json\n\
+             200.0 200.0 {} /mnt/nvme/models/llama.q4_0.gguf\n\
+             300.0 300.0 {} /mnt/nvme/models/model.gguf\n",
+            4 * GB,
+            4 * GB
+        );
+        let exec = Arc::new(
+            MockExecutor::new()
+                .on("du -sb", DU_OUTPUT)
+                .on("date +%s", "1700000000")
+                .on("find '/mnt/nvme/models'", &listing),
+        );
+        let device = mock::device("j1", crate::JetsonModel::OrinNano8GB, &exec);
+
+        let report = StorageLayout::default()
+            .with_models_quota_gb(39)
+            .enforce_quotas(&device)
+            .await
+            .unwrap();
+
+        assert_eq!(report.freed_bytes(), 4 * GB);
+        assert_eq!(
+            exec.calls().last().unwrap(),
+            "rm -f -- '/mnt/nvme/models/llama.q4_0.gguf'"
+        );
+    }
+
+    #[tokio::test]
+    async fn test_gc_prunes_collected_models_from_manifest() {
+        use crate::device::mock::{self, MockExecutor};
+        use std::sync::Arc;
+
+        let listing = format!(
+            "100.0 100.0 512 /mnt/nvme/models/manifest.json\n\
+             200.0 200.0 {} /mnt/nvme/models/llama.q4_0.gguf\n\
+             300.0 300.0 {} /mnt/nvme/models/phi.q5_1.gguf\n",
+            4 * GB,
+            4 * GB
+        );
+        let exec = Arc::new(
+            MockExecutor::new()
+                .on("du -sb", DU_OUTPUT)
+                .on("date +%s", "1700000000")
+                .on("find '/mnt/nvme/models'", &listing)
+                .on(
+                    "manifest.json",
+                    r#"{"models":[
+                        {"name":"llama","quant":"q4_0","file":"llama.q4_0.gguf"},
+                        {"name":"phi","quant":"q5_1","file":"phi.q5_1.gguf"}
+                    ]}"#,
+                ),
+        );
+        let device = mock::device("j1", crate::JetsonModel::OrinNano8GB, &exec);
+
+        StorageLayout::default()
+            .with_models_quota_gb(39)
+            .enforce_quotas(&device)
+            .await
+            .unwrap();
+
+        let calls = exec.calls();
+        let removed: Vec<&str> = calls
+            .last()
+            .unwrap()
+            .strip_prefix("rm -f -- ")
+            .unwrap()
+            .split(' ')
+            .map(|path| path.trim_matches('\''))
+            .collect();
+        let remaining: Vec<String> = parse_find_listing(&listing)
+            .into_iter()
+            .filter(|f| !removed.contains(&f.path.as_str()))
+            .filter_map(|f| f.path.rsplit('/').next().map(str::to_string))
+            .collect();
+        let (_, written) = exec.inputs().pop().unwrap();
+        let manifest: ModelManifest = serde_json::from_slice(&written).unwrap();
+        let listed: Vec<&str> = manifest.models.iter().map(|m| m.file.as_str()).collect();
+        assert_eq!(listed, vec!["phi.q5_1.gguf"]);
+        assert!(listed
+            .iter()
+            .all(|file| remaining.iter().any(|kept| kept == file)));
+    }
+
+    #[tokio::test]
+    async fn test_enforce_quotas_under_quota() {
+        use crate::device::mock::{self, MockExecutor};
+        use std::sync::Arc;
+
+        let exec = Arc::new(MockExecutor::new().on("du -sb", DU_OUTPUT));
+        let device = mock::device("j1", crate::JetsonModel::OrinNano8GB, &exec);
+        let report = StorageLayout::default()
+            .with_cache_quota_gb(20)
+            .enforce_quotas(&device)
+            .await
+            .unwrap();
+        assert_eq!(report.over_quota().count(), 0);
+        assert_eq!(report.freed_bytes(), 0);
+        assert_eq!(exec.calls().len(), 1);
+    }
+
+    #[test]
+    fn test_model_manifest_upsert() {
+        let mut manifest = ModelManifest::default();
+        manifest.upsert(ManifestEntry {
+            name: "llama".to_string(),
+            quant: QuantLevel::Q8_0,
+            file: "llama.q8_0.gguf".to_string(),
+        });
+        manifest.upsert(ManifestEntry {
+            name: "llama".to_string(),
+            quant: QuantLevel::Q4_0,
+            file: "llama.q4_0.gguf".to_string(),
+        });
+        assert_eq!(manifest.models.len(), 1);
+        assert_eq!(manifest.get("llama").unwrap().quant, QuantLevel::Q4_0);
+        assert!(manifest.get("phi").is_none());
+    }
+
+    #[tokio::test]
+    async fn test_read_and_record_manifest() {
+        use crate::device::mock::{self, MockExecutor};
+        use std::sync::Arc;
+
+        let exec = Arc::new(MockExecutor::new().on(
+            "manifest.json",
+            r#"{"models":[{"name":"phi","quant":"q5_1","file":"phi.q5_1.gguf"}]}"#,
+        ));
+        let device = mock::device("j1", crate::JetsonModel::OrinNano8GB, &exec);
+        let layout = StorageLayout::default();
+
+        let manifest = layout.read_manifest(&device).await.unwrap();
+        assert_eq!(manifest.get("phi").unwrap().quant, QuantLevel::Q5_1);
+
+        layout
+            .record_model(
+                &device,
+                ManifestEntry {
+                    name: "llama".to_string(),
+                    quant: QuantLevel::Q4_0,
+                    file: "llama.q4_0.gguf".to_string(),
+                },
+            )
+            .await
+            .unwrap();
+        let (command, written) = exec.inputs().pop().unwrap();
+        assert!(command.contains(
+            "mv -f '/mnt/nvme/models/manifest.json.tmp' '/mnt/nvme/models/manifest.json'"
+        ));
+        let written: ModelManifest = serde_json::from_slice(&written).unwrap();
+        assert_eq!(written.models.len(), 2);
+    }
+
+    #[tokio::test]
+    async fn test_read_manifest_missing_or_invalid() {
+        use crate::device::mock::{self, MockExecutor};
+        use std::sync::Arc;
+
+        let exec = Arc::new(MockExecutor::new());
+        let device = mock::device("j1", crate::JetsonModel::OrinNano8GB, &exec);
+        let manifest = StorageLayout::default()
+            .read_manifest(&device)
+            .await
+            .unwrap();
+        assert!(manifest.models.is_empty());
+
+        let exec = Arc::new(MockExecutor::new().on("manifest.json", "{not json"));
+        let device = mock::device("j1", crate::JetsonModel::OrinNano8GB, &exec);
+        assert!(StorageLayout::default()
+            .read_manifest(&device)
+            .await
+            .is_err());
+    }
+
+    /// `/proc/meminfo` total of an Orin Nano 8GB.
+    const MEMTOTAL_8GB: &str = "MemTotal:        7802880 kB\n";
+
+    const DF_BYTES: &str = "Filesystem 1-blocks Used Available Capacity Mounted on\n\
+                            /dev/nvme0n1p1 502921060352 234489561088 268435456000 47% /mnt/nvme\n";
+    const DF_INODES_EXHAUSTED: &str = "Filesystem Inodes IUsed IFree IUse% Mounted on\n\
+                                       /dev/nvme0n1p1 15630336 15630324 12 100% /mnt/nvme\n";
+
+    #[tokio::test]
+    async fn test_storage_errors_tagged_nvme() {
+        use crate::device::mock::{self, MockExecutor};
+        use std::sync::Arc;
+
+        let exec = Arc::new(MockExecutor::new().fail("df -P", 1, "df: /mnt/nvme: No such file"));
+        let device = mock::device("j1", crate::JetsonModel::OrinNano8GB, &exec);
+        let err = StorageLayout::default()
+            .preflight(&device, GB)
+            .await
+            .unwrap_err();
+        assert_eq!(err.subsystem(), Some(Subsystem::Nvme));
+        assert!(matches!(err.untagged(), Error::CommandFailed { .. }));
+    }
+
+    #[tokio::test]
+    async fn test_preflight_inodes_exhausted() {
+        use crate::device::mock::{self, MockExecutor};
+        use std::sync::Arc;
+
+        let exec = Arc::new(
+            MockExecutor::new()
+                .on("df -P -B1", DF_BYTES)
+                .on("df -P -i", DF_INODES_EXHAUSTED),
+        );
+        let device = mock::device("j1", crate::JetsonModel::OrinNano8GB, &exec);
+        let layout = StorageLayout::default();
+
+        let err = layout.preflight(&device, 4 * GB).await.unwrap_err();
+        match err {
+            Error::Storage(message) => {
+                assert!(message.contains("out of inodes"), "{message}");
+                assert!(message.contains("12 of 15630336"), "{message}");
+            }
+            other => panic!("unexpected error: {other}"),
+        }
+
+        let inodes_free = DF_INODES_EXHAUSTED.replace(" 12 ", " 900000 ");
+        let exec = Arc::new(
+            MockExecutor::new()
+                .on("df -P -B1", DF_BYTES)
+                .on("df -P -i", &inodes_free),
+        );
+        let device = mock::device("j1", crate::JetsonModel::OrinNano8GB, &exec);
+        let space = layout.preflight(&device, 4 * GB).await.unwrap();
+        assert_eq!(space.available_bytes, 250 * GB);
+        let err = layout.preflight(&device, 300 * GB).await.unwrap_err();
+        assert!(err.to_string().contains("307200 MB required"));
+    }
 }
@@ -3,8 +3,46 @@
 //! Provides tegrastats integration, thermal circuit breakers, and
 //! proactive thermal management.
 
-use crate::{device::JetsonDevice, Result};
-use std::time::Duration;
+use crate::{
+    device::JetsonDevice,
+    power::{FanController, PowerController, PowerMode, PowerProfile},
+    Error, Result, Subsystem,
+};
+use futures_util::{stream, Stream, StreamExt};
+use std::collections::VecDeque;
+use std::sync::{Mutex, PoisonError};
+use std::time::{Duration, Instant};
+
+/// Default number of samples kept in monitor history.
+pub const DEFAULT_HISTORY_CAPACITY: usize = 300;
+
+/// sysfs nodes holding thermal zone types and trip points.
+const TRIP_POINT_NODES: [&str; 4] = [
+    "/sys/class/thermal/thermal_zone*/type",
+    "/sys/class/thermal/thermal_zone*/trip_point_*_temp",
+    "/sys/class/thermal/thermal_zone*/trip_point_*_hyst",
+    "/sys/class/thermal/thermal_zone*/trip_point_*_type",
+];
+
+/// Command producing a single tegrastats line.
+const TEGRASTATS_CMD: &str = "tegrastats --interval 100 | head -n 1";
+
+/// Command printing `type millidegrees` for every thermal zone.
+const THERMAL_ZONES_CMD: &str = "for z in /sys/class/thermal/thermal_zone*; do \
+    echo \"$(cat \"$z/type\") $(cat \"$z/temp\")\"; done 2>/dev/null";
+
+/// Fields only tegrastats reports; the rest are read from sysfs and procfs.
+const TEGRASTATS_ONLY: [TegraField; 3] = [
+    TegraField::GpuUtilization,
+    TegraField::CpuUtilization,
+    TegraField::Power,
+];
+
+/// Duration of each calibration phase (load, then idle).
+const CALIBRATION_PHASE: Duration = Duration::from_secs(300);
+
+/// Samples taken per calibration phase.
+const CALIBRATION_SAMPLES: u32 = 30;
 
 /// Thermal statistics from tegrastats.
 #[derive(Debug, Clone, Default)]
@@ -29,6 +67,71 @@ pub struct TegraStats {
     pub power_watts: f32,
 }
 
+impl TegraStats {
+    /// Copy with fields outside `fields` reset to zero.
+    fn only(mut self, fields: &[TegraField]) -> Self {
+        for field in TegraField::ALL.into_iter().filter(|f| !fields.contains(f)) {
+            match field {
+                TegraField::GpuTemp => self.gpu_temp = 0.0,
+                TegraField::CpuTemp => self.cpu_temp = 0.0,
+                TegraField::SocTemp => self.soc_temp = 0.0,
+                TegraField::Memory => {
+                    self.total_memory_mb = 0;
+                    self.used_memory_mb = 0;
+                    self.available_memory_mb = 0;
+                }
+                TegraField::GpuUtilization => self.gpu_utilization = 0.0,
+                TegraField::CpuUtilization => self.cpu_utilization = 0.0,
+                TegraField::Power => self.power_watts = 0.0,
+            }
+        }
+        self
+    }
+
+    /// Temperature of a thermal zone, if tegrastats reports it.
+    #[must_use]
+    pub const fn zone_temp(&self, zone: ThermalZone) -> Option<f32> {
+        match zone {
+            ThermalZone::Gpu => Some(self.gpu_temp),
+            ThermalZone::Cpu => Some(self.cpu_temp),
+            ThermalZone::Soc => Some(self.soc_temp),
+            ThermalZone::Board => None,
+        }
+    }
+}
+
+/// A tegrastats field collected by [`TegraMonitor::sample`].
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+pub enum TegraField {
+    /// `gpu_temp`
+    GpuTemp,
+    /// `cpu_temp`
+    CpuTemp,
+    /// `soc_temp`
+    SocTemp,
+    /// `total_memory_mb`, `used_memory_mb` and `available_memory_mb`
+    Memory,
+    /// `gpu_utilization`
+    GpuUtilization,
+    /// `cpu_utilization`
+    CpuUtilization,
+    /// `power_watts`
+    Power,
+}
+
+impl TegraField {
+    /// Every field.
+    pub const ALL: [Self; 7] = [
+        Self::GpuTemp,
+        Self::CpuTemp,
+        Self::SocTemp,
+        Self::Memory,
+        Self::GpuUtilization,
+        Self::CpuUtilization,
+        Self::Power,
+    ];
+}
+
 /// Thermal zone types.
 #[derive(Debug, Clone, Copy, PartialEq, Eq)]
 pub enum ThermalZone {
@@ -43,7 +146,7 @@ pub enum ThermalZone {
 }
 
 /// Thermal policy configuration.
-#[derive(Debug, Clone)]
+#[derive(Debug, Clone, PartialEq)]
 pub struct ThermalPolicy {
     /// Temperature threshold to pause work (°C)
     pub threshold_c: f32,
@@ -83,6 +186,41 @@ impl ThermalPolicy {
             check_interval_ms,
         }
     }
+
+    /// Expected fraction of full throughput at `temp_c`.
+    ///
+    /// 1.0 up to the threshold, then falling linearly to 0.5 across one
+    /// hysteresis band (threshold minus cooldown) above it.
+    #[must_use]
+    pub fn throughput_factor(&self, temp_c: f32) -> f32 {
+        if temp_c <= self.threshold_c {
+            return 1.0;
+        }
+        let band = (self.threshold_c - self.cooldown_c).max(1.0);
+        let over = ((temp_c - self.threshold_c) / band).min(1.0);
+        over.mul_add(-0.5, 1.0)
+    }
+}
+
+impl ThermalPolicy {
+    /// Derive a policy that pauses `margin_c` below the first passive trip.
+    ///
+    /// The cooldown target sits below the threshold by the trip's hysteresis
+    /// or 10°C, whichever is larger. Returns `None` if there is no passive
+    /// trip point.
+    #[must_use]
+    pub fn from_trip_points(trips: &[TripPoint], margin_c: f32) -> Option<Self> {
+        let first = trips
+            .iter()
+            .filter(|t| t.kind == TripType::Passive)
+            .min_by(|a, b| a.temp_c.total_cmp(&b.temp_c))?;
+        let threshold_c = first.temp_c - margin_c;
+        Some(Self::custom(
+            threshold_c,
+            threshold_c - first.hyst_c.max(10.0),
+            500,
+        ))
+    }
 }
 
 impl Default for ThermalPolicy {
@@ -91,11 +229,63 @@ impl Default for ThermalPolicy {
     }
 }
 
+/// Kind of hardware thermal trip point.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+pub enum TripType {
+    /// Fan or other active cooling kicks in
+    Active,
+    /// Clocks are throttled
+    Passive,
+    /// Hot notification
+    Hot,
+    /// Emergency shutdown
+    Critical,
+    /// Unrecognized type
+    Other,
+}
+
+impl TripType {
+    /// Parse a sysfs `trip_point_N_type` value.
+    #[must_use]
+    pub fn from_sysfs(value: &str) -> Self {
+        match value.trim() {
+            "active" => Self::Active,
+            "passive" => Self::Passive,
+            "hot" => Self::Hot,
+            "critical" => Self::Critical,
+            _ => Self::Other,
+        }
+    }
+}
+
+/// Hardware trip point of a thermal zone.
+#[derive(Debug, Clone, PartialEq)]
+pub struct TripPoint {
+    /// Thermal zone index (`thermal_zoneN`)
+    pub zone: u32,
+    /// Zone type, e.g. `gpu-thermal`
+    pub zone_type: String,
+    /// Trip point index within the zone
+    pub index: u32,
+    /// Trip point kind
+    pub kind: TripType,
+    /// Trip temperature in °C
+    pub temp_c: f32,
+    /// Hysteresis in °C
+    pub hyst_c: f32,
+}
+
 /// Monitor for tegrastats data.
 #[derive(Debug)]
 pub struct TegraMonitor {
+    device: Option<JetsonDevice>,
     policy: ThermalPolicy,
     last_stats: Option<TegraStats>,
+    history: VecDeque<TegraStats>,
+    history_capacity: usize,
+    trip_points: Option<Vec<TripPoint>>,
+    fields: Vec<TegraField>,
+    adaptive_interval: Option<(u64, u64)>,
 }
 
 impl TegraMonitor {
@@ -103,8 +293,14 @@ impl TegraMonitor {
     #[must_use]
     pub fn new() -> Self {
         Self {
+            device: None,
             policy: ThermalPolicy::default(),
             last_stats: None,
+            history: VecDeque::new(),
+            history_capacity: DEFAULT_HISTORY_CAPACITY,
+            trip_points: None,
+            fields: TegraField::ALL.to_vec(),
+            adaptive_interval: None,
         }
     }
 
@@ -113,8 +309,23 @@ impl TegraMonitor {
     /// # Errors
     ///
     /// Returns an error if connection fails.
-    pub fn connect(_device: &JetsonDevice) -> Result<Self> {
-        Ok(Self::new())
+    pub fn connect(device: &JetsonDevice) -> Result<Self> {
+        Ok(Self {
+            device: Some(device.clone()),
+            ..Self::new()
+        })
+    }
+
+    /// Device this monitor samples, if connected.
+    #[must_use]
+    pub const fn device(&self) -> Option<&JetsonDevice> {
+        self.device.as_ref()
+    }
+
+    /// Thermal policy in effect.
+    #[must_use]
+    pub const fn policy(&self) -> &ThermalPolicy {
+        &self.policy
     }
 
     /// Set thermal policy.
@@ -123,14 +334,232 @@ impl TegraMonitor {
         self
     }
 
+    /// Set number of samples kept in history.
+    #[must_use]
+    pub fn with_history_capacity(mut self, capacity: usize) -> Self {
+        self.history_capacity = capacity;
+        while self.history.len() > capacity {
+            self.history.pop_front();
+        }
+        self
+    }
+
+    /// Collect only `fields` when sampling; others are left at zero.
+    ///
+    /// [`TegraField::GpuTemp`] is always collected, since the thermal
+    /// policy, throttle check and circuit breaker read it. Unless GPU or
+    /// CPU utilization or power is requested, samples are read from the
+    /// sysfs thermal zones (and `/proc/meminfo` for memory) instead of
+    /// running `tegrastats`.
+    #[must_use]
+    pub fn with_fields(mut self, fields: &[TegraField]) -> Self {
+        self.fields = fields.to_vec();
+        if !self.fields.contains(&TegraField::GpuTemp) {
+            self.fields.push(TegraField::GpuTemp);
+        }
+        self
+    }
+
+    /// Poll between `min_ms` and `max_ms` depending on temperature.
+    ///
+    /// The interval shrinks linearly from `max_ms` at the policy's cooldown
+    /// temperature to `min_ms` at its threshold, replacing the fixed
+    /// `check_interval_ms`.
+    #[must_use]
+    pub fn with_adaptive_interval(mut self, min_ms: u64, max_ms: u64) -> Self {
+        self.adaptive_interval = Some((min_ms.min(max_ms), min_ms.max(max_ms)));
+        self
+    }
+
+    /// Delay before the next poll, based on the last sample.
+    ///
+    /// The policy's `check_interval_ms` unless an adaptive interval is set
+    /// and a sample has been taken.
+    #[must_use]
+    pub fn poll_interval(&self) -> Duration {
+        let (Some((min_ms, max_ms)), Some(stats)) = (self.adaptive_interval, &self.last_stats)
+        else {
+            return Duration::from_millis(self.policy.check_interval_ms);
+        };
+        let span = (self.policy.threshold_c - self.policy.cooldown_c).max(1.0);
+        let headroom = ((self.policy.threshold_c - stats.gpu_temp) / span).clamp(0.0, 1.0);
+        #[allow(
+            clippy::cast_precision_loss,
+            clippy::cast_possible_truncation,
+            clippy::cast_sign_loss
+        )]
+        let extra_ms = ((max_ms - min_ms) as f32 * headroom).round() as u64;
+        Duration::from_millis(min_ms + extra_ms)
+    }
+
+    /// Samples in history, oldest first.
+    pub fn history(&self) -> impl Iterator<Item = &TegraStats> {
+        self.history.iter()
+    }
+
+    /// Record a sample in history, evicting the oldest when full.
+    pub fn record(&mut self, stats: TegraStats) {
+        if self.history_capacity == 0 {
+            return;
+        }
+        if self.history.len() == self.history_capacity {
+            self.history.pop_front();
+        }
+        self.history.push_back(stats);
+    }
+
+    /// Temperature percentile for a zone over the history window.
+    ///
+    /// Uses the nearest-rank method with `p` in `0.0..=100.0`. Returns
+    /// `None` if history is empty or the zone is not reported.
+    #[must_use]
+    #[allow(
+        clippy::cast_precision_loss,
+        clippy::cast_possible_truncation,
+        clippy::cast_sign_loss
+    )]
+    pub fn temp_percentile(&self, zone: ThermalZone, p: f32) -> Option<f32> {
+        let mut temps: Vec<f32> = self
+            .history
+            .iter()
+            .filter_map(|stats| stats.zone_temp(zone))
+            .collect();
+        if temps.is_empty() {
+            return None;
+        }
+        temps.sort_by(f32::total_cmp);
+
+        let n = temps.len();
+        let rank = ((p.clamp(0.0, 100.0) / 100.0) * n as f32).ceil() as usize;
+        Some(temps[rank.clamp(1, n) - 1])
+    }
+
+    /// Median temperature for a zone over the history window.
+    #[must_use]
+    pub fn temp_p50(&self, zone: ThermalZone) -> Option<f32> {
+        self.temp_percentile(zone, 50.0)
+    }
+
+    /// 95th percentile temperature for a zone over the history window.
+    #[must_use]
+    pub fn temp_p95(&self, zone: ThermalZone) -> Option<f32> {
+        self.temp_percentile(zone, 95.0)
+    }
+
+    /// 99th percentile temperature for a zone over the history window.
+    #[must_use]
+    pub fn temp_p99(&self, zone: ThermalZone) -> Option<f32> {
+        self.temp_percentile(zone, 99.0)
+    }
+
+    /// Read hardware trip points from the device's thermal zones.
+    ///
+    /// Results are cached after the first successful read.
+    ///
+    /// # Errors
+    ///
+    /// Returns an error if the monitor is not connected or sysfs cannot be read.
+    pub async fn hw_trip_points(&mut self) -> Result<Vec<TripPoint>> {
+        if let Some(trips) = &self.trip_points {
+            return Ok(trips.clone());
+        }
+        let device = self
+            .device
+            .as_ref()
+            .ok_or_else(|| Error::SubsystemUnavailable {
+                subsystem: Subsystem::Thermal,
+                reason: "trip points require a monitor connected to a device".to_string(),
+            })?;
+        let nodes = device.read_sysfs_glob(&TRIP_POINT_NODES).await?;
+        let trips = trip_points_from_nodes(nodes.iter().map(|(p, v)| (p.as_str(), v.as_str())));
+        self.trip_points = Some(trips.clone());
+        Ok(trips)
+    }
+
     /// Sample current stats.
     ///
+    /// Blocks the calling thread for the round trip to the device; async
+    /// code should use [`Self::sample_async`].
+    ///
     /// # Errors
     ///
     /// Returns an error if sampling fails.
     pub fn sample(&mut self) -> Result<TegraStats> {
-        // Placeholder - would parse tegrastats output
-        let stats = TegraStats {
+        let stats = match &self.device {
+            Some(device) => {
+                let output = device
+                    .exec_blocking(&self.sample_command())
+                    .map_err(|e| e.with_subsystem(self.sample_subsystem()))?;
+                self.parse_sample(&output)?
+            }
+            None => Self::placeholder_stats().only(&self.fields),
+        };
+        Ok(self.keep(stats))
+    }
+
+    /// Sample current stats without blocking an async worker thread.
+    ///
+    /// # Errors
+    ///
+    /// Returns an error if sampling fails.
+    pub async fn sample_async(&mut self) -> Result<TegraStats> {
+        let stats = match &self.device {
+            Some(device) => {
+                let output = device
+                    .exec(&self.sample_command())
+                    .await
+                    .map_err(|e| e.with_subsystem(self.sample_subsystem()))?;
+                self.parse_sample(&output)?
+            }
+            None => Self::placeholder_stats().only(&self.fields),
+        };
+        Ok(self.keep(stats))
+    }
+
+    /// Whether a requested field is only reported by `tegrastats`.
+    fn needs_tegrastats(&self) -> bool {
+        self.fields.iter().any(|f| TEGRASTATS_ONLY.contains(f))
+    }
+
+    /// Command collecting the requested fields.
+    fn sample_command(&self) -> String {
+        if self.needs_tegrastats() {
+            TEGRASTATS_CMD.to_string()
+        } else if self.fields.contains(&TegraField::Memory) {
+            format!("{THERMAL_ZONES_CMD}; cat /proc/meminfo")
+        } else {
+            THERMAL_ZONES_CMD.to_string()
+        }
+    }
+
+    /// Subsystem behind [`Self::sample_command`], for tagging its failures.
+    fn sample_subsystem(&self) -> Subsystem {
+        if self.needs_tegrastats() {
+            Subsystem::TegraStats
+        } else {
+            Subsystem::Thermal
+        }
+    }
+
+    /// Parse the output of [`Self::sample_command`].
+    fn parse_sample(&self, output: &str) -> Result<TegraStats> {
+        if self.needs_tegrastats() {
+            parse_tegrastats_fields(output, &self.fields)
+        } else {
+            parse_thermal_zones(output, &self.fields)
+        }
+    }
+
+    /// Record `stats` as the latest sample.
+    fn keep(&mut self, stats: TegraStats) -> TegraStats {
+        self.record(stats.clone());
+        self.last_stats = Some(stats.clone());
+        stats
+    }
+
+    /// Stats reported when no device is connected.
+    const fn placeholder_stats() -> TegraStats {
+        TegraStats {
             gpu_temp: 45.0,
             cpu_temp: 42.0,
             soc_temp: 44.0,
@@ -140,9 +569,7 @@ impl TegraMonitor {
             gpu_utilization: 0.0,
             cpu_utilization: 10.0,
             power_watts: 5.0,
-        };
-        self.last_stats = Some(stats.clone());
-        Ok(stats)
+        }
     }
 
     /// Get GPU temperature.
@@ -155,6 +582,16 @@ impl TegraMonitor {
         Ok(stats.gpu_temp)
     }
 
+    /// Get GPU temperature without blocking an async worker thread.
+    ///
+    /// # Errors
+    ///
+    /// Returns an error if temperature read fails.
+    pub async fn gpu_temp_async(&mut self) -> Result<f32> {
+        let stats = self.sample_async().await?;
+        Ok(stats.gpu_temp)
+    }
+
     /// Check if thermal threshold is exceeded.
     ///
     /// # Errors
@@ -171,8 +608,62 @@ impl TegraMonitor {
     ///
     /// Returns an error if monitoring fails.
     pub async fn wait_for_cooldown(&mut self) -> Result<()> {
+        self.wait_for_cooldown_with(CooldownStrategy::Poll).await
+    }
+
+    /// Wait for cooldown, reducing heat or boosting cooling while waiting.
+    ///
+    /// Settings changed by the strategy are restored once the device has
+    /// cooled, or if polling fails.
+    ///
+    /// # Errors
+    ///
+    /// Returns an error if monitoring fails, or the strategy needs a
+    /// connected device and cannot apply or restore its settings.
+    pub async fn wait_for_cooldown_with(&mut self, strategy: CooldownStrategy) -> Result<()> {
+        if self.gpu_temp_async().await? <= self.policy.cooldown_c {
+            return Ok(());
+        }
+        let power = PowerController::new();
+        let fan = FanController::new();
+
+        let restore = match strategy {
+            CooldownStrategy::Poll => None,
+            CooldownStrategy::PollAndThrottle => {
+                let device = self.strategy_device(strategy)?;
+                let previous = power.current_mode(&device).await?;
+                power.set_mode(&device, PowerMode::Power7W).await?;
+                Some((device, Restore::PowerMode(previous)))
+            }
+            CooldownStrategy::PollAndFanBoost => {
+                let device = self.strategy_device(strategy)?;
+                let previous = fan.pwm(&device).await?;
+                fan.set_pwm(&device, u8::MAX).await?;
+                Some((device, Restore::FanPwm(previous)))
+            }
+        };
+
+        let waited = self.poll_until_cool().await;
+        let restored = match restore {
+            None => Ok(()),
+            Some((device, Restore::PowerMode(mode))) => power.set_mode(&device, mode).await,
+            Some((device, Restore::FanPwm(duty))) => fan.set_pwm(&device, duty).await,
+        };
+        waited.and(restored)
+    }
+
+    fn strategy_device(&self, strategy: CooldownStrategy) -> Result<JetsonDevice> {
+        self.device
+            .clone()
+            .ok_or_else(|| Error::SubsystemUnavailable {
+                subsystem: Subsystem::Thermal,
+                reason: format!("{strategy:?} requires a monitor connected to a device"),
+            })
+    }
+
+    async fn poll_until_cool(&mut self) -> Result<()> {
         loop {
-            let temp = self.gpu_temp()?;
+            let temp = self.gpu_temp_async().await?;
             if temp <= self.policy.cooldown_c {
                 return Ok(());
             }
@@ -181,17 +672,144 @@ impl TegraMonitor {
                 target_c = self.policy.cooldown_c,
                 "Waiting for thermal cooldown"
             );
-            tokio::time::sleep(Duration::from_millis(self.policy.check_interval_ms)).await;
+            tokio::time::sleep(self.poll_interval()).await;
         }
     }
 }
 
+/// Setting to put back after a cooldown strategy finishes.
+enum Restore {
+    PowerMode(PowerMode),
+    FanPwm(u8),
+}
+
+/// How to wait out a thermal cooldown.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash, Default)]
+pub enum CooldownStrategy {
+    /// Poll temperature only
+    #[default]
+    Poll,
+    /// Drop to the 7W power mode while cooling
+    PollAndThrottle,
+    /// Run the fan at full speed while cooling
+    PollAndFanBoost,
+}
+
 impl Default for TegraMonitor {
     fn default() -> Self {
         Self::new()
     }
 }
 
+/// Outcome of [`JetsonDevice::thermal_soak`].
+#[derive(Debug, Clone, PartialEq)]
+pub struct SoakResult {
+    /// Load duration
+    pub duration: Duration,
+    /// Hottest CPU or GPU temperature seen (°C)
+    pub peak_temp_c: f32,
+    /// Lowest passive trip point, where clocks throttle (°C)
+    pub throttle_c: f32,
+    /// Peak reached the throttle point
+    pub throttled: bool,
+    /// Fan duty before the load, if the device has a fan
+    pub fan_start_pwm: Option<u8>,
+    /// Highest fan duty seen under load
+    pub fan_peak_pwm: Option<u8>,
+}
+
+impl SoakResult {
+    /// Whether the fan sped up under load (`None` without a fan).
+    ///
+    /// A fan already at full duty counts as responding.
+    #[must_use]
+    pub fn fan_responded(&self) -> Option<bool> {
+        let start = self.fan_start_pwm?;
+        let peak = self.fan_peak_pwm.unwrap_or(start);
+        Some(peak > start || start == u8::MAX)
+    }
+
+    /// Whether the device sustained load without throttling, and its fan
+    /// (if any) responded.
+    #[must_use]
+    pub fn passed(&self) -> bool {
+        !self.throttled && self.fan_responded() != Some(false)
+    }
+
+    /// Degrees of headroom left below the throttle point at peak.
+    #[must_use]
+    pub fn headroom_c(&self) -> f32 {
+        self.throttle_c - self.peak_temp_c
+    }
+}
+
+/// Outcome of [`JetsonDevice::benchmark`]: throughput plus thermal signature.
+#[derive(Debug, Clone, PartialEq)]
+pub struct BenchResult {
+    /// Benchmark duration
+    pub duration: Duration,
+    /// Throughput reported by the workload, if it printed one
+    pub tokens_per_sec: Option<f64>,
+    /// Hottest CPU or GPU temperature seen (°C)
+    pub peak_temp: f32,
+    /// Mean temperature over the final quarter of the run (°C)
+    pub steady_state_temp: f32,
+    /// Temperature rise from the first sample to the peak (°C/s)
+    pub temp_rise_rate: f32,
+    /// Temperature reached the throttle point
+    pub throttled: bool,
+    /// Time from start until the throttle point was first reached
+    pub time_to_throttle: Option<Duration>,
+}
+
+impl BenchResult {
+    /// Build from `(elapsed, temp)` samples taken in order during the run.
+    #[must_use]
+    pub fn from_samples(
+        duration: Duration,
+        tokens_per_sec: Option<f64>,
+        samples: &[(Duration, f32)],
+        throttle_c: f32,
+    ) -> Self {
+        let first = samples.first().copied().unwrap_or((Duration::ZERO, 0.0));
+        let peak = samples
+            .iter()
+            .copied()
+            .reduce(|a, b| if b.1 > a.1 { b } else { a })
+            .unwrap_or(first);
+        let tail = &samples[samples.len() - samples.len().div_ceil(4)..];
+        #[allow(clippy::cast_precision_loss)]
+        let steady_state_temp = if tail.is_empty() {
+            0.0
+        } else {
+            tail.iter().map(|s| s.1).sum::<f32>() / tail.len() as f32
+        };
+        let rise_secs = peak.0.saturating_sub(first.0).as_secs_f32();
+        let temp_rise_rate = if rise_secs > 0.0 {
+            (peak.1 - first.1) / rise_secs
+        } else {
+            0.0
+        };
+        let time_to_throttle = samples.iter().find(|s| s.1 >= throttle_c).map(|s| s.0);
+        Self {
+            duration,
+            tokens_per_sec,
+            peak_temp: peak.1,
+            steady_state_temp,
+            temp_rise_rate,
+            throttled: time_to_throttle.is_some(),
+            time_to_throttle,
+        }
+    }
+
+    /// Whether the device can run this workload continuously without
+    /// throttling.
+    #[must_use]
+    pub const fn sustainable(&self) -> bool {
+        !self.throttled
+    }
+}
+
 /// Thermal circuit breaker - Jidoka pattern.
 ///
 /// Automatically stops work when temperature exceeds threshold.
@@ -216,6 +834,19 @@ impl ThermalCircuitBreaker {
         self.monitor.is_throttled()
     }
 
+    /// Admit new work only if the circuit is closed, without waiting.
+    ///
+    /// # Errors
+    ///
+    /// Returns an error if temperature check fails.
+    pub fn try_admit(&mut self) -> Result<bool> {
+        let admitted = !self.is_open()?;
+        if !admitted {
+            tracing::debug!("Thermal circuit breaker OPEN - admission rejected");
+        }
+        Ok(admitted)
+    }
+
     /// Guard work with thermal protection.
     ///
     /// # Errors
@@ -226,64 +857,717 @@ impl ThermalCircuitBreaker {
         F: std::future::Future<Output = Result<T>>,
     {
         // Check before starting
-        if self.is_open()? {
+        self.wait_if_open().await?;
+        work.await
+    }
+
+    /// Guard a stream, checking temperature before each item.
+    ///
+    /// The wrapped stream is not polled while the breaker is open, so a
+    /// producer such as token-by-token generation is paused until the device
+    /// has cooled. A failed check is yielded as an error and ends the stream.
+    pub fn guard_stream<'a, S>(&'a mut self, items: S) -> impl Stream<Item = Result<S::Item>> + 'a
+    where
+        S: Stream + 'a,
+    {
+        stream::unfold(Some((self, Box::pin(items))), |state| async move {
+            let (breaker, mut items) = state?;
+            if let Err(e) = breaker.wait_if_open().await {
+                return Some((Err(e), None));
+            }
+            let item = items.next().await?;
+            Some((Ok(item), Some((breaker, items))))
+        })
+    }
+
+    async fn wait_if_open(&mut self) -> Result<()> {
+        if self.monitor.gpu_temp_async().await? > self.monitor.policy().threshold_c {
             tracing::warn!("Thermal circuit breaker OPEN - waiting for cooldown");
             self.monitor.wait_for_cooldown().await?;
         }
-
-        work.await
+        Ok(())
     }
 }
 
-#[cfg(test)]
-mod tests {
-    use super::*;
+/// Admission control holding a device near a target sustained temperature.
+///
+/// Rather than pausing at a threshold, the permitted request rate is
+/// adjusted on every temperature reading: raised while below the target,
+/// cut while above it, proportionally to the distance. [`Self::acquire`]
+/// spaces requests at the current rate.
+#[derive(Debug)]
+pub struct ThermalRateLimiter {
+    target_c: f32,
+    min_rps: f64,
+    max_rps: f64,
+    gain_per_c: f64,
+    state: Mutex<RateState>,
+}
 
-    #[test]
-    fn test_thermal_policy_conservative() {
-        let policy = ThermalPolicy::conservative();
-        assert_eq!(policy.threshold_c, 65.0);
-        assert_eq!(policy.cooldown_c, 55.0);
-        assert_eq!(policy.check_interval_ms, 500);
+#[derive(Debug)]
+struct RateState {
+    rps: f64,
+    next_slot: tokio::time::Instant,
+}
+
+impl ThermalRateLimiter {
+    /// Create a limiter targeting `target_c`, allowing between `min_rps`
+    /// and `max_rps` requests per second. Starts at `max_rps`.
+    #[must_use]
+    pub fn new(target_c: f32, min_rps: f64, max_rps: f64) -> Self {
+        let min_rps = min_rps.max(f64::EPSILON);
+        let max_rps = max_rps.max(min_rps);
+        Self {
+            target_c,
+            min_rps,
+            max_rps,
+            gain_per_c: 0.05,
+            state: Mutex::new(RateState {
+                rps: max_rps,
+                next_slot: tokio::time::Instant::now(),
+            }),
+        }
     }
 
-    #[test]
-    fn test_thermal_policy_aggressive() {
-        let policy = ThermalPolicy::aggressive();
-        assert_eq!(policy.threshold_c, 75.0);
-        assert_eq!(policy.cooldown_c, 65.0);
-        assert_eq!(policy.check_interval_ms, 1000);
+    /// Set the fractional rate change per °C from target (default 0.05).
+    #[must_use]
+    pub const fn with_gain(mut self, gain_per_c: f64) -> Self {
+        self.gain_per_c = gain_per_c;
+        self
     }
 
-    #[test]
-    fn test_thermal_policy_custom() {
-        let policy = ThermalPolicy::custom(80.0, 70.0, 250);
-        assert_eq!(policy.threshold_c, 80.0);
-        assert_eq!(policy.cooldown_c, 70.0);
-        assert_eq!(policy.check_interval_ms, 250);
+    /// Target sustained temperature (°C).
+    #[must_use]
+    pub const fn target_c(&self) -> f32 {
+        self.target_c
     }
 
-    #[test]
-    fn test_thermal_policy_default() {
-        let policy = ThermalPolicy::default();
-        assert_eq!(policy.threshold_c, 65.0);
-        assert_eq!(policy.cooldown_c, 55.0);
+    /// Currently permitted requests per second.
+    #[must_use]
+    pub fn rate(&self) -> f64 {
+        self.lock().rps
     }
 
-    #[test]
-    fn test_thermal_policy_clone() {
-        let policy = ThermalPolicy::aggressive();
-        let cloned = policy.clone();
-        assert_eq!(cloned.threshold_c, policy.threshold_c);
-        assert_eq!(cloned.cooldown_c, policy.cooldown_c);
+    /// Adjust the rate for a temperature reading and return the new rate.
+    ///
+    /// Each reading scales the rate by `1 + gain * (target - temp)`,
+    /// limited to halving or a 50% increase per step.
+    pub fn observe(&self, temp_c: f32) -> f64 {
+        let error = f64::from(self.target_c - temp_c);
+        let factor = self.gain_per_c.mul_add(error, 1.0).clamp(0.5, 1.5);
+        let mut state = self.lock();
+        state.rps = (state.rps * factor).clamp(self.min_rps, self.max_rps);
+        state.rps
     }
 
-    #[test]
-    fn test_tegra_stats_default() {
-        let stats = TegraStats::default();
-        assert_eq!(stats.gpu_temp, 0.0);
-        assert_eq!(stats.cpu_temp, 0.0);
-        assert_eq!(stats.total_memory_mb, 0);
+    /// Sample the monitor's GPU temperature and adjust the rate.
+    ///
+    /// # Errors
+    ///
+    /// Returns an error if the temperature cannot be read.
+    pub fn observe_monitor(&self, monitor: &mut TegraMonitor) -> Result<f64> {
+        Ok(self.observe(monitor.gpu_temp()?))
+    }
+
+    /// Wait until a request may start at the current rate.
+    pub async fn acquire(&self) {
+        let wait_until = {
+            let mut state = self.lock();
+            let now = tokio::time::Instant::now();
+            let slot = state.next_slot.max(now);
+            state.next_slot = slot + Duration::from_secs_f64(1.0 / state.rps);
+            slot
+        };
+        tokio::time::sleep_until(wait_until).await;
+    }
+
+    fn lock(&self) -> std::sync::MutexGuard<'_, RateState> {
+        self.state.lock().unwrap_or_else(PoisonError::into_inner)
+    }
+}
+
+/// Active/rest duty cycle for passively cooled devices.
+///
+/// Accumulates "hot time" while work runs; once it reaches the active
+/// budget, work must rest for `rest * hot / active` before running again,
+/// so the configured ratio holds even when a check comes late. A scheduler
+/// polls [`Self::can_run_now`] before each unit of work.
+#[derive(Debug, Clone)]
+pub struct ThermalDutyCycle {
+    active: Duration,
+    rest: Duration,
+    hot: Duration,
+    running_since: Option<Instant>,
+    idle_since: Option<Instant>,
+    rest_until: Option<Instant>,
+}
+
+impl ThermalDutyCycle {
+    /// Allow `active` of work followed by `rest` of cooling.
+    #[must_use]
+    pub fn new(active: Duration, rest: Duration) -> Self {
+        Self {
+            active: active.max(Duration::from_millis(1)),
+            rest,
+            hot: Duration::ZERO,
+            running_since: None,
+            idle_since: None,
+            rest_until: None,
+        }
+    }
+
+    /// Fraction of time work may run (`active / (active + rest)`).
+    #[must_use]
+    pub fn ratio(&self) -> f64 {
+        let active = self.active.as_secs_f64();
+        active / (active + self.rest.as_secs_f64())
+    }
+
+    /// Hot time accumulated in the current active period.
+    #[must_use]
+    pub const fn hot_time(&self) -> Duration {
+        self.hot
+    }
+
+    /// Whether work may run now; counts time since the last `true` as hot.
+    pub fn can_run_now(&mut self) -> bool {
+        self.can_run_at(Instant::now())
+    }
+
+    /// [`Self::can_run_now`] at an explicit instant.
+    pub fn can_run_at(&mut self, now: Instant) -> bool {
+        if let Some(since) = self.running_since.take() {
+            self.hot += now.saturating_duration_since(since);
+        }
+        if let Some(until) = self.rest_until {
+            if now < until {
+                return false;
+            }
+            self.rest_until = None;
+            self.hot = Duration::ZERO;
+        }
+        // An idle gap as long as a full rest cools the device just the same
+        if let Some(idle) = self.idle_since.take() {
+            if now.saturating_duration_since(idle) >= self.rest {
+                self.hot = Duration::ZERO;
+            }
+        }
+        if self.hot >= self.active {
+            let rest = self
+                .rest
+                .mul_f64(self.hot.as_secs_f64() / self.active.as_secs_f64());
+            self.rest_until = Some(now + rest);
+            return false;
+        }
+        self.running_since = Some(now);
+        true
+    }
+
+    /// Mark work as stopped, counting its hot time so far.
+    pub fn stop(&mut self) {
+        self.stop_at(Instant::now());
+    }
+
+    /// [`Self::stop`] at an explicit instant.
+    pub fn stop_at(&mut self, now: Instant) {
+        if let Some(since) = self.running_since.take() {
+            self.hot += now.saturating_duration_since(since);
+            self.idle_since = Some(now);
+        }
+    }
+
+    /// Time left before work may run again (zero when not resting).
+    #[must_use]
+    pub fn rest_remaining(&self) -> Duration {
+        self.rest_remaining_at(Instant::now())
+    }
+
+    /// [`Self::rest_remaining`] at an explicit instant.
+    #[must_use]
+    pub fn rest_remaining_at(&self, now: Instant) -> Duration {
+        self.rest_until
+            .map_or(Duration::ZERO, |until| until.saturating_duration_since(now))
+    }
+}
+
+/// Parse one line of tegrastats output.
+///
+/// # Errors
+///
+/// Returns `Error::Parse` if the line has no GPU temperature.
+pub fn parse_tegrastats(line: &str) -> Result<TegraStats> {
+    parse_tegrastats_fields(line, &TegraField::ALL)
+}
+
+/// Parse only `fields` from one line of tegrastats output.
+///
+/// # Errors
+///
+/// Returns `Error::Parse` if [`TegraField::GpuTemp`] is requested and the
+/// line has no GPU temperature.
+pub fn parse_tegrastats_fields(line: &str, fields: &[TegraField]) -> Result<TegraStats> {
+    let wants = |field| fields.contains(&field);
+    let tokens: Vec<&str> = line.split_whitespace().collect();
+    let mut stats = TegraStats::default();
+    let mut gpu_found = false;
+    let mut rail_total_mw = 0.0;
+    let mut vdd_in_mw = None;
+
+    for (i, token) in tokens.iter().enumerate() {
+        let next = tokens.get(i + 1).copied().unwrap_or_default();
+        match *token {
+            "RAM" if wants(TegraField::Memory) => {
+                if let Some((used, total)) = next.trim_end_matches("MB").split_once('/') {
+                    stats.used_memory_mb = used.parse().unwrap_or_default();
+                    stats.total_memory_mb = total.parse().unwrap_or_default();
+                    stats.available_memory_mb =
+                        stats.total_memory_mb.saturating_sub(stats.used_memory_mb);
+                }
+            }
+            "CPU" if next.starts_with('[') && wants(TegraField::CpuUtilization) => {
+                stats.cpu_utilization = parse_cpu_loads(next);
+            }
+            "GR3D_FREQ" if wants(TegraField::GpuUtilization) => {
+                stats.gpu_utilization = leading_number(next).unwrap_or_default();
+            }
+            _ => {}
+        }
+
+        if let Some((name, temp)) = token.split_once('@') {
+            if let Some(temp) = temp.strip_suffix('C').and_then(|t| t.parse::<f32>().ok()) {
+                match name {
+                    "GPU" if wants(TegraField::GpuTemp) => {
+                        stats.gpu_temp = temp;
+                        gpu_found = true;
+                    }
+                    "CPU" if wants(TegraField::CpuTemp) => stats.cpu_temp = temp,
+                    soc if soc.starts_with("SOC") && wants(TegraField::SocTemp) => {
+                        stats.soc_temp = stats.soc_temp.max(temp);
+                    }
+                    _ => {}
+                }
+            }
+        }
+
+        // Rails are reported as `NAME current/average`, e.g. `VDD_IN 4825mW/4825mW`
+        if !wants(TegraField::Power) {
+            continue;
+        }
+        if let Some(current) = next
+            .split_once('/')
+            .filter(|(_, avg)| avg.ends_with("mW"))
+            .map(|(cur, _)| cur.trim_end_matches("mW"))
+        {
+            if let Ok(mw) = current.parse::<f32>() {
+                rail_total_mw += mw;
+                if *token == "VDD_IN" {
+                    vdd_in_mw = Some(mw);
+                }
+            }
+        }
+    }
+
+    if !gpu_found && wants(TegraField::GpuTemp) {
+        return Err(Error::Parse {
+            context: "tegrastats".to_string(),
+            message: format!("no GPU temperature in: {line}"),
+        });
+    }
+
+    stats.power_watts = vdd_in_mw.unwrap_or(rail_total_mw) / 1000.0;
+    Ok(stats)
+}
+
+/// Parse `type millidegrees` thermal zone lines, followed by
+/// `/proc/meminfo` when memory is requested, into `fields`.
+///
+/// # Errors
+///
+/// Returns `Error::Parse` if [`TegraField::GpuTemp`] is requested and no
+/// `gpu-thermal` zone reports a temperature.
+fn parse_thermal_zones(output: &str, fields: &[TegraField]) -> Result<TegraStats> {
+    let wants = |field| fields.contains(&field);
+    let mut stats = TegraStats::default();
+    let mut gpu_found = false;
+
+    for line in output.lines() {
+        let Some((zone, value)) = line.trim().split_once(' ') else {
+            continue;
+        };
+        let Ok(millidegrees) = value.trim().parse::<i32>() else {
+            continue;
+        };
+        let temp = millicelsius(millidegrees);
+        match zone {
+            "gpu-thermal" if wants(TegraField::GpuTemp) => {
+                stats.gpu_temp = temp;
+                gpu_found = true;
+            }
+            "cpu-thermal" if wants(TegraField::CpuTemp) => stats.cpu_temp = temp,
+            soc if soc.starts_with("soc") && wants(TegraField::SocTemp) => {
+                stats.soc_temp = stats.soc_temp.max(temp);
+            }
+            _ => {}
+        }
+    }
+
+    if wants(TegraField::Memory) {
+        let meminfo_mb = |key: &str| {
+            output.lines().find_map(|line| {
+                let kb = line.strip_prefix(key)?.trim().strip_suffix("kB")?;
+                kb.trim().parse::<u64>().ok().map(|kb| kb / 1024)
+            })
+        };
+        stats.total_memory_mb = meminfo_mb("MemTotal:").unwrap_or_default();
+        stats.available_memory_mb = meminfo_mb("MemAvailable:").unwrap_or_default();
+        stats.used_memory_mb = stats
+            .total_memory_mb
+            .saturating_sub(stats.available_memory_mb);
+    }
+
+    if !gpu_found && wants(TegraField::GpuTemp) {
+        return Err(Error::Parse {
+            context: "thermal zones".to_string(),
+            message: format!("no gpu-thermal zone in: {}", output.trim()),
+        });
+    }
+    Ok(stats)
+}
+
+/// Parse `path:value` lines for thermal zone sysfs files into trip points.
+///
+/// Trip points missing a temperature are dropped; sorted by zone and index.
+#[must_use]
+pub fn parse_trip_points(output: &str) -> Vec<TripPoint> {
+    trip_points_from_nodes(output.lines().filter_map(|line| line.split_once(':')))
+}
+
+/// Build trip points from `(path, value)` pairs of thermal zone nodes.
+fn trip_points_from_nodes<'a>(
+    nodes: impl IntoIterator<Item = (&'a str, &'a str)>,
+) -> Vec<TripPoint> {
+    use std::collections::BTreeMap;
+
+    let mut zone_types: BTreeMap<u32, String> = BTreeMap::new();
+    let mut trips: BTreeMap<(u32, u32), (Option<f32>, f32, TripType)> = BTreeMap::new();
+
+    for (path, value) in nodes {
+        let mut parts = path.rsplit('/');
+        let (Some(file), Some(zone_dir)) = (parts.next(), parts.next()) else {
+            continue;
+        };
+        let Some(zone) = zone_dir
+            .strip_prefix("thermal_zone")
+            .and_then(|z| z.parse().ok())
+        else {
+            continue;
+        };
+        if file == "type" {
+            zone_types.insert(zone, value.trim().to_string());
+            continue;
+        }
+        let Some((index, attr)) = file
+            .strip_prefix("trip_point_")
+            .and_then(|rest| rest.split_once('_'))
+        else {
+            continue;
+        };
+        let Ok(index) = index.parse() else {
+            continue;
+        };
+        let entry = trips
+            .entry((zone, index))
+            .or_insert((None, 0.0, TripType::Other));
+        let millidegrees = || value.trim().parse::<i32>().ok().map(millicelsius);
+        match attr {
+            "temp" => entry.0 = millidegrees(),
+            "hyst" => entry.1 = millidegrees().unwrap_or_default(),
+            "type" => entry.2 = TripType::from_sysfs(value),
+            _ => {}
+        }
+    }
+
+    trips
+        .into_iter()
+        .filter_map(|((zone, index), (temp, hyst_c, kind))| {
+            Some(TripPoint {
+                zone,
+                zone_type: zone_types.get(&zone).cloned().unwrap_or_default(),
+                index,
+                kind,
+                temp_c: temp?,
+                hyst_c,
+            })
+        })
+        .collect()
+}
+
+/// Convert sysfs millidegrees to °C.
+#[allow(clippy::cast_precision_loss)]
+fn millicelsius(value: i32) -> f32 {
+    value as f32 / 1000.0
+}
+
+/// Average load of online cores from `[2%@729,1%@729,off]`.
+#[allow(clippy::cast_precision_loss)]
+fn parse_cpu_loads(field: &str) -> f32 {
+    let loads: Vec<f32> = field
+        .trim_matches(|c| c == '[' || c == ']')
+        .split(',')
+        .filter_map(leading_number)
+        .collect();
+    if loads.is_empty() {
+        return 0.0;
+    }
+    loads.iter().sum::<f32>() / loads.len() as f32
+}
+
+/// Number at the start of a token such as `42%@729`.
+fn leading_number(token: &str) -> Option<f32> {
+    let end = token
+        .find(|c: char| !(c.is_ascii_digit() || c == '.'))
+        .unwrap_or(token.len());
+    token[..end].parse().ok()
+}
+
+/// First-order thermal model of a device's cooling solution.
+///
+/// Temperature approaches a steady state exponentially: toward
+/// `load_steady` with time constant `tau_heat` under load, and toward
+/// `ambient` with `tau_cool` at idle. Heatsink, fan, and passive setups
+/// differ mainly in these constants.
+#[derive(Debug, Clone, PartialEq)]
+pub struct ThermalModel {
+    /// Heating time constant under load
+    pub tau_heat: Duration,
+    /// Cooling time constant at idle
+    pub tau_cool: Duration,
+    /// Idle steady-state temperature (°C)
+    pub ambient: f32,
+    /// Steady-state temperature under load (°C)
+    pub load_steady: f32,
+}
+
+impl ThermalModel {
+    /// Calibrate by running a load/idle cycle on the monitor's device.
+    ///
+    /// Applies the power profile's mode, runs `stress-ng` for one phase,
+    /// then lets the device idle for another, sampling GPU temperature
+    /// throughout. The previous power mode is restored afterwards, whether
+    /// or not calibration succeeds. Takes about ten minutes.
+    ///
+    /// # Errors
+    ///
+    /// Returns an error if the monitor is not connected, the load cannot be
+    /// started, or the measured curves cannot be fitted.
+    pub async fn calibrate(
+        monitor: &mut TegraMonitor,
+        power_profile: &PowerProfile,
+    ) -> Result<Self> {
+        Self::calibrate_for(monitor, power_profile, CALIBRATION_PHASE).await
+    }
+
+    /// Calibrate with a custom phase duration.
+    ///
+    /// # Errors
+    ///
+    /// See [`Self::calibrate`].
+    pub async fn calibrate_for(
+        monitor: &mut TegraMonitor,
+        power_profile: &PowerProfile,
+        phase: Duration,
+    ) -> Result<Self> {
+        let device = monitor
+            .device()
+            .cloned()
+            .ok_or_else(|| Error::SubsystemUnavailable {
+                subsystem: Subsystem::Thermal,
+                reason: "calibration requires a monitor connected to a device".to_string(),
+            })?;
+
+        let power = PowerController::new();
+        let previous = power.current_mode(&device).await?;
+        let calibrated = Self::run_cycle(monitor, &device, power_profile.mode, phase).await;
+        let restored = power.set_mode(&device, previous).await;
+        calibrated.and_then(|model| restored.map(|()| model))
+    }
+
+    async fn run_cycle(
+        monitor: &mut TegraMonitor,
+        device: &JetsonDevice,
+        mode: PowerMode,
+        phase: Duration,
+    ) -> Result<Self> {
+        PowerController::new().set_mode(device, mode).await?;
+        device
+            .exec(&format!(
+                "nohup stress-ng --cpu 0 --timeout {}s >/dev/null 2>&1 &",
+                phase.as_secs().max(1)
+            ))
+            .await
+            .map_err(|e| e.with_subsystem(Subsystem::Thermal))?;
+
+        let interval = phase / CALIBRATION_SAMPLES;
+        let heating = Self::sample_phase(monitor, interval).await?;
+        let cooling = Self::sample_phase(monitor, interval).await?;
+        Self::fit(&heating, &cooling)
+    }
+
+    async fn sample_phase(
+        monitor: &mut TegraMonitor,
+        interval: Duration,
+    ) -> Result<Vec<(Duration, f32)>> {
+        let mut samples = Vec::new();
+        for i in 0..CALIBRATION_SAMPLES {
+            if i > 0 {
+                tokio::time::sleep(interval).await;
+            }
+            samples.push((interval * i, monitor.gpu_temp_async().await?));
+        }
+        Ok(samples)
+    }
+
+    /// Fit the model to heating and cooling curves.
+    ///
+    /// Each curve is `(elapsed, temperature)` samples starting at the phase
+    /// transition. Curves should run several time constants so the last
+    /// sample approximates the steady state.
+    ///
+    /// # Errors
+    ///
+    /// Returns `Error::Parse` if a curve has too few samples or the
+    /// temperature barely changes.
+    pub fn fit(heating: &[(Duration, f32)], cooling: &[(Duration, f32)]) -> Result<Self> {
+        let (tau_heat, load_steady) = time_constant(heating, "heating")?;
+        let (tau_cool, ambient) = time_constant(cooling, "cooling")?;
+        Ok(Self {
+            tau_heat,
+            tau_cool,
+            ambient,
+            load_steady,
+        })
+    }
+
+    /// Predict temperature after `horizon` from `current_c`.
+    #[must_use]
+    pub fn predict_temp(&self, current_c: f32, under_load: bool, horizon: Duration) -> f32 {
+        let (target, tau) = if under_load {
+            (self.load_steady, self.tau_heat)
+        } else {
+            (self.ambient, self.tau_cool)
+        };
+        if tau.is_zero() {
+            return target;
+        }
+        let decay = (-horizon.as_secs_f32() / tau.as_secs_f32()).exp();
+        (current_c - target).mul_add(decay, target)
+    }
+
+    /// Time until temperature crosses `limit_c` under load, if it ever does.
+    #[must_use]
+    pub fn time_to_reach(&self, current_c: f32, limit_c: f32) -> Option<Duration> {
+        if current_c >= limit_c {
+            return Some(Duration::ZERO);
+        }
+        if self.load_steady <= limit_c {
+            return None;
+        }
+        let remaining = (self.load_steady - limit_c) / (self.load_steady - current_c);
+        Some(self.tau_heat.mul_f32(-remaining.ln()))
+    }
+}
+
+/// Estimate a time constant from the 63.2% crossing of a step response.
+///
+/// Returns the time constant and the final (steady-state) temperature.
+fn time_constant(curve: &[(Duration, f32)], phase: &str) -> Result<(Duration, f32)> {
+    let fit_error = |message: &str| Error::Parse {
+        context: format!("thermal calibration ({phase})"),
+        message: message.to_string(),
+    };
+    let (Some(&(_, start)), Some(&(_, end))) = (curve.first(), curve.last()) else {
+        return Err(fit_error("no samples"));
+    };
+    if curve.len() < 3 {
+        return Err(fit_error("need at least 3 samples"));
+    }
+    let delta = end - start;
+    if delta.abs() < 1.0 {
+        return Err(fit_error("temperature changed by less than 1°C"));
+    }
+
+    let target = 0.632_f32.mul_add(delta, start);
+    let crossed = |temp: f32| {
+        if delta > 0.0 {
+            temp >= target
+        } else {
+            temp <= target
+        }
+    };
+    curve
+        .windows(2)
+        .find(|w| crossed(w[1].1))
+        .map(|w| {
+            let ((t0, temp0), (t1, temp1)) = (w[0], w[1]);
+            let frac = if (temp1 - temp0).abs() < f32::EPSILON {
+                1.0
+            } else {
+                ((target - temp0) / (temp1 - temp0)).clamp(0.0, 1.0)
+            };
+            (t0 + t1.saturating_sub(t0).mul_f32(frac), end)
+        })
+        .ok_or_else(|| fit_error("curve never crossed 63% of its range"))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_thermal_policy_conservative() {
+        let policy = ThermalPolicy::conservative();
+        assert_eq!(policy.threshold_c, 65.0);
+        assert_eq!(policy.cooldown_c, 55.0);
+        assert_eq!(policy.check_interval_ms, 500);
+    }
+
+    #[test]
+    fn test_thermal_policy_aggressive() {
+        let policy = ThermalPolicy::aggressive();
+        assert_eq!(policy.threshold_c, 75.0);
+        assert_eq!(policy.cooldown_c, 65.0);
+        assert_eq!(policy.check_interval_ms, 1000);
+    }
+
+    #[test]
+    fn test_thermal_policy_custom() {
+        let policy = ThermalPolicy::custom(80.0, 70.0, 250);
+        assert_eq!(policy.threshold_c, 80.0);
+        assert_eq!(policy.cooldown_c, 70.0);
+        assert_eq!(policy.check_interval_ms, 250);
+    }
+
+    #[test]
+    fn test_thermal_policy_default() {
+        let policy = ThermalPolicy::default();
+        assert_eq!(policy.threshold_c, 65.0);
+        assert_eq!(policy.cooldown_c, 55.0);
+    }
+
+    #[test]
+    fn test_thermal_policy_clone() {
+        let policy = ThermalPolicy::aggressive();
+        let cloned = policy.clone();
+        assert_eq!(cloned.threshold_c, policy.threshold_c);
+        assert_eq!(cloned.cooldown_c, policy.cooldown_c);
+    }
+
+    #[test]
+    fn test_tegra_stats_default() {
+        let stats = TegraStats::default();
+        assert_eq!(stats.gpu_temp, 0.0);
+        assert_eq!(stats.cpu_temp, 0.0);
+        assert_eq!(stats.total_memory_mb, 0);
     }
 
     #[test]
@@ -346,6 +1630,43 @@ mod tests {
         assert_eq!(temp, 45.0); // Placeholder value
     }
 
+    #[test]
+    fn test_thermal_policy_throughput_factor() {
+        let policy = ThermalPolicy::conservative();
+        assert_eq!(policy.throughput_factor(40.0), 1.0);
+        assert_eq!(policy.throughput_factor(65.0), 1.0);
+        assert!((policy.throughput_factor(70.0) - 0.75).abs() < 0.001);
+        assert!((policy.throughput_factor(90.0) - 0.5).abs() < 0.001);
+    }
+
+    #[test]
+    fn test_adaptive_interval_shrinks_as_temperature_rises() {
+        use crate::device::mock::{self, MockExecutor};
+        use std::sync::Arc;
+
+        let lines: Vec<String> = [40.0, 58.0, 62.0, 70.0]
+            .iter()
+            .map(|t| format!("RAM 2048/7620MB CPU [5%@729] GPU@{t}C VDD_IN 4000mW/4000mW"))
+            .collect();
+        let lines: Vec<&str> = lines.iter().map(String::as_str).collect();
+        let exec = Arc::new(MockExecutor::new().on_seq("tegrastats", &lines));
+        let device = mock::device("j1", crate::JetsonModel::OrinNano8GB, &exec);
+        let mut monitor = TegraMonitor::connect(&device)
+            .unwrap()
+            .with_policy(ThermalPolicy::conservative())
+            .with_adaptive_interval(100, 2000);
+
+        // No sample yet: the policy's fixed interval
+        assert_eq!(monitor.poll_interval(), Duration::from_millis(500));
+
+        let mut intervals = Vec::new();
+        for _ in &lines {
+            monitor.sample().unwrap();
+            intervals.push(monitor.poll_interval().as_millis());
+        }
+        assert_eq!(intervals, [2000, 1430, 670, 100]);
+    }
+
     #[test]
     fn test_tegra_monitor_is_throttled() {
         let mut monitor = TegraMonitor::new();
@@ -381,21 +1702,876 @@ mod tests {
         assert_eq!(result.unwrap(), 42);
     }
 
+    #[tokio::test]
+    async fn test_circuit_breaker_guard_stream_pauses_while_hot() {
+        use crate::device::mock::MockExecutor;
+        use std::sync::Arc;
+
+        // Closed, then over threshold mid-stream until cooled to 50C
+        let lines: Vec<String> = [50.0, 70.0, 62.0, 58.0, 50.0, 50.0]
+            .into_iter()
+            .map(tegrastats_line)
+            .collect();
+        let lines: Vec<&str> = lines.iter().map(String::as_str).collect();
+        let exec = Arc::new(MockExecutor::new().on_seq("tegrastats", &lines));
+        let mut breaker = ThermalCircuitBreaker::new(cooling_monitor(&exec));
+
+        // Each token records how many samples were taken before it was produced
+        let tokens = stream::iter(1..=3).map(|token| (token, exec.count("tegrastats")));
+        let delivered: Vec<(i32, usize)> = breaker
+            .guard_stream(tokens)
+            .map(Result::unwrap)
+            .collect()
+            .await;
+        assert_eq!(delivered, [(1, 1), (2, 5), (3, 6)]);
+    }
+
+    #[tokio::test]
+    async fn test_circuit_breaker_guard_stream_ends_on_error() {
+        use crate::device::mock::MockExecutor;
+        use std::sync::Arc;
+
+        let exec = Arc::new(MockExecutor::new().fail("tegrastats", 1, "not found"));
+        let mut breaker = ThermalCircuitBreaker::new(cooling_monitor(&exec));
+        let items: Vec<Result<i32>> = breaker.guard_stream(stream::iter(1..=3)).collect().await;
+        assert_eq!(items.len(), 1);
+        assert!(items[0].is_err());
+    }
+
+    #[tokio::test]
+    async fn test_sample_failures_tagged_by_source() {
+        use crate::device::mock::MockExecutor;
+        use std::sync::Arc;
+
+        let exec = Arc::new(
+            MockExecutor::new()
+                .fail(
+                    "tegrastats",
+                    255,
+                    "ssh: connect to host j1: Connection reset",
+                )
+                .fail("thermal_zone", 1, "sh: fork failed: Resource unavailable"),
+        );
+        let device = crate::device::mock::device("j1", crate::JetsonModel::OrinNano8GB, &exec);
+
+        let mut monitor = TegraMonitor::connect(&device)
+            .unwrap()
+            .with_fields(&[TegraField::Power]);
+        let err = monitor.sample_async().await.unwrap_err();
+        assert_eq!(err.subsystem(), Some(Subsystem::TegraStats));
+        assert!(matches!(
+            err.untagged(),
+            Error::CommandFailed { exit_code: 255, .. }
+        ));
+        let err = monitor.sample().unwrap_err();
+        assert_eq!(err.subsystem(), Some(Subsystem::TegraStats));
+
+        let mut monitor = TegraMonitor::connect(&device)
+            .unwrap()
+            .with_fields(&[TegraField::GpuTemp]);
+        let err = monitor.sample_async().await.unwrap_err();
+        assert_eq!(err.subsystem(), Some(Subsystem::Thermal));
+    }
+
     #[test]
     fn test_tegra_monitor_connect() {
         use crate::device::{ConnectionMethod, DeviceInfo, JetsonDevice};
 
-        let device = JetsonDevice {
-            info: DeviceInfo {
-                id: "test".to_string(),
-                model: crate::JetsonModel::OrinNano8GB,
-                connection: ConnectionMethod::Usb,
-                jetpack_version: None,
-                hostname: None,
-            },
-        };
+        let device = JetsonDevice::new(DeviceInfo {
+            id: "test".to_string(),
+            model: crate::JetsonModel::OrinNano8GB,
+            connection: ConnectionMethod::Usb,
+            jetpack_version: None,
+            hostname: None,
+        });
 
         let monitor = TegraMonitor::connect(&device).unwrap();
         assert!(monitor.last_stats.is_none());
     }
+
+    fn gpu_sample(gpu_temp: f32) -> TegraStats {
+        TegraStats {
+            gpu_temp,
+            cpu_temp: gpu_temp - 5.0,
+            ..TegraStats::default()
+        }
+    }
+
+    #[test]
+    fn test_temp_percentiles() {
+        let mut monitor = TegraMonitor::new();
+        // Record out of order to make sure percentiles sort
+        for temp in (1..=100u8).rev() {
+            monitor.record(gpu_sample(f32::from(temp)));
+        }
+        assert_eq!(monitor.temp_p50(ThermalZone::Gpu), Some(50.0));
+        assert_eq!(monitor.temp_p95(ThermalZone::Gpu), Some(95.0));
+        assert_eq!(monitor.temp_p99(ThermalZone::Gpu), Some(99.0));
+        assert_eq!(monitor.temp_percentile(ThermalZone::Gpu, 0.0), Some(1.0));
+        assert_eq!(
+            monitor.temp_percentile(ThermalZone::Gpu, 100.0),
+            Some(100.0)
+        );
+        assert_eq!(monitor.temp_p95(ThermalZone::Cpu), Some(90.0));
+    }
+
+    #[test]
+    fn test_temp_percentile_spike() {
+        let mut monitor = TegraMonitor::new();
+        for _ in 0..95 {
+            monitor.record(gpu_sample(50.0));
+        }
+        for _ in 0..5 {
+            monitor.record(gpu_sample(80.0));
+        }
+        // Spikes hidden in the median show up at p99
+        assert_eq!(monitor.temp_p50(ThermalZone::Gpu), Some(50.0));
+        assert_eq!(monitor.temp_p95(ThermalZone::Gpu), Some(50.0));
+        assert_eq!(monitor.temp_p99(ThermalZone::Gpu), Some(80.0));
+    }
+
+    #[test]
+    fn test_temp_percentile_empty() {
+        let monitor = TegraMonitor::new();
+        assert_eq!(monitor.temp_p50(ThermalZone::Gpu), None);
+        assert_eq!(monitor.temp_percentile(ThermalZone::Soc, 95.0), None);
+    }
+
+    #[test]
+    fn test_temp_percentile_board_unreported() {
+        let mut monitor = TegraMonitor::new();
+        monitor.record(gpu_sample(50.0));
+        assert_eq!(monitor.temp_p50(ThermalZone::Board), None);
+    }
+
+    #[test]
+    fn test_history_capacity() {
+        let mut monitor = TegraMonitor::new().with_history_capacity(3);
+        for temp in [40.0, 50.0, 60.0, 70.0] {
+            monitor.record(gpu_sample(temp));
+        }
+        let temps: Vec<f32> = monitor.history().map(|s| s.gpu_temp).collect();
+        assert_eq!(temps, vec![50.0, 60.0, 70.0]);
+    }
+
+    #[test]
+    fn test_sample_records_history() {
+        let mut monitor = TegraMonitor::new();
+        monitor.sample().unwrap();
+        monitor.sample().unwrap();
+        assert_eq!(monitor.history().count(), 2);
+    }
+
+    const THERMAL_ZONES: &str = "cpu-thermal 44500
+gpu-thermal 42937
+cv0-thermal 41000
+soc0-thermal 43281
+soc1-thermal 42100
+tj-thermal 44500
+";
+
+    const ORIN_NANO_LINE: &str = "RAM 2448/7620MB (lfb 1148x4MB) SWAP 0/3810MB (cached 0MB) \
+        CPU [2%@729,4%@729,0%@729,2%@729,off,off] EMC_FREQ 0% GR3D_FREQ 37% \
+        CV0@-256C CPU@45.656C Tboard@34C SOC2@42.781C Tdiode@36.5C SOC0@43.281C \
+        CV1@-256C GPU@42.937C tj@45.656C SOC1@42.5C CV2@-256C \
+        VDD_IN 4825mW/4825mW VDD_CPU_GPU_CV 483mW/483mW VDD_SOC 1448mW/1448mW";
+
+    fn tegrastats_line(gpu_temp: f32) -> String {
+        format!("RAM 2048/7620MB CPU [10%@1510,10%@1510] GR3D_FREQ 99% CPU@50C GPU@{gpu_temp}C VDD_IN 9000mW/8000mW")
+    }
+
+    /// Synthetic step response sampled every 10s for 300s.
+    fn exponential_curve(start: f32, end: f32, tau_secs: f32) -> Vec<(Duration, f32)> {
+        (0..=30u8)
+            .map(|i| {
+                let t = f32::from(i) * 10.0;
+                (
+                    Duration::from_secs_f32(t),
+                    (start - end).mul_add((-t / tau_secs).exp(), end),
+                )
+            })
+            .collect()
+    }
+
+    #[test]
+    fn test_parse_tegrastats_orin_nano() {
+        let stats = parse_tegrastats(ORIN_NANO_LINE).unwrap();
+        assert!((stats.gpu_temp - 42.937).abs() < 0.001);
+        assert!((stats.cpu_temp - 45.656).abs() < 0.001);
+        assert!((stats.soc_temp - 43.281).abs() < 0.001);
+        assert_eq!(stats.used_memory_mb, 2448);
+        assert_eq!(stats.total_memory_mb, 7620);
+        assert_eq!(stats.available_memory_mb, 5172);
+        assert!((stats.gpu_utilization - 37.0).abs() < 0.001);
+        assert!((stats.cpu_utilization - 2.0).abs() < 0.001);
+        assert!((stats.power_watts - 4.825).abs() < 0.001);
+    }
+
+    #[test]
+    fn test_parse_tegrastats_sums_rails_without_vdd_in() {
+        let line = "RAM 4000/30536MB GPU@50C VDD_GPU_SOC 3000mW/3000mW VDD_CPU_CV 1000mW/1000mW";
+        let stats = parse_tegrastats(line).unwrap();
+        assert!((stats.power_watts - 4.0).abs() < 0.001);
+    }
+
+    #[test]
+    fn test_parse_tegrastats_missing_gpu() {
+        assert!(parse_tegrastats("RAM 2448/7620MB CPU@45C").is_err());
+    }
+
+    #[test]
+    fn test_parse_tegrastats_selected_fields() {
+        let stats = parse_tegrastats_fields(ORIN_NANO_LINE, &[TegraField::GpuTemp]).unwrap();
+        assert!((stats.gpu_temp - 42.937).abs() < 0.001);
+        assert_eq!(stats.cpu_temp, 0.0);
+        assert_eq!(stats.soc_temp, 0.0);
+        assert_eq!(stats.total_memory_mb, 0);
+        assert_eq!(stats.gpu_utilization, 0.0);
+        assert_eq!(stats.cpu_utilization, 0.0);
+        assert_eq!(stats.power_watts, 0.0);
+
+        let fields = [TegraField::Memory, TegraField::Power];
+        let stats = parse_tegrastats_fields("RAM 2448/7620MB CPU@45C", &fields).unwrap();
+        assert_eq!(stats.used_memory_mb, 2448);
+        assert_eq!(stats.cpu_temp, 0.0);
+    }
+
+    #[test]
+    fn test_monitor_with_fields() {
+        use crate::device::mock::{self, MockExecutor};
+        use std::sync::Arc;
+
+        let exec = Arc::new(
+            MockExecutor::new()
+                .on("thermal_zone", THERMAL_ZONES)
+                .on("tegrastats", ORIN_NANO_LINE),
+        );
+        let device = mock::device("j1", crate::JetsonModel::OrinNano8GB, &exec);
+        let mut monitor = TegraMonitor::connect(&device)
+            .unwrap()
+            .with_fields(&[TegraField::GpuTemp, TegraField::SocTemp]);
+        let stats = monitor.sample().unwrap();
+        assert!((stats.gpu_temp - 42.937).abs() < 0.001);
+        assert!((stats.soc_temp - 43.281).abs() < 0.001);
+        assert_eq!(stats.cpu_temp, 0.0);
+        assert_eq!(stats.used_memory_mb, 0);
+        assert_eq!(stats.power_watts, 0.0);
+        // Temperatures alone come from sysfs, without running tegrastats
+        assert_eq!(exec.count("tegrastats"), 0);
+        assert!(exec.calls()[0].contains("thermal_zone"));
+
+        // Power needs tegrastats
+        let stats = TegraMonitor::connect(&device)
+            .unwrap()
+            .with_fields(&[TegraField::Power])
+            .sample()
+            .unwrap();
+        assert!(stats.power_watts > 0.0);
+        assert_eq!(exec.count("tegrastats"), 1);
+
+        // The GPU temperature the breaker reads is always kept
+        let stats = TegraMonitor::new()
+            .with_fields(&[TegraField::CpuTemp])
+            .sample()
+            .unwrap();
+        assert!(stats.gpu_temp > 0.0);
+        assert!(stats.cpu_temp > 0.0);
+        assert_eq!(stats.soc_temp, 0.0);
+    }
+
+    #[test]
+    fn test_monitor_with_fields_reads_meminfo() {
+        use crate::device::mock::{self, MockExecutor};
+        use std::sync::Arc;
+
+        let output =
+            format!("{THERMAL_ZONES}MemTotal:        7802832 kB\nMemAvailable:    5242880 kB\n");
+        let exec = Arc::new(MockExecutor::new().on("thermal_zone", &output));
+        let device = mock::device("j1", crate::JetsonModel::OrinNano8GB, &exec);
+        let mut monitor = TegraMonitor::connect(&device)
+            .unwrap()
+            .with_fields(&[TegraField::Memory]);
+        let stats = monitor.sample().unwrap();
+        assert_eq!(stats.total_memory_mb, 7619);
+        assert_eq!(stats.available_memory_mb, 5120);
+        assert_eq!(stats.used_memory_mb, 2499);
+        assert!((stats.gpu_temp - 42.937).abs() < 0.001);
+        assert_eq!(stats.cpu_temp, 0.0);
+        assert!(exec.calls()[0].contains("/proc/meminfo"));
+    }
+
+    #[test]
+    fn test_parse_thermal_zones_requires_gpu_zone() {
+        let stats = parse_thermal_zones(THERMAL_ZONES, &TegraField::ALL).unwrap();
+        assert!((stats.cpu_temp - 44.5).abs() < 0.001);
+        assert!((stats.soc_temp - 43.281).abs() < 0.001);
+        assert!(parse_thermal_zones("cpu-thermal 44500\n", &[TegraField::GpuTemp]).is_err());
+        assert!(parse_thermal_zones("cpu-thermal 44500\n", &[TegraField::CpuTemp]).is_ok());
+    }
+
+    #[test]
+    fn test_connected_monitor_samples_device() {
+        use crate::device::mock::{self, MockExecutor};
+        use std::sync::Arc;
+
+        let exec = Arc::new(MockExecutor::new().on("tegrastats", ORIN_NANO_LINE));
+        let device = mock::device("j1", crate::JetsonModel::OrinNano8GB, &exec);
+        let mut monitor = TegraMonitor::connect(&device).unwrap();
+        let temp = monitor.gpu_temp().unwrap();
+        assert!((temp - 42.937).abs() < 0.001);
+        assert_eq!(exec.count("tegrastats"), 1);
+    }
+
+    #[tokio::test]
+    async fn test_sample_async_records_history() {
+        use crate::device::mock::{self, MockExecutor};
+        use std::sync::Arc;
+
+        let exec = Arc::new(MockExecutor::new().on("tegrastats", ORIN_NANO_LINE));
+        let device = mock::device("j1", crate::JetsonModel::OrinNano8GB, &exec);
+        let mut monitor = TegraMonitor::connect(&device).unwrap();
+        let stats = monitor.sample_async().await.unwrap();
+        assert!((stats.gpu_temp - 42.937).abs() < 0.001);
+        assert_eq!(monitor.history().count(), 1);
+        assert!((monitor.gpu_temp_async().await.unwrap() - 42.937).abs() < 0.001);
+        assert_eq!(exec.count("tegrastats"), 2);
+    }
+
+    #[test]
+    fn test_thermal_model_fit_exponential() {
+        let heating = exponential_curve(40.0, 80.0, 45.0);
+        let cooling = exponential_curve(80.0, 38.0, 60.0);
+        let model = ThermalModel::fit(&heating, &cooling).unwrap();
+
+        let tau_heat = model.tau_heat.as_secs_f32();
+        let tau_cool = model.tau_cool.as_secs_f32();
+        assert!((tau_heat - 45.0).abs() < 4.5, "tau_heat = {tau_heat}");
+        assert!((tau_cool - 60.0).abs() < 6.0, "tau_cool = {tau_cool}");
+        assert!((model.load_steady - 80.0).abs() < 1.0);
+        assert!((model.ambient - 38.0).abs() < 1.0);
+    }
+
+    #[test]
+    fn test_thermal_model_fit_flat_curve() {
+        let flat: Vec<(Duration, f32)> =
+            (0..10u64).map(|i| (Duration::from_secs(i), 45.0)).collect();
+        let cooling = exponential_curve(80.0, 38.0, 60.0);
+        assert!(ThermalModel::fit(&flat, &cooling).is_err());
+        assert!(ThermalModel::fit(&[], &cooling).is_err());
+    }
+
+    #[test]
+    fn test_thermal_model_predict() {
+        let model = ThermalModel {
+            tau_heat: Duration::from_secs(60),
+            tau_cool: Duration::from_secs(120),
+            ambient: 35.0,
+            load_steady: 85.0,
+        };
+        // One time constant covers 63% of the gap
+        let heated = model.predict_temp(45.0, true, Duration::from_secs(60));
+        assert!((heated - (-40.0f32).mul_add((-1.0f32).exp(), 85.0)).abs() < 0.01);
+        // Exponential, not linear: never overshoots the steady state
+        assert!(model.predict_temp(45.0, true, Duration::from_secs(3600)) <= 85.0);
+        let cooled = model.predict_temp(70.0, false, Duration::from_secs(120));
+        assert!((cooled - 35.0f32.mul_add((-1.0f32).exp(), 35.0)).abs() < 0.01);
+    }
+
+    #[test]
+    fn test_thermal_model_time_to_reach() {
+        let model = ThermalModel {
+            tau_heat: Duration::from_secs(60),
+            tau_cool: Duration::from_secs(120),
+            ambient: 35.0,
+            load_steady: 85.0,
+        };
+        let t = model.time_to_reach(45.0, 75.0).unwrap();
+        let predicted = model.predict_temp(45.0, true, t);
+        assert!((predicted - 75.0).abs() < 0.1);
+        assert_eq!(model.time_to_reach(80.0, 75.0), Some(Duration::ZERO));
+        assert_eq!(model.time_to_reach(45.0, 90.0), None);
+    }
+
+    #[tokio::test]
+    async fn test_thermal_model_calibrate() {
+        use crate::device::mock::{self, MockExecutor};
+        use std::sync::Arc;
+
+        let heating = exponential_curve(40.0, 80.0, 45.0);
+        let cooling = exponential_curve(80.0, 38.0, 60.0);
+        let lines: Vec<String> = heating
+            .iter()
+            .chain(cooling.iter())
+            .map(|&(_, temp)| tegrastats_line(temp))
+            .collect();
+        let lines: Vec<&str> = lines.iter().map(String::as_str).collect();
+        let exec = Arc::new(
+            MockExecutor::new()
+                .on("nvpmodel -q", "NV Power Mode: 15W\n1\n")
+                .on_seq("tegrastats", &lines),
+        );
+        let device = mock::device("j1", crate::JetsonModel::OrinNano8GB, &exec);
+        let mut monitor = TegraMonitor::connect(&device).unwrap();
+
+        let model = ThermalModel::calibrate_for(
+            &mut monitor,
+            &PowerProfile::max_performance(),
+            Duration::from_millis(30),
+        )
+        .await
+        .unwrap();
+
+        assert!(model.load_steady > model.ambient);
+        assert!(exec.count("sudo nvpmodel -m 0") == 1);
+        assert!(exec.count("stress-ng") == 1);
+        assert_eq!(exec.calls().last().unwrap(), "sudo nvpmodel -m 1");
+    }
+
+    #[tokio::test]
+    async fn test_thermal_model_calibrate_restores_mode_on_error() {
+        use crate::device::mock::{self, MockExecutor};
+        use std::sync::Arc;
+
+        let exec = Arc::new(
+            MockExecutor::new()
+                .on("nvpmodel -q", "NV Power Mode: 7W\n2\n")
+                .fail("stress-ng", 127, "stress-ng: command not found"),
+        );
+        let device = mock::device("j1", crate::JetsonModel::OrinNano8GB, &exec);
+        let mut monitor = TegraMonitor::connect(&device).unwrap();
+
+        let result = ThermalModel::calibrate_for(
+            &mut monitor,
+            &PowerProfile::max_performance(),
+            Duration::from_millis(30),
+        )
+        .await;
+
+        assert_eq!(result.unwrap_err().subsystem(), Some(Subsystem::Thermal));
+        let modes: Vec<String> = exec
+            .calls()
+            .into_iter()
+            .filter(|c| c.starts_with("sudo nvpmodel -m"))
+            .collect();
+        assert_eq!(modes, ["sudo nvpmodel -m 0", "sudo nvpmodel -m 2"]);
+    }
+
+    #[tokio::test]
+    async fn test_thermal_model_calibrate_requires_device() {
+        let mut monitor = TegraMonitor::new();
+        let result = ThermalModel::calibrate_for(
+            &mut monitor,
+            &PowerProfile::balanced(),
+            Duration::from_millis(1),
+        )
+        .await;
+        assert!(result.is_err());
+    }
+
+    const ORIN_TRIP_POINTS: &str = "\
+/sys/class/thermal/thermal_zone0/type:cpu-thermal
+/sys/class/thermal/thermal_zone1/type:gpu-thermal
+/sys/class/thermal/thermal_zone0/trip_point_0_temp:-40000
+/sys/class/thermal/thermal_zone0/trip_point_1_temp:99000
+/sys/class/thermal/thermal_zone0/trip_point_2_temp:103000
+/sys/class/thermal/thermal_zone1/trip_point_0_temp:99000
+/sys/class/thermal/thermal_zone1/trip_point_1_temp:103000
+/sys/class/thermal/thermal_zone0/trip_point_0_hyst:0
+/sys/class/thermal/thermal_zone0/trip_point_1_hyst:8000
+/sys/class/thermal/thermal_zone0/trip_point_2_hyst:0
+/sys/class/thermal/thermal_zone1/trip_point_0_hyst:8000
+/sys/class/thermal/thermal_zone1/trip_point_1_hyst:0
+/sys/class/thermal/thermal_zone0/trip_point_0_type:active
+/sys/class/thermal/thermal_zone0/trip_point_1_type:passive
+/sys/class/thermal/thermal_zone0/trip_point_2_type:critical
+/sys/class/thermal/thermal_zone1/trip_point_0_type:passive
+/sys/class/thermal/thermal_zone1/trip_point_1_type:critical
+";
+
+    /// [`ORIN_TRIP_POINTS`] as a device returns them to a sysfs glob read.
+    fn orin_trip_point_records() -> String {
+        ORIN_TRIP_POINTS
+            .lines()
+            .filter_map(|line| line.split_once(':'))
+            .fold(String::new(), |records, (path, value)| {
+                records + path + "\x1f" + value + "\n\x1e"
+            })
+    }
+
+    #[test]
+    fn test_parse_trip_points() {
+        let trips = parse_trip_points(ORIN_TRIP_POINTS);
+        assert_eq!(trips.len(), 5);
+        assert_eq!(
+            trips[1],
+            TripPoint {
+                zone: 0,
+                zone_type: "cpu-thermal".to_string(),
+                index: 1,
+                kind: TripType::Passive,
+                temp_c: 99.0,
+                hyst_c: 8.0,
+            }
+        );
+        assert_eq!(trips[0].temp_c, -40.0);
+        assert_eq!(trips[0].kind, TripType::Active);
+        assert_eq!(trips[4].zone_type, "gpu-thermal");
+        assert_eq!(trips[4].kind, TripType::Critical);
+    }
+
+    #[test]
+    fn test_parse_trip_points_partial() {
+        let output = "/sys/class/thermal/thermal_zone3/trip_point_0_type:passive\n\
+                      /sys/class/thermal/thermal_zone3/trip_point_1_temp:95500\n\
+                      garbage line\n";
+        let trips = parse_trip_points(output);
+        assert_eq!(trips.len(), 1);
+        assert_eq!(trips[0].index, 1);
+        assert_eq!(trips[0].kind, TripType::Other);
+        assert!((trips[0].temp_c - 95.5).abs() < 0.001);
+        assert!(trips[0].zone_type.is_empty());
+        assert!(parse_trip_points("").is_empty());
+    }
+
+    #[test]
+    fn test_trip_type_from_sysfs() {
+        assert_eq!(TripType::from_sysfs("passive\n"), TripType::Passive);
+        assert_eq!(TripType::from_sysfs("hot"), TripType::Hot);
+        assert_eq!(TripType::from_sysfs("bogus"), TripType::Other);
+    }
+
+    #[test]
+    fn test_policy_from_trip_points() {
+        let trips = parse_trip_points(ORIN_TRIP_POINTS);
+        let policy = ThermalPolicy::from_trip_points(&trips, 15.0).unwrap();
+        assert_eq!(policy.threshold_c, 84.0);
+        assert_eq!(policy.cooldown_c, 74.0);
+
+        let critical_only: Vec<TripPoint> = trips
+            .into_iter()
+            .filter(|t| t.kind == TripType::Critical)
+            .collect();
+        assert!(ThermalPolicy::from_trip_points(&critical_only, 15.0).is_none());
+    }
+
+    #[tokio::test]
+    async fn test_hw_trip_points_cached() {
+        use crate::device::mock::{self, MockExecutor};
+        use std::sync::Arc;
+
+        let exec = Arc::new(MockExecutor::new().on("trip_point", &orin_trip_point_records()));
+        let device = mock::device("j1", crate::JetsonModel::OrinNano8GB, &exec);
+        let mut monitor = TegraMonitor::connect(&device).unwrap();
+        assert_eq!(monitor.hw_trip_points().await.unwrap().len(), 5);
+        assert_eq!(monitor.hw_trip_points().await.unwrap().len(), 5);
+        assert_eq!(exec.count("trip_point"), 1);
+
+        assert!(TegraMonitor::new().hw_trip_points().await.is_err());
+    }
+
+    fn cooling_monitor(exec: &std::sync::Arc<crate::device::mock::MockExecutor>) -> TegraMonitor {
+        let device = crate::device::mock::device("j1", crate::JetsonModel::OrinNano8GB, exec);
+        TegraMonitor::connect(&device)
+            .unwrap()
+            .with_policy(ThermalPolicy::custom(65.0, 55.0, 1))
+    }
+
+    #[tokio::test]
+    async fn test_cooldown_throttle_restores_mode() {
+        use crate::device::mock::MockExecutor;
+        use std::sync::Arc;
+
+        let lines = [
+            tegrastats_line(70.0),
+            tegrastats_line(60.0),
+            tegrastats_line(50.0),
+        ];
+        let lines: Vec<&str> = lines.iter().map(String::as_str).collect();
+        let exec = Arc::new(
+            MockExecutor::new()
+                .on_seq("tegrastats", &lines)
+                .on("nvpmodel -q", "NV Power Mode: MAXN\n0\n"),
+        );
+        let mut monitor = cooling_monitor(&exec);
+
+        monitor
+            .wait_for_cooldown_with(CooldownStrategy::PollAndThrottle)
+            .await
+            .unwrap();
+
+        let calls: Vec<String> = exec
+            .calls()
+            .into_iter()
+            .filter(|c| c.starts_with("sudo nvpmodel"))
+            .collect();
+        assert_eq!(calls, vec!["sudo nvpmodel -m 2", "sudo nvpmodel -m 0"]);
+        assert_eq!(exec.count("tegrastats"), 3);
+    }
+
+    #[tokio::test]
+    async fn test_cooldown_fan_boost_restores_pwm() {
+        use crate::device::mock::MockExecutor;
+        use std::sync::Arc;
+
+        let lines = [tegrastats_line(70.0), tegrastats_line(50.0)];
+        let lines: Vec<&str> = lines.iter().map(String::as_str).collect();
+        let exec = Arc::new(
+            MockExecutor::new()
+                .on_seq("tegrastats", &lines)
+                .on("cat /sys/devices/platform/pwm-fan", "90\n"),
+        );
+        let mut monitor = cooling_monitor(&exec);
+
+        monitor
+            .wait_for_cooldown_with(CooldownStrategy::PollAndFanBoost)
+            .await
+            .unwrap();
+
+        let writes: Vec<String> = exec
+            .calls()
+            .into_iter()
+            .filter(|c| c.contains("sudo tee"))
+            .collect();
+        assert_eq!(writes.len(), 2);
+        assert!(writes[0].starts_with("echo 255 |"));
+        assert!(writes[1].starts_with("echo 90 |"));
+    }
+
+    #[tokio::test]
+    async fn test_cooldown_already_cool_skips_strategy() {
+        use crate::device::mock::MockExecutor;
+        use std::sync::Arc;
+
+        let exec = Arc::new(MockExecutor::new().on("tegrastats", &tegrastats_line(40.0)));
+        let mut monitor = cooling_monitor(&exec);
+        monitor
+            .wait_for_cooldown_with(CooldownStrategy::PollAndThrottle)
+            .await
+            .unwrap();
+        assert_eq!(exec.calls().len(), 1);
+    }
+
+    fn soak_executor(
+        temps: &[f32],
+        fan: &[&str],
+    ) -> std::sync::Arc<crate::device::mock::MockExecutor> {
+        use crate::device::mock::MockExecutor;
+        use std::sync::Arc;
+
+        let lines: Vec<String> = temps.iter().map(|t| tegrastats_line(*t)).collect();
+        let lines: Vec<&str> = lines.iter().map(String::as_str).collect();
+        Arc::new(
+            MockExecutor::new()
+                .on("trip_point", &orin_trip_point_records())
+                .on_seq("tegrastats", &lines)
+                .on_seq("pwm1", fan),
+        )
+    }
+
+    #[tokio::test]
+    async fn test_thermal_soak_passes() {
+        let exec = soak_executor(&[50.0, 62.0, 71.0, 74.0], &["80", "80", "140", "200"]);
+        let device = crate::device::mock::device("j1", crate::JetsonModel::OrinNano8GB, &exec);
+
+        let result = device
+            .thermal_soak(Duration::from_millis(20))
+            .await
+            .unwrap();
+
+        assert!(result.passed());
+        assert!(!result.throttled);
+        assert!((result.peak_temp_c - 74.0).abs() < 0.001);
+        assert!((result.throttle_c - 99.0).abs() < 0.001);
+        assert!((result.headroom_c() - 25.0).abs() < 0.001);
+        assert_eq!(result.fan_start_pwm, Some(80));
+        assert_eq!(result.fan_peak_pwm, Some(200));
+        assert_eq!(result.fan_responded(), Some(true));
+        assert_eq!(exec.count("nvpmodel -m 0"), 1);
+        assert_eq!(exec.count("stress-ng"), 1);
+    }
+
+    #[tokio::test]
+    async fn test_thermal_soak_overheats() {
+        let exec = soak_executor(&[60.0, 85.0, 97.0, 101.5], &["255"]);
+        let device = crate::device::mock::device("j1", crate::JetsonModel::OrinNano8GB, &exec);
+
+        let result = device
+            .thermal_soak(Duration::from_millis(20))
+            .await
+            .unwrap();
+
+        assert!(result.throttled);
+        assert!(!result.passed());
+        assert_eq!(result.fan_responded(), Some(true));
+        assert!(result.headroom_c() < 0.0);
+    }
+
+    #[tokio::test]
+    async fn test_benchmark_heavy_model_throttles_partway() {
+        // Climbs 4°C per sample from 55°C, crossing the 99°C trip at sample 11
+        let lines: Vec<String> = (0u8..20)
+            .map(|i| tegrastats_line(f32::from(i).mul_add(4.0, 55.0)))
+            .collect();
+        let lines: Vec<&str> = lines.iter().map(String::as_str).collect();
+        let exec = std::sync::Arc::new(
+            crate::device::mock::MockExecutor::new()
+                .on("trip_point", &orin_trip_point_records())
+                .on_seq("tegrastats", &lines)
+                .on("nohup", "4242 /tmp/cohete-bench.a1B2c3\n")
+                // Still running when sampling ends, then exits
+                .on_seq("ps -o stat=", &["R\n", "S\n", ""])
+                .on("tail -n 1", "12.5\n"),
+        );
+        let device = crate::device::mock::device("j1", crate::JetsonModel::OrinNano8GB, &exec);
+
+        let result = device
+            .benchmark("llama-bench -m heavy.gguf", Duration::from_millis(20))
+            .await
+            .unwrap();
+        assert_eq!(exec.count("ps -o stat= -p 4242"), 3);
+        let calls = exec.calls();
+        let read = calls.iter().position(|c| c.contains("tail -n 1")).unwrap();
+        let exited = calls
+            .iter()
+            .rposition(|c| c.contains("ps -o stat="))
+            .unwrap();
+        assert!(read > exited);
+        assert!(calls[read].contains("'/tmp/cohete-bench.a1B2c3'"));
+
+        assert!(result.throttled);
+        assert!(!result.sustainable());
+        assert_eq!(result.time_to_throttle, Some(Duration::from_millis(11)));
+        assert_eq!(result.tokens_per_sec, Some(12.5));
+        assert!((result.peak_temp - 131.0).abs() < 0.001);
+        assert!(result.temp_rise_rate > 0.0);
+        assert_eq!(exec.count("llama-bench"), 1);
+    }
+
+    #[test]
+    fn test_bench_result_light_model_sustainable() {
+        let samples: Vec<(Duration, f32)> = [50.0, 58.0, 63.0, 65.0, 66.0, 66.0, 65.0, 66.0]
+            .iter()
+            .zip(0u64..)
+            .map(|(t, i)| (Duration::from_secs(i * 10), *t))
+            .collect();
+
+        let result = BenchResult::from_samples(Duration::from_secs(80), None, &samples, 99.0);
+
+        assert!(result.sustainable());
+        assert_eq!(result.time_to_throttle, None);
+        assert!((result.peak_temp - 66.0).abs() < 0.001);
+        assert!((result.steady_state_temp - 65.5).abs() < 0.001);
+        assert!((result.temp_rise_rate - 0.4).abs() < 0.001);
+    }
+
+    #[test]
+    fn test_soak_result_stuck_fan_fails() {
+        let result = SoakResult {
+            duration: Duration::from_secs(60),
+            peak_temp_c: 80.0,
+            throttle_c: 99.0,
+            throttled: false,
+            fan_start_pwm: Some(0),
+            fan_peak_pwm: Some(0),
+        };
+        assert_eq!(result.fan_responded(), Some(false));
+        assert!(!result.passed());
+
+        let passive = SoakResult {
+            fan_start_pwm: None,
+            fan_peak_pwm: None,
+            ..result
+        };
+        assert_eq!(passive.fan_responded(), None);
+        assert!(passive.passed());
+    }
+
+    #[test]
+    fn test_rate_limiter_tracks_rising_temperature() {
+        let limiter = ThermalRateLimiter::new(70.0, 0.5, 20.0);
+        assert!((limiter.rate() - 20.0).abs() < 1e-9);
+
+        // Cool device stays at the ceiling
+        assert!((limiter.observe(55.0) - 20.0).abs() < 1e-9);
+
+        // Temperature climbs under load: the rate falls once past target
+        let rates: Vec<f64> = [68.0, 71.0, 74.0, 78.0, 83.0]
+            .iter()
+            .map(|t| limiter.observe(*t))
+            .collect();
+        assert!(rates.windows(2).skip(1).all(|w| w[1] < w[0]), "{rates:?}");
+        assert!(rates[4] < 10.0);
+
+        // Pinned at the floor however hot it gets
+        for _ in 0..50 {
+            limiter.observe(100.0);
+        }
+        assert!((limiter.rate() - 0.5).abs() < 1e-9);
+
+        // Recovers when the device cools
+        let before = limiter.rate();
+        assert!(limiter.observe(60.0) > before);
+    }
+
+    #[tokio::test]
+    async fn test_rate_limiter_acquire_spacing() {
+        let limiter = ThermalRateLimiter::new(70.0, 1.0, 200.0);
+        let start = tokio::time::Instant::now();
+        for _ in 0..5 {
+            limiter.acquire().await;
+        }
+        // First request is immediate, then one every 5ms
+        assert!(start.elapsed() >= Duration::from_millis(20));
+    }
+
+    #[test]
+    fn test_duty_cycle_enforces_ratio() {
+        let mut duty = ThermalDutyCycle::new(Duration::from_secs(10), Duration::from_secs(30));
+        assert!((duty.ratio() - 0.25).abs() < 1e-9);
+
+        // Scheduler checks every second for 400 simulated seconds
+        let start = Instant::now();
+        let mut ran = 0u32;
+        for tick in 0..400 {
+            if duty.can_run_at(start + Duration::from_secs(tick)) {
+                ran += 1;
+            }
+        }
+        assert_eq!(ran, 100);
+
+        // 400s is ten full periods, so the next active window opens
+        assert!(duty.can_run_at(start + Duration::from_secs(400)));
+        let now = start + Duration::from_secs(410);
+        assert!(!duty.can_run_at(now));
+        assert_eq!(duty.rest_remaining_at(now), Duration::from_secs(30));
+    }
+
+    #[test]
+    fn test_duty_cycle_late_check_extends_rest() {
+        let mut duty = ThermalDutyCycle::new(Duration::from_secs(10), Duration::from_secs(30));
+        let start = Instant::now();
+        assert!(duty.can_run_at(start));
+        // Work overran the budget by half before the next check
+        let late = start + Duration::from_secs(15);
+        assert!(!duty.can_run_at(late));
+        assert_eq!(duty.rest_remaining_at(late), Duration::from_secs(45));
+        assert!(duty.can_run_at(late + Duration::from_secs(45)));
+        assert_eq!(duty.hot_time(), Duration::ZERO);
+    }
+
+    #[test]
+    fn test_duty_cycle_idle_gap_resets_budget() {
+        let mut duty = ThermalDutyCycle::new(Duration::from_secs(10), Duration::from_secs(30));
+        let start = Instant::now();
+        assert!(duty.can_run_at(start));
+        duty.stop_at(start + Duration::from_secs(8));
+        assert_eq!(duty.hot_time(), Duration::from_secs(8));
+
+        // A short pause keeps the debt; a rest-length pause clears it
+        assert!(duty.can_run_at(start + Duration::from_secs(9)));
+        duty.stop_at(start + Duration::from_secs(10));
+        assert_eq!(duty.hot_time(), Duration::from_secs(9));
+        assert!(duty.can_run_at(start + Duration::from_secs(40)));
+        assert_eq!(duty.hot_time(), Duration::ZERO);
+    }
 }